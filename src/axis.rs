@@ -0,0 +1,151 @@
+//! Shared Axis
+//! Many tables of a system are usually sampled at the same breakpoints, eg. a set of engine maps
+//! which all depend on the engine speed. An [`Axis`] validates such breakpoints once, and can then be
+//! referenced by any number of 1D and 2D tables, similar to a COM_AXIS in ASAM terms.
+//! Since the tables on an axis share their breakpoints, the search for the segment containing an index
+//! can be done once with [`Axis::search`], and the resulting [`AxisPosition`] reused for the lookups in
+//! all of the tables.
+//!
+//! Alternatively, the search can be split from the interpolation altogether, like the Prelookup block of
+//! Simulink: [`Axis::prelookup`] returns an [`IndexFraction`], which any table with the same
+//! breakpoints can interpolate with, without access to the axis.
+
+use crate::oned_lut::are_breakpoints_valid;
+use crate::ConstructionError;
+use num::Float;
+
+/// Validated breakpoints, which can be shared by several look up tables.
+/// ```
+///  use look_up_table::{Axis, OneDLookUpTableRef, Interpolation};
+///  let speed = Axis::new(&[1000.0f64, 2000.0, 3000.0]).unwrap();
+///  let torque = OneDLookUpTableRef::from_axis(&speed, &[100.0, 180.0, 150.0], Interpolation::Linear).unwrap();
+///  let power = OneDLookUpTableRef::from_axis(&speed, &[10.0, 38.0, 47.0], Interpolation::Linear).unwrap();
+///
+///  let position = speed.search(2500.0);
+///  assert!((torque.get_at(&position) - 165.0).abs() < 1e-12);
+///  assert!((power.get_at(&position) - 42.5).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Axis<'a, T = f64> {
+    breakpoints: &'a [T],
+}
+
+impl<'a, T: Float> Axis<'a, T> {
+    /// Constructs an `Axis` object, after validating the breakpoints with the same rules as the x
+    /// values of a 1D table, i.e. at least two finite values in increasing order.
+    /// ```
+    ///  use look_up_table::{Axis, ConstructionError};
+    ///  let axis = Axis::new(&[1.0, 3.0, 2.0]);
+    ///  assert!(matches!(axis.err().unwrap(), ConstructionError::IncreasingDimOrderError))
+    /// ```
+    pub fn new(breakpoints: &'a [T]) -> Result<Axis<'a, T>, ConstructionError> {
+        are_breakpoints_valid(breakpoints).map(|_| Axis { breakpoints })
+    }
+
+    /// Returns the breakpoints of the axis.
+    pub fn breakpoints(&self) -> &'a [T] {
+        self.breakpoints
+    }
+
+    /// Searches for the segment of the axis containing the index `x`. The result can be passed to the
+    /// `get_at` methods of all the tables on this axis, which then skip their own search.
+    pub fn search(&self, x: T) -> AxisPosition<T> {
+        let (lower, upper) = search(x, self.breakpoints);
+
+        AxisPosition { x, lower, upper }
+    }
+
+    /// Returns the segment of the axis containing the index `x`, along with the position of `x` within
+    /// it. The fraction is not clamped, so that it is negative below the first breakpoint, and greater
+    /// than one above the last breakpoint.
+    /// ```
+    ///  use look_up_table::{Axis, IndexFraction};
+    ///  let axis = Axis::new(&[0.0, 10.0, 20.0, 40.0]).unwrap();
+    ///  assert_eq!(axis.prelookup(25.0), IndexFraction { index: 2, fraction: 0.25 });
+    ///  assert_eq!(axis.prelookup(40.0), IndexFraction { index: 2, fraction: 1.0 });
+    ///  assert_eq!(axis.prelookup(-5.0), IndexFraction { index: 0, fraction: -0.5 });
+    /// ```
+    pub fn prelookup(&self, x: T) -> IndexFraction<T> {
+        let vs = self.breakpoints;
+        let (lower, upper) = search(x, vs);
+
+        if lower != upper {
+            IndexFraction {
+                index: lower,
+                fraction: (x - vs[lower]) / (vs[upper] - vs[lower]),
+            }
+        } else if lower == vs.len() - 1 {
+            IndexFraction {
+                index: lower - 1,
+                fraction: T::one(),
+            }
+        } else {
+            IndexFraction {
+                index: lower,
+                fraction: T::zero(),
+            }
+        }
+    }
+}
+
+/// The result of a prelookup of an index on an [`Axis`], which is the segment between the breakpoints
+/// `index` and `index + 1` containing it, and its position `fraction` within the segment, normalized to
+/// `[0, 1]`. The positions outside the range of the breakpoints have a fraction outside `[0, 1]` on the
+/// first or the last segment, and the tables apply their extrapolation policies to them.
+/// The tables interpolating with an `IndexFraction` are expected to have the same breakpoints as the
+/// axis of the prelookup, since they use the `index` as is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IndexFraction<T = f64> {
+    pub index: usize,
+    pub fraction: T,
+}
+
+/// The result of searching an index on an [`Axis`]. It holds the index along with the indices of the
+/// breakpoints on either side of it, which are the same when the index lies exactly on a breakpoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisPosition<T = f64> {
+    x: T,
+    lower: usize,
+    upper: usize,
+}
+
+impl<T: Float> AxisPosition<T> {
+    /// Returns the index, which was searched on the axis.
+    pub fn value(&self) -> T {
+        self.x
+    }
+
+    /// Returns the indices of the breakpoints around `x` from the search result, if they are valid for
+    /// the breakpoints `vs`. Otherwise, i.e. when the position is from a different axis, or the index was
+    /// changed by an extrapolation policy, the breakpoints are searched again.
+    pub(crate) fn locate(position: Option<&AxisPosition<T>>, x: T, vs: &[T]) -> (usize, usize) {
+        match position {
+            Some(&AxisPosition { lower, upper, .. })
+                if upper < vs.len()
+                    && ((lower == upper && vs[lower] == x)
+                        || (lower + 1 == upper && vs[lower] < x && x < vs[upper])) =>
+            {
+                (lower, upper)
+            }
+            _ => search(x, vs),
+        }
+    }
+}
+
+/// Given an index value, find the lower and upper bound indices of the segment containing it.
+/// If the index is out of bounds, return the indices of the boundary segment, which is continued for the
+/// linear extrapolation. If the index directly matches the values present in the array, then return the
+/// same value as lower and upper bounds.
+#[inline]
+pub(crate) fn search<T: Float>(v: T, vs: &[T]) -> (usize, usize) {
+    if v.is_nan() || v < vs[0] {
+        (0, 1)
+    } else if v > vs[vs.len() - 1] {
+        (vs.len() - 2, vs.len() - 1)
+    } else {
+        match vs.binary_search_by(|val| val.partial_cmp(&v).unwrap()) {
+            Ok(ind) => (ind, ind),
+            Err(ind) => (ind - 1, ind),
+        }
+    }
+}
@@ -0,0 +1,409 @@
+//! Caching
+//! The tables remember the values of the previous lookups, to return them without an interpolation when
+//! the same index is looked up again. The cache is a type parameter of the tables, so that it can be
+//! chosen to suit how the table is used:
+//! * [`LocalCache`] is the default cache, which is cheap but can't be shared across threads, so the
+//!   tables using it are not `Sync`.
+//! * [`NoCache`] doesn't store anything, so the tables using it are `Sync` without any locking.
+//! * [`ShardedCache`] spreads the values over several mutex protected maps, so that the tables using it
+//!   are `Sync`, and the threads looking up different indices rarely contend for the same lock.
+//! * [`LruCache`] holds at most `S` values, and evicts the least recently used one to make room for a new
+//!   value.
+//! * [`DirectMappedCache`] holds at most `S` values, each in the slot given by the hash of its key, and
+//!   evicts the value in that slot to make room for a new value. It is faster than [`LruCache`] for large
+//!   capacities, at the expense of evicting values which are still in use when their keys collide.
+//!
+//! Unlike the first three, the bounded caches never allocate, and keep their values inside the table. So
+//! they suit the long running processes, where almost every lookup is a different index, as well as the
+//! `no-std` targets.
+//!
+//! The cache only stores the values computed by the table, so the lookups return identical values with
+//! any of the caches. The caches count their hits, misses and evictions in [`CacheStats`], to tell whether
+//! the caching pays off for a table.
+
+use core::cell::{Cell, RefCell};
+use core::hash::{Hash, Hasher};
+
+#[cfg(not(feature = "no-std"))]
+use std::collections::HashMap;
+
+#[cfg(feature = "no-std")]
+use hashbrown::HashMap;
+
+#[cfg(not(feature = "no-std"))]
+use std::{
+    collections::hash_map::RandomState,
+    hash::BuildHasher,
+    sync::atomic::{AtomicU64, Ordering},
+    sync::Mutex,
+};
+
+/// The number of the lookups served by a cache, and of the values it had to evict, since the table was
+/// constructed. Changing the configuration of a table clears its cache, but not the counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// The lookups which returned a stored value.
+    pub hits: u64,
+    /// The lookups which had to be interpolated.
+    pub misses: u64,
+    /// The stored values which were dropped to make room for a new value.
+    pub evictions: u64,
+}
+
+/// A store for the values of the previous lookups of a table, keyed by the bit patterns of the indices.
+pub trait Cache<K, V> {
+    /// Returns the stored value for the `key`, if any.
+    fn get(&self, key: &K) -> Option<V>;
+
+    /// Stores the `value` for the `key`.
+    fn insert(&self, key: K, value: V);
+
+    /// Removes all the stored values, which is done when the configuration of the table changes.
+    fn clear(&mut self);
+
+    /// Returns the counts of the hits, misses and evictions of the cache.
+    fn stats(&self) -> CacheStats;
+}
+
+/// The counts of a cache, which is used from a single thread.
+#[derive(Debug, Default)]
+struct Counters {
+    hits: Cell<u64>,
+    misses: Cell<u64>,
+    evictions: Cell<u64>,
+}
+
+impl Counters {
+    fn count<V>(&self, value: Option<V>) -> Option<V> {
+        let counter = if value.is_some() { &self.hits } else { &self.misses };
+        counter.set(counter.get() + 1);
+        value
+    }
+
+    fn evict(&self) {
+        self.evictions.set(self.evictions.get() + 1);
+    }
+
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.get(),
+            misses: self.misses.get(),
+            evictions: self.evictions.get(),
+        }
+    }
+}
+
+/// The default cache of the tables, which stores all the values in a single map behind a `RefCell`.
+#[derive(Debug)]
+pub struct LocalCache<K, V> {
+    map: RefCell<HashMap<K, V>>,
+    counters: Counters,
+}
+
+impl<K, V> Default for LocalCache<K, V> {
+    fn default() -> Self {
+        LocalCache {
+            map: RefCell::new(HashMap::default()),
+            counters: Counters::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Copy> Cache<K, V> for LocalCache<K, V> {
+    fn get(&self, key: &K) -> Option<V> {
+        self.counters.count(self.map.borrow().get(key).copied())
+    }
+
+    fn insert(&self, key: K, value: V) {
+        self.map.borrow_mut().insert(key, value);
+    }
+
+    fn clear(&mut self) {
+        self.map.get_mut().clear();
+    }
+
+    fn stats(&self) -> CacheStats {
+        self.counters.stats()
+    }
+}
+
+/// A cache which doesn't store anything, so that every lookup is interpolated. Since it doesn't keep
+/// track of anything either, its [`CacheStats`] are always zero.
+/// ```
+///  use look_up_table::{NoCache, OneDLookUpTable};
+///  use std::sync::Arc;
+///  let lut: Arc<OneDLookUpTable<3, f64, NoCache>> =
+///      Arc::new(OneDLookUpTable::new([1.0, 2.0, 3.0], [2.0, 4.0, 8.0]).unwrap().with_cache());
+///  let worker = Arc::clone(&lut);
+///  let y = std::thread::spawn(move || worker.get(&2.5)).join().unwrap();
+///  assert_eq!(y, lut.get(&2.5));
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoCache;
+
+impl<K, V> Cache<K, V> for NoCache {
+    fn get(&self, _key: &K) -> Option<V> {
+        None
+    }
+
+    fn insert(&self, _key: K, _value: V) {}
+
+    fn clear(&mut self) {}
+
+    fn stats(&self) -> CacheStats {
+        CacheStats::default()
+    }
+}
+
+#[cfg(not(feature = "no-std"))]
+const SHARDS: usize = 16;
+
+/// A cache which can be shared across threads, where the values are spread over several maps by the hash
+/// of their keys, each protected by its own mutex.
+/// ```
+///  use look_up_table::{ShardedCache, TwoDLookUpTable};
+///  use std::sync::Arc;
+///  let lut: TwoDLookUpTable<2, 2, f64, ShardedCache<_, _>> =
+///      TwoDLookUpTable::new([1.0, 2.0], [1.0, 2.0], [[1.0, 2.0], [3.0, 4.0]]).unwrap().with_cache();
+///  let lut = Arc::new(lut);
+///  let workers: Vec<_> = (0..4)
+///      .map(|i| {
+///          let lut = Arc::clone(&lut);
+///          std::thread::spawn(move || lut.get(&(1.0 + i as f64 / 4.0), &1.5))
+///      })
+///      .collect();
+///  let values: Vec<f64> = workers.into_iter().map(|w| w.join().unwrap()).collect();
+///  assert_eq!(values, [1.5, 2.0, 2.5, 3.0]);
+/// ```
+#[cfg(not(feature = "no-std"))]
+#[derive(Debug)]
+pub struct ShardedCache<K, V> {
+    shards: [Mutex<HashMap<K, V>>; SHARDS],
+    hasher: RandomState,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[cfg(not(feature = "no-std"))]
+impl<K, V> Default for ShardedCache<K, V> {
+    fn default() -> Self {
+        ShardedCache {
+            shards: core::array::from_fn(|_| Mutex::new(HashMap::new())),
+            hasher: RandomState::new(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+}
+
+#[cfg(not(feature = "no-std"))]
+impl<K: Hash + Eq, V: Copy> ShardedCache<K, V> {
+    fn shard(&self, key: &K) -> std::sync::MutexGuard<'_, HashMap<K, V>> {
+        let shard = &self.shards[self.hasher.hash_one(key) as usize % SHARDS];
+
+        // The maps are always left in a valid state, so a panic in another thread holding the lock
+        // doesn't make the values invalid.
+        shard.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(not(feature = "no-std"))]
+impl<K: Hash + Eq, V: Copy> Cache<K, V> for ShardedCache<K, V> {
+    fn get(&self, key: &K) -> Option<V> {
+        let value = self.shard(key).get(key).copied();
+        let counter = if value.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        value
+    }
+
+    fn insert(&self, key: K, value: V) {
+        self.shard(&key).insert(key, value);
+    }
+
+    fn clear(&mut self) {
+        for shard in self.shards.iter_mut() {
+            shard.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+        }
+    }
+
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: 0,
+        }
+    }
+}
+
+/// A cache holding at most `S` values, which evicts the least recently used value when it is full. The
+/// values are searched linearly, so the capacity is meant to be small, eg. the handful of operating
+/// points a controller keeps returning to.
+/// ```
+///  use look_up_table::{LruCache, OneDLookUpTable};
+///  let lut: OneDLookUpTable<3, f64, LruCache<_, _, 2>> =
+///      OneDLookUpTable::new([1.0, 2.0, 3.0], [2.0, 4.0, 8.0]).unwrap().with_cache();
+///  for x in [1.5, 2.5, 1.5, 2.75, 1.5] {
+///      lut.get(&x);
+///  }
+///  let stats = lut.cache_stats();
+///  assert_eq!((stats.hits, stats.misses, stats.evictions), (2, 3, 1));
+/// ```
+#[derive(Debug)]
+pub struct LruCache<K, V, const S: usize> {
+    entries: RefCell<[Option<(K, V)>; S]>,
+    used: RefCell<[u64; S]>, // The time of the last use of each of the entries.
+    clock: Cell<u64>,
+    counters: Counters,
+}
+
+impl<K, V, const S: usize> Default for LruCache<K, V, S> {
+    fn default() -> Self {
+        LruCache {
+            entries: RefCell::new(core::array::from_fn(|_| None)),
+            used: RefCell::new([0; S]),
+            clock: Cell::new(0),
+            counters: Counters::default(),
+        }
+    }
+}
+
+impl<K: Eq, V: Copy, const S: usize> LruCache<K, V, S> {
+    fn tick(&self) -> u64 {
+        self.clock.set(self.clock.get() + 1);
+        self.clock.get()
+    }
+}
+
+impl<K: Eq, V: Copy, const S: usize> Cache<K, V> for LruCache<K, V, S> {
+    fn get(&self, key: &K) -> Option<V> {
+        let entries = self.entries.borrow();
+        let found = entries.iter().position(|e| matches!(e, Some((k, _)) if k == key));
+
+        self.counters.count(found.map(|i| {
+            self.used.borrow_mut()[i] = self.tick();
+            entries[i].as_ref().unwrap().1
+        }))
+    }
+
+    fn insert(&self, key: K, value: V) {
+        if S == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.borrow_mut();
+        let mut used = self.used.borrow_mut();
+
+        // Reuse the entry of the key or an empty entry if any, otherwise the least recently used one.
+        let i = entries
+            .iter()
+            .position(|e| matches!(e, Some((k, _)) if *k == key))
+            .or_else(|| entries.iter().position(Option::is_none))
+            .unwrap_or_else(|| {
+                self.counters.evict();
+                (0..S).min_by_key(|&i| used[i]).unwrap()
+            });
+
+        entries[i] = Some((key, value));
+        used[i] = self.tick();
+    }
+
+    fn clear(&mut self) {
+        self.entries.get_mut().iter_mut().for_each(|e| *e = None);
+    }
+
+    fn stats(&self) -> CacheStats {
+        self.counters.stats()
+    }
+}
+
+/// A cache holding at most `S` values, where each value can only be stored in the slot given by the hash
+/// of its key, evicting the value of another key in that slot.
+/// ```
+///  use look_up_table::{DirectMappedCache, TwoDLookUpTable};
+///  let lut: TwoDLookUpTable<2, 2, f64, DirectMappedCache<_, _, 64>> =
+///      TwoDLookUpTable::new([1.0, 2.0], [1.0, 2.0], [[1.0, 2.0], [3.0, 4.0]]).unwrap().with_cache();
+///  assert_eq!(lut.get(&1.5, &1.5), lut.get(&1.5, &1.5));
+///  assert_eq!(lut.cache_stats().hits, 1);
+/// ```
+#[derive(Debug)]
+pub struct DirectMappedCache<K, V, const S: usize> {
+    slots: RefCell<[Option<(K, V)>; S]>,
+    counters: Counters,
+}
+
+impl<K, V, const S: usize> Default for DirectMappedCache<K, V, S> {
+    fn default() -> Self {
+        DirectMappedCache {
+            slots: RefCell::new(core::array::from_fn(|_| None)),
+            counters: Counters::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Copy, const S: usize> DirectMappedCache<K, V, S> {
+    fn slot(key: &K) -> usize {
+        let mut hasher = Fnv::default();
+        key.hash(&mut hasher);
+
+        (hasher.finish() % S as u64) as usize
+    }
+}
+
+impl<K: Hash + Eq, V: Copy, const S: usize> Cache<K, V> for DirectMappedCache<K, V, S> {
+    fn get(&self, key: &K) -> Option<V> {
+        if S == 0 {
+            return self.counters.count(None);
+        }
+
+        let value = match &self.slots.borrow()[Self::slot(key)] {
+            Some((k, v)) if k == key => Some(*v),
+            _ => None,
+        };
+
+        self.counters.count(value)
+    }
+
+    fn insert(&self, key: K, value: V) {
+        if S == 0 {
+            return;
+        }
+
+        let slot = &mut self.slots.borrow_mut()[Self::slot(&key)];
+        if matches!(slot, Some((k, _)) if *k != key) {
+            self.counters.evict();
+        }
+
+        *slot = Some((key, value));
+    }
+
+    fn clear(&mut self) {
+        self.slots.get_mut().iter_mut().for_each(|s| *s = None);
+    }
+
+    fn stats(&self) -> CacheStats {
+        self.counters.stats()
+    }
+}
+
+/// The FNV-1a hash, which needs no state or randomness, so that it is available on every target. The
+/// keys are the bit patterns of the indices, and are not chosen by an adversary.
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Compression
+//! The tables are often sampled more densely than their lookups need, eg. when they are exported from a
+//! simulation at a fixed step. Compressing a linearly interpolated table drops the breakpoints which the
+//! interpolation between their neighbours reproduces within a tolerance, into a table of the same
+//! dimensions:
+//! * A [`DynOneDLookUpTable`] for the [`OneDLookUpTable`], [`OneDLookUpTableRef`] and [`DynOneDLookUpTable`].
+//! * A [`DynTwoDLookUpTable`] for the [`TwoDLookUpTable`], [`TwoDLookUpTableRef`] and [`DynTwoDLookUpTable`].
+//! * An [`NDLookUpTable`] of one or two dimensions for the [`NDLookUpTable`].
+//!
+//! The largest difference between the lookups of the two tables is returned along with the compressed
+//! table, as a [`Compressed`] table. The 1D and 2D tables with other interpolation schemes are reported as
+//! a [`CompressionError::NonLinearInterpolation`].
+//! ```
+//!  use look_up_table::OneDLookUpTable;
+//!  let lut = OneDLookUpTable::new([0.0f64, 1.0, 2.0, 3.0], [0.0, 1.0, 2.05, 3.0]).unwrap();
+//!  let compressed = lut.compress(0.1).unwrap();
+//!  assert_eq!(compressed.table.breakpoints(), [0.0, 3.0]);
+//!  assert!((compressed.error - 0.05).abs() < 1e-12);
+//! ```
+
+use crate::sampling::is_tolerance_valid;
+use crate::{push, zeroed, ConstructionError, Extrapolation, Vec};
+use num::Float;
+use thiserror_no_std::Error;
+
+/// The errors while compressing a 1D or a 2D table, whose interpolation scheme can be other than linear.
+#[derive(Error, Debug)]
+pub enum CompressionError {
+    #[error("Only the tables with linear interpolation can be compressed")]
+    NonLinearInterpolation,
+    #[error("{0}")]
+    InvalidTable(#[from] ConstructionError),
+}
+
+/// A table with fewer breakpoints than the one it was compressed from, along with the largest absolute
+/// difference between the lookups of the two tables, within the range of the breakpoints.
+#[derive(Debug)]
+pub struct Compressed<L, T> {
+    /// The compressed table.
+    pub table: L,
+    /// The largest difference from the lookups of the original table within the range of its breakpoints,
+    /// which is within the tolerance.
+    pub error: T,
+}
+
+/// Compresses the linear interpolation of the values `ys` at the breakpoints `xs` as described in
+/// [`NDLookUpTable::<1>::compress`], and builds the compressed table from the kept breakpoints and values
+/// with `build`, which sets the extrapolation policy on it.
+pub(crate) fn compress_curve<T: Float, L>(
+    xs: &[T],
+    ys: &[T],
+    extrapolation: Extrapolation<T>,
+    tolerance: T,
+    build: impl FnOnce(&[T], &[T]) -> Result<L, ConstructionError>,
+) -> Result<Compressed<L, T>, ConstructionError> {
+    is_tolerance_valid(tolerance)?;
+
+    let worst = |a: usize, b: usize| {
+        (a + 1..b)
+            .map(|k| (k, (ys[k] - lerp(xs[a], xs[b], ys[a], ys[b], xs[k])).abs()))
+            .fold((a, T::zero()), |worst, k| if k.1 > worst.1 { k } else { worst })
+    };
+
+    let kept = simplify(xs.len(), &worst, extrapolation == Extrapolation::Linear, tolerance)?;
+    let (mut x, mut y) = (Vec::new(), Vec::new());
+    for &k in &kept {
+        push(&mut x, xs[k])?;
+        push(&mut y, ys[k])?;
+    }
+
+    Ok(Compressed {
+        table: build(&x, &y)?,
+        error: error(&kept, &worst),
+    })
+}
+
+/// Compresses the bilinear interpolation of the values `value(i, j)` at the breakpoints `xs[i]` and `ys[j]`
+/// as described in [`NDLookUpTable::<2>::compress`], and builds the compressed table from the kept
+/// breakpoints and the values in row major order with `build`, which sets the extrapolation policies on it.
+pub(crate) fn compress_surface<T: Float, L>(
+    [xs, ys]: [&[T]; 2],
+    value: impl Fn(usize, usize) -> T,
+    extrapolation: [Extrapolation<T>; 2],
+    tolerance: T,
+    build: impl FnOnce([&[T]; 2], &[T]) -> Result<L, ConstructionError>,
+) -> Result<Compressed<L, T>, ConstructionError> {
+    is_tolerance_valid(tolerance)?;
+
+    let worst_x = |a: usize, b: usize| {
+        (a + 1..b)
+            .flat_map(|i| (0..ys.len()).map(move |j| (i, j)))
+            .map(|(i, j)| (i, (value(i, j) - lerp(xs[a], xs[b], value(a, j), value(b, j), xs[i])).abs()))
+            .fold((a, T::zero()), |worst, k| if k.1 > worst.1 { k } else { worst })
+    };
+    let kept_x = simplify(xs.len(), &worst_x, extrapolation[0] == Extrapolation::Linear, tolerance)?;
+
+    // The values interpolated along the first axis between the kept breakpoints, at every one of the
+    // breakpoints, so the error along the second axis includes the error along the first one.
+    let mut along_x = zeroed(xs.len() * ys.len())?;
+    for pair in kept_x.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        for i in a..=b {
+            for j in 0..ys.len() {
+                along_x[i * ys.len() + j] = lerp(xs[a], xs[b], value(a, j), value(b, j), xs[i]);
+            }
+        }
+    }
+    let worst_y = |a: usize, b: usize| {
+        (a + 1..b)
+            .flat_map(|j| (0..xs.len()).map(move |i| (i, j)))
+            .map(|(i, j)| {
+                let (za, zb) = (along_x[i * ys.len() + a], along_x[i * ys.len() + b]);
+                (j, (value(i, j) - lerp(ys[a], ys[b], za, zb, ys[j])).abs())
+            })
+            .fold((a, T::zero()), |worst, k| if k.1 > worst.1 { k } else { worst })
+    };
+    let kept_y = simplify(ys.len(), &worst_y, extrapolation[1] == Extrapolation::Linear, tolerance)?;
+
+    let (mut x, mut y, mut values) = (Vec::new(), Vec::new(), Vec::new());
+    kept_x.iter().try_for_each(|&i| push(&mut x, xs[i]))?;
+    kept_y.iter().try_for_each(|&j| push(&mut y, ys[j]))?;
+    for &i in &kept_x {
+        kept_y.iter().try_for_each(|&j| push(&mut values, value(i, j)))?;
+    }
+
+    // Dropping the breakpoints of the first axis only changes the values between the kept ones, and the
+    // error at the kept breakpoints of the second axis is the one measured along the first axis.
+    let kept_error = kept_y
+        .iter()
+        .flat_map(|&j| (0..xs.len()).map(move |i| (i, j)))
+        .map(|(i, j)| (value(i, j) - along_x[i * ys.len() + j]).abs())
+        .fold(T::zero(), T::max);
+
+    Ok(Compressed {
+        table: build([&x, &y], &values)?,
+        error: error(&kept_y, &worst_y).max(kept_error),
+    })
+}
+
+/// Returns the indices of the breakpoints to keep out of `n`, so that dropping the ones between each pair
+/// of the kept breakpoints leaves an error within the tolerance. `worst(a, b)` returns the breakpoint
+/// between `a` and `b` with the largest error, if those between them were dropped, along with the error.
+/// The end segments are kept when they are continued by the linear extrapolation.
+fn simplify<T: Float>(
+    n: usize,
+    worst: &impl Fn(usize, usize) -> (usize, T),
+    is_linear: bool,
+    tolerance: T,
+) -> Result<Vec<usize>, ConstructionError> {
+    let is_end = |k: usize| k == 0 || k + 1 == n || (is_linear && (k == 1 || k + 2 == n));
+    let mut is_kept = Vec::new();
+    (0..n).try_for_each(|k| push(&mut is_kept, is_end(k)))?;
+
+    let mut ranges = Vec::new();
+    let mut a = 0;
+    for b in (1..n).filter(|&k| is_end(k)) {
+        push(&mut ranges, (a, b))?;
+        a = b;
+    }
+    while let Some((a, b)) = ranges.pop() {
+        let (k, error) = worst(a, b);
+        if k > a && error > tolerance {
+            is_kept[k] = true;
+            push(&mut ranges, (a, k))?;
+            push(&mut ranges, (k, b))?;
+        }
+    }
+
+    let mut kept = Vec::new();
+    (0..n).filter(|&k| is_kept[k]).try_for_each(|k| push(&mut kept, k))?;
+
+    Ok(kept)
+}
+
+/// Returns the largest error of the breakpoints dropped between the kept ones.
+fn error<T: Float>(kept: &[usize], worst: &impl Fn(usize, usize) -> (usize, T)) -> T {
+    kept.windows(2)
+        .map(|pair| worst(pair[0], pair[1]).1)
+        .fold(T::zero(), T::max)
+}
+
+/// Interpolates linearly between the points `(x0, y0)` and `(x1, y1)` at `x`.
+fn lerp<T: Float>(x0: T, x1: T, y0: T, y1: T, x: T) -> T {
+    y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+}
@@ -0,0 +1,233 @@
+//! Const Look Up Table
+//! On firmware, the calibration tables are usually fixed at build time, and are best placed in flash
+//! rather than built in RAM at startup. The const tables can be constructed in a `const` or a `static`
+//! item, and never allocate, since they own their values in arrays, and have no cache.
+//! Their constructors validate the breakpoints and the values with the same rules as the other tables,
+//! and when evaluated in a `const` context, an invalid table fails the build, with the message of the
+//! corresponding [`ConstructionError`]:
+//! ```compile_fail
+//!  use look_up_table::ConstOneDLookUpTable;
+//!  static LUT: ConstOneDLookUpTable<3> = ConstOneDLookUpTable::<3>::new([1.0, 3.0, 2.0], [0.0, 1.0, 2.0]);
+//! ```
+//!
+//! The constructors are available for `f32` and `f64` values, as the floating point comparisons are only
+//! possible on the concrete types in a `const fn`. So the type of the table has to be spelled out when
+//! calling them, eg. `ConstOneDLookUpTable::<3>::new` for `f64` values, and
+//! `ConstOneDLookUpTable::<3, f32>::new` for `f32` values. The tables interpolate linearly, and the lookups
+//! return identical values to the other tables with the same breakpoints, values and extrapolation.
+
+mod validation;
+
+use crate::const_lut::validation::Validate;
+use crate::oned_lut::interpolate_curve;
+use crate::twod_lut::{interpolate_surface, SurfaceValueGetter};
+use crate::{AxisInterpolation, AxisRange, Checked, ConstructionError, Extrapolation, Interpolation, LookUpError};
+use num::Float;
+
+/// Linear interpolation, which can be constructed at compile time. It is the const counterpart of a
+/// [`crate::OneDLookUpTable`] with no cache.
+/// ```
+///  use look_up_table::{ConstOneDLookUpTable, Extrapolation};
+///  static LUT: ConstOneDLookUpTable<3> =
+///      ConstOneDLookUpTable::<3>::new([1.0, 2.0, 3.0], [2.0, 4.0, 8.0]).with_extrapolation(Extrapolation::Linear);
+///  assert!((LUT.get(&2.5) - 6.0).abs() < f64::EPSILON);
+///  assert!((LUT.get(&4.0) - 12.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ConstOneDLookUpTable<const N: usize, T = f64> {
+    x: [T; N],
+    y: [T; N],
+    extrapolation: Extrapolation<T>,
+}
+
+/// Bilinear interpolation, which can be constructed at compile time. It is the const counterpart of a
+/// [`crate::TwoDLookUpTable`] with no cache, where the value at `surface[i][j]` corresponds to the
+/// breakpoints `x[i]` and `y[j]`.
+/// ```
+///  use look_up_table::ConstTwoDLookUpTable;
+///  const LUT: ConstTwoDLookUpTable<2, 2, f32> =
+///      ConstTwoDLookUpTable::<2, 2, f32>::new([1.0, 2.0], [1.0, 2.0], [[1.0, 2.0], [3.0, 4.0]]);
+///  assert!((LUT.get(&1.5, &1.25) - 2.25).abs() < f32::EPSILON);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ConstTwoDLookUpTable<const M: usize, const N: usize, T = f64> {
+    x: [T; M],
+    y: [T; N],
+    surface: [[T; N]; M],
+    extrapolation: [Extrapolation<T>; 2],
+}
+
+/// Panics with the message of the error, which fails the build when the table is constructed in a `const`
+/// context.
+const fn invalid(error: ConstructionError) -> ! {
+    match error {
+        ConstructionError::IncreasingDimOrderError => {
+            panic!("Independent Dimension values should be in strictly increasing order")
+        }
+        ConstructionError::ContainingNansOrInfinities => {
+            panic!("Cannot create a Lookup Table containing NaNs or Infinities")
+        }
+        ConstructionError::MinLengthError => panic!("At least two values should be provided for all dimensions"),
+        _ => panic!("Cannot create a Lookup Table with the given dimensions"),
+    }
+}
+
+macro_rules! impl_const_constructors {
+    ($($t:ty),*) => {$(
+        impl<const N: usize> ConstOneDLookUpTable<N, $t> {
+            /// Constructs a `ConstOneDLookUpTable` object, given the breakpoints `x` and the values `y`,
+            /// or panics if they are invalid.
+            pub const fn new(x: [$t; N], y: [$t; N]) -> Self {
+                match Self::try_new(x, y) {
+                    Ok(lut) => lut,
+                    Err(error) => invalid(error),
+                }
+            }
+
+            /// Constructs a `ConstOneDLookUpTable` object, or returns the same errors as
+            /// [`crate::OneDLookUpTable::new`] for invalid breakpoints or values.
+            /// ```
+            ///  use look_up_table::{ConstOneDLookUpTable, ConstructionError};
+            ///  let lut = ConstOneDLookUpTable::<2>::try_new([1.0, 2.0], [f64::NAN, 1.0]);
+            ///  assert!(matches!(lut.err().unwrap(), ConstructionError::ContainingNansOrInfinities))
+            /// ```
+            pub const fn try_new(x: [$t; N], y: [$t; N]) -> Result<Self, ConstructionError> {
+                if N < 2 {
+                    return Err(ConstructionError::MinLengthError);
+                }
+
+                if !Validate::<$t>::is_finite(&x) || !Validate::<$t>::is_finite(&y) {
+                    return Err(ConstructionError::ContainingNansOrInfinities);
+                }
+
+                if !Validate::<$t>::is_increasing(&x) {
+                    return Err(ConstructionError::IncreasingDimOrderError);
+                }
+
+                Ok(ConstOneDLookUpTable {
+                    x,
+                    y,
+                    extrapolation: Extrapolation::Clamp,
+                })
+            }
+
+            /// Returns the table with the given policy for the lookups outside the range of the
+            /// breakpoints. The default policy is [`Extrapolation::Clamp`].
+            pub const fn with_extrapolation(mut self, extrapolation: Extrapolation<$t>) -> Self {
+                self.extrapolation = extrapolation;
+                self
+            }
+        }
+
+        impl<const M: usize, const N: usize> ConstTwoDLookUpTable<M, N, $t> {
+            /// Constructs a `ConstTwoDLookUpTable` object, given the breakpoints `x` and `y` and the
+            /// `surface` values, or panics if they are invalid.
+            pub const fn new(x: [$t; M], y: [$t; N], surface: [[$t; N]; M]) -> Self {
+                match Self::try_new(x, y, surface) {
+                    Ok(lut) => lut,
+                    Err(error) => invalid(error),
+                }
+            }
+
+            /// Constructs a `ConstTwoDLookUpTable` object, or returns the same errors as
+            /// [`crate::TwoDLookUpTable::new`] for invalid breakpoints or values.
+            pub const fn try_new(x: [$t; M], y: [$t; N], surface: [[$t; N]; M]) -> Result<Self, ConstructionError> {
+                if M < 2 || N < 2 {
+                    return Err(ConstructionError::MinLengthError);
+                }
+
+                if !Validate::<$t>::is_finite(&x) || !Validate::<$t>::is_finite(&y) {
+                    return Err(ConstructionError::ContainingNansOrInfinities);
+                }
+
+                let mut i = 0;
+                while i < M {
+                    if !Validate::<$t>::is_finite(&surface[i]) {
+                        return Err(ConstructionError::ContainingNansOrInfinities);
+                    }
+                    i += 1;
+                }
+
+                if !Validate::<$t>::is_increasing(&x) || !Validate::<$t>::is_increasing(&y) {
+                    return Err(ConstructionError::IncreasingDimOrderError);
+                }
+
+                Ok(ConstTwoDLookUpTable {
+                    x,
+                    y,
+                    surface,
+                    extrapolation: [Extrapolation::Clamp; 2],
+                })
+            }
+
+            /// Returns the table with the given policies for the lookups outside the range of the
+            /// breakpoints, for each of the axes. The default policy is [`Extrapolation::Clamp`] for both
+            /// the axes.
+            pub const fn with_extrapolation(mut self, x: Extrapolation<$t>, y: Extrapolation<$t>) -> Self {
+                self.extrapolation = [x, y];
+                self
+            }
+        }
+    )*};
+}
+
+impl_const_constructors!(f32, f64);
+
+impl<const N: usize, T: Float> ConstOneDLookUpTable<N, T> {
+    /// Returns an interpolated value for the given `index`, same as [`crate::OneDLookUpTable::get`].
+    pub fn get(&self, index: &T) -> T {
+        self.try_get(index).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value for the given `index`, or an error if the index lies outside the
+    /// range of the breakpoints, and the extrapolation policy is [`Extrapolation::Error`].
+    pub fn try_get(&self, index: &T) -> Result<T, LookUpError> {
+        interpolate_curve(index, &self.x, &self.y, &[], &Interpolation::Linear, &self.extrapolation, None)
+    }
+
+    /// Returns an interpolated value for the given `index`, along with where the index lies relative to
+    /// the breakpoints.
+    pub fn get_checked(&self, index: &T) -> Result<Checked<T, 1>, LookUpError> {
+        Ok(Checked {
+            value: self.try_get(index)?,
+            range: [AxisRange::of(*index, &self.x)],
+        })
+    }
+}
+
+impl<const M: usize, const N: usize, T: Float> ConstTwoDLookUpTable<M, N, T> {
+    /// Returns an interpolated value for the given `x` and `y` indices, same as
+    /// [`crate::TwoDLookUpTable::get`].
+    pub fn get(&self, x: &T, y: &T) -> T {
+        self.try_get(x, y).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value for the given `x` and `y` indices, or an error if an index lies
+    /// outside the range of the breakpoints, and the extrapolation policy of its axis is
+    /// [`Extrapolation::Error`].
+    pub fn try_get(&self, x: &T, y: &T) -> Result<T, LookUpError> {
+        interpolate_surface(
+            [*x, *y],
+            [&self.x, &self.y],
+            &[AxisInterpolation::Linear; 2],
+            &self.extrapolation,
+            self,
+            [None; 2],
+        )
+    }
+
+    /// Returns an interpolated value for the given `x` and `y` indices, along with where the indices lie
+    /// relative to the breakpoints of each of the axes.
+    pub fn get_checked(&self, x: &T, y: &T) -> Result<Checked<T, 2>, LookUpError> {
+        Ok(Checked {
+            value: self.try_get(x, y)?,
+            range: [AxisRange::of(*x, &self.x), AxisRange::of(*y, &self.y)],
+        })
+    }
+}
+
+impl<const M: usize, const N: usize, T: Float> SurfaceValueGetter<T> for ConstTwoDLookUpTable<M, N, T> {
+    fn get(&self, x: usize, y: usize) -> T {
+        self.surface[x][y]
+    }
+}
@@ -0,0 +1,41 @@
+use crate::EPSILON;
+use core::marker::PhantomData;
+
+/// The checks of the breakpoints and the values for the const constructors. The traits of `num` can't be
+/// used in a `const fn`, so the checks are implemented for each of the floating point types.
+pub(super) struct Validate<T>(PhantomData<T>);
+
+macro_rules! impl_validate {
+    ($($t:ty),*) => {$(
+        impl Validate<$t> {
+            /// Checks that none of the values is a NaN or an Infinity.
+            pub(super) const fn is_finite(vs: &[$t]) -> bool {
+                let mut i = 0;
+                while i < vs.len() {
+                    if !vs[i].is_finite() {
+                        return false;
+                    }
+                    i += 1;
+                }
+
+                true
+            }
+
+            /// Checks that the values are strictly increasing, by more than the tolerance used for the
+            /// other tables.
+            pub(super) const fn is_increasing(vs: &[$t]) -> bool {
+                let mut i = 1;
+                while i < vs.len() {
+                    if vs[i] - vs[i - 1] <= EPSILON as $t {
+                        return false;
+                    }
+                    i += 1;
+                }
+
+                true
+            }
+        }
+    )*};
+}
+
+impl_validate!(f32, f64);
@@ -0,0 +1,283 @@
+//! CSV
+//! Calibrations are often kept in spreadsheets, and the owned and the dynamic tables can be read from and
+//! written to their comma separated values.
+//! * A 1D table has a line for each of the breakpoints, with the breakpoint in the first column and the
+//!   value in the second column.
+//! * A 2D table has a header line with the x breakpoints from the second column on, and a line for each of
+//!   the y breakpoints, with the breakpoint in the first column and the values at each of the x breakpoints
+//!   in the following columns. The first column of the header line is not read, eg. to label the axes.
+//!
+//! The blank lines are skipped, and the spaces around the values are ignored.
+//! Reading a table reports the line and the column of the offending value, along with the kind of the
+//! error, including the [`ConstructionError`] for the values which don't make a valid table.
+//! ```
+//!  use look_up_table::{CsvError, OneDLookUpTable, TwoDLookUpTable};
+//!  let curve = OneDLookUpTable::<3>::from_csv("1.0, 2.0\n2.0, 4.0\n3.0, 8.0\n").unwrap();
+//!  assert!((curve.get(&2.5) - 6.0).abs() < f64::EPSILON);
+//!
+//!  let surface = TwoDLookUpTable::<2, 3>::from_csv("load\\speed, 1000, 2000\n0, 0, 10\n0.5, 1, 11\n1, 2, 12").unwrap();
+//!  assert!((surface.get(&1500.0, &0.75) - 6.5).abs() < f64::EPSILON);
+//!
+//!  let err = OneDLookUpTable::<3>::from_csv("1.0, 2.0\n3.0, 4.0\n2.0, 8.0\n").unwrap_err();
+//!  assert!(matches!(err, CsvError::InvalidTable { line: 3, column: 1, .. }));
+//!  assert_eq!(err.to_string(), "Line 3, column 1: Independent Dimension values should be in strictly increasing order");
+//! ```
+
+use crate::error::ConstructionError;
+use crate::{Vec, EPSILON};
+use core::fmt::{Display, Result as FmtResult, Write};
+use num::Float;
+use thiserror_no_std::Error;
+
+/// The errors while reading a table from comma separated values. The lines and the columns are counted
+/// from one, and an error about the number of values in a table is reported on the line after the last
+/// one.
+#[derive(Error, Debug)]
+pub enum CsvError {
+    #[error("Line {line}, column {column}: Cannot parse the value as a number")]
+    InvalidNumber { line: usize, column: usize },
+    #[error("Line {line}: Expected {expected} columns, but found {actual}")]
+    ColumnCountMismatch {
+        line: usize,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("Line {line}, column {column}: {error}")]
+    InvalidTable {
+        line: usize,
+        column: usize,
+        error: ConstructionError,
+    },
+}
+
+/// The breakpoints and the values of a 1D table, along with the lines they were read from.
+pub(crate) struct Curve<T> {
+    pub(crate) x: Vec<T>,
+    pub(crate) y: Vec<T>,
+    lines: Vec<usize>,
+}
+
+/// The breakpoints and the values of a 2D table, along with the lines they were read from. The values
+/// are stored in the order they were read, i.e. a row for each of the y breakpoints.
+pub(crate) struct Surface<T> {
+    pub(crate) x: Vec<T>,
+    pub(crate) y: Vec<T>,
+    values: Vec<T>,
+    lines: Vec<usize>, // The line of the header, followed by the lines of the rows.
+}
+
+impl<T: Float> Curve<T> {
+    /// Reads the lines of a 1D table, each with a breakpoint and a value.
+    pub(crate) fn read(csv: &str) -> Result<Curve<T>, CsvError> {
+        let mut curve = Curve {
+            x: Vec::new(),
+            y: Vec::new(),
+            lines: Vec::new(),
+        };
+
+        for (line, cells) in records(csv) {
+            let count = cells.clone().count();
+            if count != 2 {
+                return Err(CsvError::ColumnCountMismatch {
+                    line,
+                    expected: 2,
+                    actual: count,
+                });
+            }
+
+            let mut values = cells.enumerate().map(|(i, cell)| parse(cell, line, i + 1));
+            let (x, y) = (values.next().unwrap()?, values.next().unwrap()?);
+            push(&mut curve.x, x, line)?;
+            push(&mut curve.y, y, line)?;
+            push(&mut curve.lines, line, line)?;
+        }
+
+        Ok(curve)
+    }
+
+    /// Checks that the table has `n` breakpoints, for the tables of a fixed size.
+    pub(crate) fn expect(&self, n: usize) -> Result<(), CsvError> {
+        if self.x.len() != n {
+            return Err(self.locate(ConstructionError::DimensionMismatch {
+                expected: n,
+                actual: self.x.len(),
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Finds the value responsible for the error of the constructor.
+    pub(crate) fn locate(&self, error: ConstructionError) -> CsvError {
+        let (line, column) = match error {
+            ConstructionError::ContainingNansOrInfinities => (0..self.x.len())
+                .find_map(|i| {
+                    let column = [self.x[i], self.y[i]].iter().position(|v| !v.is_finite())?;
+                    Some((self.lines[i], column + 1))
+                })
+                .unwrap_or((self.end(), 1)),
+            ConstructionError::IncreasingDimOrderError => (1..self.x.len())
+                .find(|&i| !increasing(self.x[i - 1], self.x[i]))
+                .map_or((self.end(), 1), |i| (self.lines[i], 1)),
+            _ => (self.end(), 1),
+        };
+
+        CsvError::InvalidTable { line, column, error }
+    }
+
+    fn end(&self) -> usize {
+        self.lines.last().map_or(1, |line| line + 1)
+    }
+}
+
+impl<T: Float> Surface<T> {
+    /// Reads the header line with the x breakpoints, followed by the lines with a y breakpoint and the
+    /// values at each of the x breakpoints.
+    pub(crate) fn read(csv: &str) -> Result<Surface<T>, CsvError> {
+        let mut surface = Surface {
+            x: Vec::new(),
+            y: Vec::new(),
+            values: Vec::new(),
+            lines: Vec::new(),
+        };
+
+        let mut records = records(csv);
+        let Some((header, cells)) = records.next() else {
+            return Err(CsvError::InvalidTable {
+                line: 1,
+                column: 1,
+                error: ConstructionError::MinLengthError,
+            });
+        };
+        for (i, cell) in cells.enumerate().skip(1) {
+            push(&mut surface.x, parse(cell, header, i + 1)?, header)?;
+        }
+        push(&mut surface.lines, header, header)?;
+
+        for (line, cells) in records {
+            let count = cells.clone().count();
+            if count != surface.x.len() + 1 {
+                return Err(CsvError::ColumnCountMismatch {
+                    line,
+                    expected: surface.x.len() + 1,
+                    actual: count,
+                });
+            }
+
+            for (i, cell) in cells.enumerate() {
+                let value = parse(cell, line, i + 1)?;
+                push(if i == 0 { &mut surface.y } else { &mut surface.values }, value, line)?;
+            }
+            push(&mut surface.lines, line, line)?;
+        }
+
+        Ok(surface)
+    }
+
+    /// Checks that the table has `m` x breakpoints and `n` y breakpoints, for the tables of a fixed size.
+    pub(crate) fn expect(&self, m: usize, n: usize) -> Result<(), CsvError> {
+        if self.x.len() != m {
+            return Err(CsvError::ColumnCountMismatch {
+                line: self.lines[0],
+                expected: m + 1,
+                actual: self.x.len() + 1,
+            });
+        }
+
+        if self.y.len() != n {
+            return Err(self.locate(ConstructionError::RowCountMismatch {
+                expected: n,
+                actual: self.y.len(),
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value at the `i`th x breakpoint and the `j`th y breakpoint.
+    pub(crate) fn value(&self, i: usize, j: usize) -> T {
+        self.values[j * self.x.len() + i]
+    }
+
+    /// Finds the value responsible for the error of the constructor.
+    pub(crate) fn locate(&self, error: ConstructionError) -> CsvError {
+        let (nx, ny) = (self.x.len(), self.y.len());
+        let (line, column) = match error {
+            ConstructionError::MinLengthError if nx < 2 => (self.lines[0], nx + 2),
+            ConstructionError::ContainingNansOrInfinities => {
+                let header = (0..nx)
+                    .find(|&i| !self.x[i].is_finite())
+                    .map(|i| (self.lines[0], i + 2));
+                let rows = (0..ny).find_map(|j| {
+                    let column =
+                        (0..=nx).find(|&c| !if c == 0 { self.y[j] } else { self.value(c - 1, j) }.is_finite())?;
+                    Some((self.lines[j + 1], column + 1))
+                });
+                header.or(rows).unwrap_or((self.end(), 1))
+            }
+            ConstructionError::IncreasingDimOrderError => {
+                let header = (1..nx)
+                    .find(|&i| !increasing(self.x[i - 1], self.x[i]))
+                    .map(|i| (self.lines[0], i + 2));
+                let rows = (1..ny)
+                    .find(|&j| !increasing(self.y[j - 1], self.y[j]))
+                    .map(|j| (self.lines[j + 1], 1));
+                header.or(rows).unwrap_or((self.end(), 1))
+            }
+            _ => (self.end(), 1),
+        };
+
+        CsvError::InvalidTable { line, column, error }
+    }
+
+    fn end(&self) -> usize {
+        self.lines.last().map_or(1, |line| line + 1)
+    }
+}
+
+/// Writes the lines of a 1D table, each with a breakpoint and a value.
+pub(crate) fn write_curve<T: Display, W: Write>(out: &mut W, xs: &[T], ys: &[T]) -> FmtResult {
+    xs.iter().zip(ys).try_for_each(|(x, y)| writeln!(out, "{x},{y}"))
+}
+
+/// Writes the header line with the x breakpoints, followed by the lines with a y breakpoint and the
+/// values at each of the x breakpoints, which are given by `value(i, j)` for the `i`th x breakpoint and
+/// the `j`th y breakpoint.
+pub(crate) fn write_surface<T: Display, W: Write>(
+    out: &mut W,
+    xs: &[T],
+    ys: &[T],
+    value: impl Fn(usize, usize) -> T,
+) -> FmtResult {
+    xs.iter().try_for_each(|x| write!(out, ",{x}"))?;
+    writeln!(out)?;
+
+    for (j, y) in ys.iter().enumerate() {
+        write!(out, "{y}")?;
+        (0..xs.len()).try_for_each(|i| write!(out, ",{}", value(i, j)))?;
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the non blank lines along with their numbers, and an iterator over their cells.
+fn records(csv: &str) -> impl Iterator<Item = (usize, core::str::Split<'_, char>)> {
+    csv.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, line.split(',')))
+}
+
+fn parse<T: Float>(cell: &str, line: usize, column: usize) -> Result<T, CsvError> {
+    T::from_str_radix(cell.trim(), 10).map_err(|_| CsvError::InvalidNumber { line, column })
+}
+
+fn increasing<T: Float>(prev: T, curr: T) -> bool {
+    curr - prev > T::from(EPSILON).unwrap()
+}
+
+/// Appends a value, reporting the line which exceeds the capacity in no-std builds.
+fn push<V>(vec: &mut Vec<V>, value: V, line: usize) -> Result<(), CsvError> {
+    crate::push(vec, value).map_err(|error| CsvError::InvalidTable { line, column: 1, error })
+}
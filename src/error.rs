@@ -16,7 +16,25 @@ pub enum ConstructionError {
     ContainingNansOrInfinities,
     #[error("At least two values should be provided for all dimensions")]
     MinLengthError,
+    #[error("Expected {expected} values for the given dimensions, but found {actual}")]
+    DimensionMismatch { expected: usize, actual: usize },
+    #[error("Expected {expected} rows in the surface, but found {actual}")]
+    RowCountMismatch { expected: usize, actual: usize },
+    #[error("Expected {expected} values in row {row} of the surface, but found {actual}")]
+    RowLengthMismatch { row: usize, expected: usize, actual: usize },
+    #[error("The tolerance should not be negative")]
+    NegativeTolerance,
     #[cfg(feature = "no-std")]
     #[error("Functions with more than {MAX_FUNCTION_POINTS} are not supported")]
     MaxLengthError,
 }
+
+#[derive(Error, Debug, PartialEq)]
+pub enum LookUpError {
+    #[error("Index on axis {axis} lies outside the range of the breakpoints")]
+    OutOfRange { axis: usize },
+    #[error("The values are not strictly monotonic, so they don't have a unique inverse")]
+    NotMonotonic,
+    #[error("The value lies outside the range of the values of the table")]
+    ValueOutOfRange,
+}
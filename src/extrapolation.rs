@@ -0,0 +1,126 @@
+//! Extrapolation
+//! When an index lies outside the range of the breakpoints of an axis, there is no sample on either
+//! side of it to interpolate between. The extrapolation policy of the axis decides what the look up
+//! table does in such cases. Within the range of the breakpoints, the policy has no effect.
+//!
+//! Since the default policy silently clamps the indices, the tables also report where each index lies
+//! relative to the breakpoints of its axis with their `get_checked` methods, eg. to log the lookups
+//! outside the range.
+
+use crate::error::LookUpError;
+use num::Float;
+
+/// Behavior of a look up table along an axis, when the index lies outside the range of the breakpoints.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Extrapolation<T = f64> {
+    /// The index is clamped to the first or the last breakpoint, so that the boundary values are returned.
+    #[default]
+    Clamp,
+    /// The slope of the first or the last segment is continued beyond the breakpoints.
+    Linear,
+    /// The given value is returned.
+    Constant(T),
+    /// NaN is returned.
+    NaN,
+    /// The breakpoints are treated as one period of a periodic function, and the index is wrapped
+    /// into the range `[first, last)` of the breakpoints.
+    Periodic,
+    /// The lookup is an error. The fallible getters of the tables report it as a
+    /// [`LookUpError::OutOfRange`], while the infallible ones return NaN.
+    Error,
+}
+
+/// The outcome of applying an extrapolation policy to an index.
+pub(crate) enum Bounded<T> {
+    /// The index to interpolate at. It lies within the range of the breakpoints, except for the
+    /// [`Extrapolation::Linear`] policy, where it is left as is.
+    Index(T),
+    /// The result of the lookup, which needs no interpolation.
+    Value(T),
+}
+
+impl<T: Float> Extrapolation<T> {
+    /// Applies the policy to the index `v`, given the breakpoints `vs` of the axis numbered `axis`.
+    pub(crate) fn apply(&self, v: T, vs: &[T], axis: usize) -> Result<Bounded<T>, LookUpError> {
+        let (first, last) = (vs[0], vs[vs.len() - 1]);
+        if v >= first && v <= last {
+            return Ok(Bounded::Index(v));
+        }
+
+        match self {
+            Extrapolation::Clamp => Ok(Bounded::Index(if v < first { first } else { last })),
+            Extrapolation::Linear => Ok(Bounded::Index(v)),
+            Extrapolation::Constant(c) => Ok(Bounded::Value(*c)),
+            Extrapolation::NaN => Ok(Bounded::Value(T::nan())),
+            Extrapolation::Periodic => {
+                let period = last - first;
+                let offset = (v - first) % period;
+                Ok(Bounded::Index(
+                    first + if offset < T::zero() { offset + period } else { offset },
+                ))
+            }
+            Extrapolation::Error => Err(LookUpError::OutOfRange { axis }),
+        }
+    }
+}
+
+/// Where an index lies relative to the breakpoints of an axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AxisRange {
+    /// The index is below the first breakpoint.
+    Below,
+    /// The index is above the last breakpoint. A NaN index is also reported here, as it is clamped to the
+    /// last breakpoint by default.
+    Above,
+    /// The index is exactly on one of the breakpoints, so no interpolation is needed along the axis.
+    OnBreakpoint,
+    /// The index is strictly between two neighbouring breakpoints.
+    Between,
+}
+
+impl AxisRange {
+    /// Returns where the index `v` lies relative to the breakpoints `vs`.
+    pub(crate) fn of<T: Float>(v: T, vs: &[T]) -> AxisRange {
+        if v < vs[0] {
+            AxisRange::Below
+        } else if v.is_nan() || v > vs[vs.len() - 1] {
+            AxisRange::Above
+        } else if vs.binary_search_by(|val| val.partial_cmp(&v).unwrap()).is_ok() {
+            AxisRange::OnBreakpoint
+        } else {
+            AxisRange::Between
+        }
+    }
+
+    /// Returns true if the index lies outside the range of the breakpoints.
+    pub fn is_out_of_range(&self) -> bool {
+        matches!(self, AxisRange::Below | AxisRange::Above)
+    }
+}
+
+/// The result of a checked lookup in a table with `D` axes, which is the value along with where the index
+/// lies on each of the axes.
+/// ```
+///  use look_up_table::{AxisRange, TwoDLookUpTable};
+///  let lut = TwoDLookUpTable::new([1.0, 2.0], [1.0, 2.0], [[1.0, 2.0], [3.0, 4.0]]).unwrap();
+///  let checked = lut.get_checked(&0.0, &2.0).unwrap();
+///  assert_eq!(checked.value, 2.0);
+///  assert_eq!(checked.range, [AxisRange::Below, AxisRange::OnBreakpoint]);
+///  assert!(checked.is_out_of_range());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Checked<T, const D: usize> {
+    /// The value of the lookup, after applying the extrapolation policies.
+    pub value: T,
+    /// Where the index lies relative to the breakpoints, for each of the axes.
+    pub range: [AxisRange; D],
+}
+
+impl<T, const D: usize> Checked<T, D> {
+    /// Returns true if the index lies outside the range of the breakpoints on any of the axes, i.e. the
+    /// value was extrapolated.
+    pub fn is_out_of_range(&self) -> bool {
+        self.range.iter().any(AxisRange::is_out_of_range)
+    }
+}
@@ -0,0 +1,87 @@
+use crate::fixed_lut::FixedPoint;
+use crate::ConstructionError;
+use crate::ConstructionError::{IncreasingDimOrderError, MinLengthError};
+use num::{One, PrimInt, Signed, Zero};
+
+/// Checks the breakpoints of an axis. The raw integers can't be NaN or infinite, so they only need at
+/// least two values in strictly increasing order.
+pub(super) fn is_axis_valid<T: FixedPoint>(vs: &[T]) -> Result<bool, ConstructionError> {
+    if vs.len() < 2 {
+        return Err(MinLengthError);
+    }
+
+    if !vs.windows(2).all(|c| c[1] > c[0]) {
+        return Err(IncreasingDimOrderError);
+    }
+
+    Ok(true)
+}
+
+/// Divides `n` by the positive `d`, rounding the quotient to the nearest integer, and the ties away
+/// from zero.
+#[inline]
+pub(super) fn div_round<W: PrimInt + Signed>(n: W, d: W) -> W {
+    let half = d / (W::one() + W::one());
+    if n >= W::zero() {
+        (n + half) / d
+    } else {
+        (n - half) / d
+    }
+}
+
+/// Given an index value, find the lower and upper bound indices of the segment containing it. If the
+/// index is out of bounds, or directly matches a breakpoint, then both the indices are the same.
+#[inline]
+fn get_indices<T: FixedPoint>(v: T, vs: &[T]) -> (usize, usize) {
+    if v <= vs[0] {
+        (0, 0)
+    } else if v >= vs[vs.len() - 1] {
+        (vs.len() - 1, vs.len() - 1)
+    } else {
+        match vs.binary_search(&v) {
+            Ok(ind) => (ind, ind),
+            Err(ind) => (ind - 1, ind),
+        }
+    }
+}
+
+pub(super) fn interpolate<T: FixedPoint>(x: T, xs: &[T], ys: &[T]) -> T {
+    let (lo, hi) = get_indices(x, xs);
+    if lo == hi {
+        return ys[lo];
+    }
+
+    let w = |v: T| T::Wide::from(v);
+    let (x1, x2, y1, y2) = (w(xs[lo]), w(xs[hi]), w(ys[lo]), w(ys[hi]));
+
+    // The interpolated value is a single fraction over the segment length, so that it's rounded only once.
+    T::saturate(div_round(y1 * (x2 - x1) + (y2 - y1) * (w(x) - x1), x2 - x1))
+}
+
+/// Bilinear interpolation, where the weighted sum of the four corners of the quad is computed exactly in
+/// the wide type, and rounded only once at the end.
+pub(super) fn interpolate_2d<T: FixedPoint, const N: usize>(x: T, y: T, xs: &[T], ys: &[T], surface: &[[T; N]]) -> T {
+    let (x1_ind, x2_ind) = get_indices(x, xs);
+    let (y1_ind, y2_ind) = get_indices(y, ys);
+
+    let w = |v: T| T::Wide::from(v);
+    let one = T::Wide::one();
+    // The distances of the index from the bounds along each axis, with a degenerate segment (index on a
+    // breakpoint or out of bounds) having all of the weight on the lower bound.
+    let span = |v: T, vs: &[T], lo: usize, hi: usize| {
+        if lo == hi {
+            (one, T::Wide::zero(), one)
+        } else {
+            (w(vs[hi]) - w(v), w(v) - w(vs[lo]), w(vs[hi]) - w(vs[lo]))
+        }
+    };
+    let (dx2, dx1, dx) = span(x, xs, x1_ind, x2_ind);
+    let (dy2, dy1, dy) = span(y, ys, y1_ind, y2_ind);
+
+    let sum = dx2 * dy2 * w(surface[x1_ind][y1_ind])
+        + dx1 * dy2 * w(surface[x2_ind][y1_ind])
+        + dx2 * dy1 * w(surface[x1_ind][y2_ind])
+        + dx1 * dy1 * w(surface[x2_ind][y2_ind]);
+
+    T::saturate(div_round(sum, dx * dy))
+}
@@ -0,0 +1,193 @@
+//! Fixed Point Look Up Table
+//! On microcontrollers without a floating point unit, every floating point operation is emulated in
+//! software, which defeats the purpose of a look up table. The fixed point tables store the breakpoints
+//! and the values as raw integers, and do the whole interpolation in integer arithmetic.
+//! The raw integers are related to the physical values by a [`Scaling`], i.e.
+//! `physical = raw * scale + offset`, which is only needed to convert the values in and out of the table,
+//! eg. [`Scaling::Q15`] for the Q15 format.
+//!
+//! Rounding: The interpolated value is computed exactly in a wider integer type, and rounded once to the
+//! nearest raw value, with the ties rounded away from zero. So the result is within half of the least
+//! significant bit of the exact interpolation of the raw table.
+//!
+//! Saturation: The interpolated value always lies between the values at the neighbouring breakpoints,
+//! so it never overflows. The conversions from physical values saturate at the minimum and maximum raw
+//! values of the type.
+//!
+//! Extrapolation: The indices outside the range of the breakpoints are clamped to the boundary values.
+//! The tables have no cache, as they are meant for targets without an allocator.
+
+mod interpolation;
+
+use crate::fixed_lut::interpolation::{interpolate, interpolate_2d, is_axis_valid};
+use crate::ConstructionError;
+use core::fmt::Debug;
+use num::{Float, PrimInt, Signed};
+
+/// Raw integer types, which can be used as the breakpoints and the values of the fixed point tables.
+pub trait FixedPoint: PrimInt + Debug {
+    /// A signed integer type, which is wide enough to hold the intermediate products of the interpolation.
+    type Wide: PrimInt + Signed + From<Self>;
+
+    /// Converts the value back from the wide type, saturating at the minimum and the maximum values.
+    fn saturate(v: Self::Wide) -> Self {
+        let (min, max) = (Self::Wide::from(Self::min_value()), Self::Wide::from(Self::max_value()));
+        num::cast(v.max(min).min(max)).unwrap()
+    }
+}
+
+impl FixedPoint for i16 {
+    type Wide = i64;
+}
+
+impl FixedPoint for u16 {
+    type Wide = i64;
+}
+
+impl FixedPoint for i32 {
+    type Wide = i128;
+}
+
+impl FixedPoint for u32 {
+    type Wide = i128;
+}
+
+/// Linear relation between the raw integers stored in a fixed point table and the physical values they
+/// represent: `physical = raw * scale + offset`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scaling {
+    pub scale: f64,
+    pub offset: f64,
+}
+
+impl Scaling {
+    /// Signed Q15 format, with 15 fractional bits and the range `[-1, 1)` in an `i16`.
+    pub const Q15: Scaling = Scaling::new(1.0 / 32768.0, 0.0);
+
+    /// Signed Q31 format, with 31 fractional bits and the range `[-1, 1)` in an `i32`.
+    pub const Q31: Scaling = Scaling::new(1.0 / 2147483648.0, 0.0);
+
+    pub const fn new(scale: f64, offset: f64) -> Scaling {
+        Scaling { scale, offset }
+    }
+
+    /// Converts a physical value to the nearest raw value, with the ties rounded away from zero. The
+    /// values beyond the range of the raw type saturate at its minimum or maximum, and NaN maps to zero.
+    /// ```
+    ///  use look_up_table::Scaling;
+    ///  assert_eq!(Scaling::Q15.to_raw::<i16>(0.5), 16384);
+    ///  assert_eq!(Scaling::Q15.to_raw::<i16>(1.0), i16::MAX);
+    ///  assert_eq!(Scaling::new(0.5, 10.0).to_raw::<u16>(-3.0), 0);
+    /// ```
+    pub fn to_raw<T: FixedPoint>(&self, v: f64) -> T {
+        let raw = Float::round((v - self.offset) / self.scale);
+        if raw.is_nan() {
+            T::zero()
+        } else {
+            num::cast(raw).unwrap_or(if raw < 0.0 { T::min_value() } else { T::max_value() })
+        }
+    }
+
+    /// Converts a raw value to the physical value it represents.
+    pub fn to_physical<T: FixedPoint>(&self, raw: T) -> f64 {
+        raw.to_f64().unwrap() * self.scale + self.offset
+    }
+}
+
+/// One dimensional linear interpolation over raw integers, with clamping to the boundary values when
+/// the index is outside support region. The breakpoints and the values share the raw type `T`, but can
+/// have different scalings.
+/// ```
+///  use look_up_table::{FixedOneDLookUpTable, Scaling};
+///  let lut = FixedOneDLookUpTable::<3, i16>::new([0, 100, 200], [0, 1000, -1000]).unwrap();
+///  assert_eq!(lut.get(50), 500);
+///  assert_eq!(lut.get(175), -500);
+///  assert_eq!(lut.get(300), -1000);
+/// ```
+#[derive(Debug)]
+pub struct FixedOneDLookUpTable<const N: usize, T: FixedPoint = i16> {
+    x: [T; N],
+    y: [T; N],
+}
+
+impl<const N: usize, T: FixedPoint> FixedOneDLookUpTable<N, T> {
+    /// Constructs a `FixedOneDLookUpTable` object, given the raw breakpoints `x` and values `y`.
+    /// If the `x` values are not strictly increasing:
+    /// ```
+    ///  use look_up_table::{FixedOneDLookUpTable, ConstructionError};
+    ///  let lut = FixedOneDLookUpTable::<3, u16>::new([3, 1, 2], [1; 3]);
+    ///  assert!(matches!(lut.err().unwrap(), ConstructionError::IncreasingDimOrderError))
+    /// ```
+    pub fn new(x: [T; N], y: [T; N]) -> Result<FixedOneDLookUpTable<N, T>, ConstructionError> {
+        is_axis_valid(&x).map(|_| FixedOneDLookUpTable { x, y })
+    }
+
+    /// Constructs a `FixedOneDLookUpTable` object from the physical breakpoints and values, by converting
+    /// them to the raw values with the given scalings.
+    pub fn from_physical(
+        x: [f64; N],
+        y: [f64; N],
+        x_scaling: &Scaling,
+        y_scaling: &Scaling,
+    ) -> Result<FixedOneDLookUpTable<N, T>, ConstructionError> {
+        Self::new(x.map(|v| x_scaling.to_raw(v)), y.map(|v| y_scaling.to_raw(v)))
+    }
+
+    /// Returns the interpolated raw value for the given raw `index`. If the `index` lies outside the
+    /// range, then it clamps the values to the boundary values.
+    pub fn get(&self, index: T) -> T {
+        interpolate(index, &self.x, &self.y)
+    }
+}
+
+/// Two dimensional bilinear interpolation over raw integers, with clamping to the boundary values when
+/// the indices are outside support region. The value at `surface[i][j]` corresponds to the breakpoints
+/// `x[i]` and `y[j]`.
+#[derive(Debug)]
+pub struct FixedTwoDLookUpTable<const M: usize, const N: usize, T: FixedPoint = i16> {
+    x: [T; M],
+    y: [T; N],
+    surface: [[T; N]; M],
+}
+
+impl<const M: usize, const N: usize, T: FixedPoint> FixedTwoDLookUpTable<M, N, T> {
+    /// Constructs a `FixedTwoDLookUpTable` object, given the raw breakpoints `xs` and `ys`, and the raw
+    /// `surface` values.
+    /// ```
+    ///  use look_up_table::FixedTwoDLookUpTable;
+    ///  let lut = FixedTwoDLookUpTable::<2, 2, i16>::new([0, 10], [0, 10], [[0, 100], [200, 300]]).unwrap();
+    ///  assert_eq!(lut.get(5, 5), 150);
+    ///  assert_eq!(lut.get(-5, 3), 30);
+    /// ```
+    pub fn new(
+        xs: [T; M],
+        ys: [T; N],
+        surface: [[T; N]; M],
+    ) -> Result<FixedTwoDLookUpTable<M, N, T>, ConstructionError> {
+        is_axis_valid(&xs)?;
+        is_axis_valid(&ys).map(|_| FixedTwoDLookUpTable { x: xs, y: ys, surface })
+    }
+
+    /// Constructs a `FixedTwoDLookUpTable` object from the physical breakpoints and surface values, by
+    /// converting them to the raw values with the given scalings.
+    pub fn from_physical(
+        xs: [f64; M],
+        ys: [f64; N],
+        surface: [[f64; N]; M],
+        x_scaling: &Scaling,
+        y_scaling: &Scaling,
+        z_scaling: &Scaling,
+    ) -> Result<FixedTwoDLookUpTable<M, N, T>, ConstructionError> {
+        Self::new(
+            xs.map(|v| x_scaling.to_raw(v)),
+            ys.map(|v| y_scaling.to_raw(v)),
+            surface.map(|row| row.map(|v| z_scaling.to_raw(v))),
+        )
+    }
+
+    /// Returns the interpolated raw value for the given raw `x` and `y` indices. If an index lies outside
+    /// the range, then it is clamped to the boundary breakpoint of its axis.
+    pub fn get(&self, x: T, y: T) -> T {
+        interpolate_2d(x, y, &self.x, &self.y, &self.surface)
+    }
+}
@@ -3,13 +3,26 @@
 #[cfg(not(feature = "no-std"))]
 extern crate std;
 
+mod axis;
+mod cache;
+mod compression;
+mod const_lut;
+mod csv;
 mod error;
+mod extrapolation;
+mod fixed_lut;
+mod macros;
+mod nd_lut;
 mod oned_lut;
+mod sampling;
+#[cfg(feature = "serde")]
+mod serialization;
 mod twod_lut;
 
 pub(crate) const EPSILON: f64 = 0.00000001;
 
 use cfg_if::cfg_if;
+use num::Float;
 
 cfg_if! {
     if #[cfg(feature="no-std")] {
@@ -22,7 +35,65 @@ cfg_if! {
     }
 }
 
+/// Returns a buffer of `len` zeros, to hold the values which are derived from the breakpoints when a
+/// table is constructed.
+pub(crate) fn zeroed<T: Float>(len: usize) -> Result<Vec<T>, ConstructionError> {
+    let mut vec = Vec::new();
+    cfg_if! {
+        if #[cfg(feature="no-std")] {
+            vec.resize(len, T::zero()).map_err(|_| ConstructionError::MaxLengthError)?;
+        } else {
+            vec.resize(len, T::zero());
+        }
+    }
+
+    Ok(vec)
+}
+
+/// Returns a buffer holding a copy of the values in the slice, for the tables which own their values.
+pub(crate) fn to_vec<T: Float>(values: &[T]) -> Result<Vec<T>, ConstructionError> {
+    cfg_if! {
+        if #[cfg(feature="no-std")] {
+            Vec::from_slice(values).map_err(|_| ConstructionError::MaxLengthError)
+        } else {
+            Ok(values.to_vec())
+        }
+    }
+}
+
+/// Appends a value to a buffer, which can run out of capacity in no-std builds.
+pub(crate) fn push<T>(vec: &mut Vec<T>, value: T) -> Result<(), ConstructionError> {
+    cfg_if! {
+        if #[cfg(feature="no-std")] {
+            vec.push(value).map_err(|_| ConstructionError::MaxLengthError)
+        } else {
+            vec.push(value);
+            Ok(())
+        }
+    }
+}
+
+/// Converts a constant used in the computations into the element type of a table. All the constants
+/// used are small and exactly representable in the supported floating point types.
+#[inline]
+pub(crate) fn constant<T: Float>(v: f64) -> T {
+    T::from(v).unwrap()
+}
+
 // Re-exports for public api
-pub use error::ConstructionError;
-pub use oned_lut::{OneDLookUpTable, OneDLookUpTableRef};
-pub use twod_lut::{TwoDLookUpTable, TwoDLookUpTableRef};
+pub use axis::{Axis, AxisPosition, IndexFraction};
+#[cfg(not(feature = "no-std"))]
+pub use cache::ShardedCache;
+pub use cache::{Cache, CacheStats, DirectMappedCache, LocalCache, LruCache, NoCache};
+pub use compression::{Compressed, CompressionError};
+pub use const_lut::{ConstOneDLookUpTable, ConstTwoDLookUpTable};
+pub use csv::CsvError;
+pub use error::{ConstructionError, LookUpError};
+pub use extrapolation::{AxisRange, Checked, Extrapolation};
+pub use fixed_lut::{FixedOneDLookUpTable, FixedPoint, FixedTwoDLookUpTable, Scaling};
+pub use nd_lut::{NDLookUpTable, NDLookUpTableRef};
+pub use oned_lut::{DynOneDLookUpTable, Interpolation, OneDLookUpTable, OneDLookUpTableRef, SplineBoundary};
+pub use twod_lut::{
+    AxisInterpolation, DynTwoDLookUpTable, SurfaceInterpolation, SurfaceOrientation, TwoDLookUpTable,
+    TwoDLookUpTableRef,
+};
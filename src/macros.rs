@@ -0,0 +1,80 @@
+//! Table Macros
+//! Calibrations are usually written down as tables, with a row for each breakpoint. The macros accept the
+//! breakpoints and the values in the same layout, and expand to the const tables, which are validated at
+//! compile time. So a typo in a calibration, such as an unsorted or a non finite breakpoint, or a row of
+//! the wrong length, fails the build instead of the first lookup.
+
+/// Constructs a [`crate::ConstOneDLookUpTable`] from the rows `x => y`, validating them at compile time.
+/// The values are `f64`, unless another floating point type is given before the rows.
+/// ```
+///  use look_up_table::{lut, ConstOneDLookUpTable};
+///  static TORQUE: ConstOneDLookUpTable<3> = lut![
+///      1000.0 => 100.0,
+///      2000.0 => 180.0,
+///      3000.0 => 150.0,
+///  ];
+///  let gain = lut![f32;
+///      0.0 => 1.0,
+///      1.0 => 0.5,
+///  ];
+///  assert!((TORQUE.get(&2500.0) - 165.0).abs() < f64::EPSILON);
+///  assert!((gain.get(&0.5) - 0.75).abs() < f32::EPSILON);
+/// ```
+///
+/// If the breakpoints are not sorted in ascending order, the build fails:
+/// ```compile_fail
+///  use look_up_table::lut;
+///  let lut = lut![
+///      1.0 => 0.0,
+///      3.0 => 1.0,
+///      2.0 => 2.0,
+///  ];
+/// ```
+#[macro_export]
+macro_rules! lut {
+    ($t:ty; $($x:expr => $y:expr),+ $(,)?) => {
+        const { $crate::ConstOneDLookUpTable::<{ [$($x),+].len() }, $t>::new([$($x),+], [$($y),+]) }
+    };
+    ($($x:expr => $y:expr),+ $(,)?) => {
+        $crate::lut![f64; $($x => $y),+]
+    };
+}
+
+/// Constructs a [`crate::ConstTwoDLookUpTable`] from a header row with the `y` breakpoints, followed by a
+/// row `x => [z, ...]` for each of the `x` breakpoints, with a value for each of the `y` breakpoints. The
+/// rows are validated at compile time, including their lengths. The values are `f64`, unless another
+/// floating point type is given before the rows.
+/// ```
+///  use look_up_table::lut2d;
+///  let torque = lut2d![
+///      y:         [0.0, 0.5, 1.0],
+///      1000.0 => [10.0, 50.0, 90.0],
+///      2000.0 => [20.0, 90.0, 160.0],
+///  ];
+///  assert!((torque.get(&1500.0, &0.25) - 42.5).abs() < f64::EPSILON);
+/// ```
+///
+/// If a row doesn't have a value for each of the `y` breakpoints, the build fails:
+/// ```compile_fail
+///  use look_up_table::lut2d;
+///  let torque = lut2d![
+///      y:         [0.0, 0.5, 1.0],
+///      1000.0 => [10.0, 50.0],
+///      2000.0 => [20.0, 90.0, 160.0],
+///  ];
+/// ```
+#[macro_export]
+macro_rules! lut2d {
+    ($t:ty; y: [$($y:expr),+ $(,)?], $($x:expr => [$($z:expr),+ $(,)?]),+ $(,)?) => {
+        const {
+            $crate::ConstTwoDLookUpTable::<{ [$($x),+].len() }, { [$($y),+].len() }, $t>::new(
+                [$($x),+],
+                [$($y),+],
+                [$([$($z),+]),+],
+            )
+        }
+    };
+    (y: [$($y:expr),+ $(,)?], $($x:expr => [$($z:expr),+ $(,)?]),+ $(,)?) => {
+        $crate::lut2d![f64; y: [$($y),+], $($x => [$($z),+]),+]
+    };
+}
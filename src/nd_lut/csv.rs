@@ -0,0 +1,60 @@
+use crate::csv::{write_curve, write_surface, CsvError, Curve, Surface};
+use crate::nd_lut::interpolation::Key;
+use crate::{zeroed, Cache, NDLookUpTable};
+use core::fmt::{Display, Result as FmtResult, Write};
+use num::Float;
+
+impl<T: Float> NDLookUpTable<1, T> {
+    /// Constructs a one dimensional `NDLookUpTable` object from comma separated values, in the layout of
+    /// [`crate::OneDLookUpTable::from_csv`], for the tables whose number of breakpoints is only known at
+    /// runtime.
+    /// ```
+    ///  use look_up_table::NDLookUpTable;
+    ///  let lut = NDLookUpTable::<1>::from_csv("800, 90\n1500, 140\n2500, 175\n").unwrap();
+    ///  assert!((lut.get(&[2000.0]) - 157.5).abs() < f64::EPSILON);
+    /// ```
+    pub fn from_csv(csv: &str) -> Result<NDLookUpTable<1, T>, CsvError> {
+        let curve = Curve::read(csv)?;
+
+        Self::new([&curve.x], &curve.y).map_err(|e| curve.locate(e))
+    }
+}
+
+impl<T: Float> NDLookUpTable<2, T> {
+    /// Constructs a two dimensional `NDLookUpTable` object from comma separated values, in the layout of
+    /// [`crate::TwoDLookUpTable::from_csv`], for the tables whose number of breakpoints is only known at
+    /// runtime. The x breakpoints of the header line are the first axis of the table.
+    /// ```
+    ///  use look_up_table::NDLookUpTable;
+    ///  let lut = NDLookUpTable::<2>::from_csv(",1000, 2000, 3000\n0.0, 10, 20, 30\n1.0, 90, 160, 150").unwrap();
+    ///  assert!((lut.get(&[1500.0, 0.5]) - 70.0).abs() < f64::EPSILON);
+    /// ```
+    pub fn from_csv(csv: &str) -> Result<NDLookUpTable<2, T>, CsvError> {
+        let surface = Surface::read(csv)?;
+
+        let (nx, ny) = (surface.x.len(), surface.y.len());
+        let mut values = zeroed(nx * ny).map_err(|e| surface.locate(e))?;
+        for (k, value) in values.iter_mut().enumerate() {
+            *value = surface.value(k / ny, k % ny);
+        }
+
+        Self::new([&surface.x, &surface.y], &values).map_err(|e| surface.locate(e))
+    }
+}
+
+impl<T: Float + Display, C: Cache<[Key; 1], T>> NDLookUpTable<1, T, C> {
+    /// Writes the breakpoints and the values of the table as comma separated values, in the layout read
+    /// by [`NDLookUpTable::<1>::from_csv`].
+    pub fn to_csv<W: Write>(&self, out: &mut W) -> FmtResult {
+        write_curve(out, &self.axes[0], &self.values)
+    }
+}
+
+impl<T: Float + Display, C: Cache<[Key; 2], T>> NDLookUpTable<2, T, C> {
+    /// Writes the breakpoints and the values of the table as comma separated values, in the layout read
+    /// by [`NDLookUpTable::<2>::from_csv`].
+    pub fn to_csv<W: Write>(&self, out: &mut W) -> FmtResult {
+        let ny = self.axes[1].len();
+        write_surface(out, &self.axes[0], &self.axes[1], |i, j| self.values[i * ny + j])
+    }
+}
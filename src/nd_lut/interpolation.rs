@@ -0,0 +1,90 @@
+use crate::axis::search;
+use crate::error::ConstructionError::{
+    ContainingNansOrInfinities, DimensionMismatch, IncreasingDimOrderError, MinLengthError,
+};
+use crate::extrapolation::{Bounded, Extrapolation};
+use crate::{constant, ConstructionError, LookUpError, EPSILON};
+use num::Float;
+
+pub(super) type Key = (u64, i16, i8);
+
+pub(super) fn is_object_constructible<const D: usize, T: Float>(
+    axes: &[&[T]; D],
+    values: &[T],
+) -> Result<bool, ConstructionError> {
+    if D == 0 || axes.iter().any(|axis| axis.len() < 2) {
+        return Err(MinLengthError);
+    }
+
+    if axes
+        .iter()
+        .any(|axis| axis.iter().any(|v| v.is_nan() || v.is_infinite()))
+        || values.iter().any(|v| v.is_nan() || v.is_infinite())
+    {
+        return Err(ContainingNansOrInfinities);
+    }
+
+    if !axes
+        .iter()
+        .all(|axis| axis.windows(2).all(|c| c[1] - c[0] > constant(EPSILON)))
+    {
+        return Err(IncreasingDimOrderError);
+    }
+
+    let expected = axes.iter().map(|axis| axis.len()).product();
+    if values.len() != expected {
+        return Err(DimensionMismatch {
+            expected,
+            actual: values.len(),
+        });
+    }
+
+    Ok(true)
+}
+
+/// Multilinear interpolation over a rectilinear grid, whose values are stored in row major order, i.e.
+/// the index along the last axis varies the fastest. The result is the weighted sum of the values at the
+/// `2^D` corners of the grid cell containing the index, where the corners with zero weight (the ones
+/// beyond an index which lies exactly on a breakpoint) are skipped.
+pub(super) fn interpolate<const D: usize, T: Float>(
+    index: &[T; D],
+    axes: &[&[T]; D],
+    values: &[T],
+    extrapolation: &[Extrapolation<T>; D],
+) -> Result<T, LookUpError> {
+    let mut segments = [(0, T::zero()); D];
+    for (k, segment) in segments.iter_mut().enumerate() {
+        let v = match extrapolation[k].apply(index[k], axes[k], k)? {
+            Bounded::Index(v) => v,
+            Bounded::Value(z) => return Ok(z),
+        };
+        // An index directly matching a breakpoint gives a zero fraction, and an index outside the range
+        // (possible only with the linear extrapolation) gives the boundary segment, with a fraction outside
+        // `[0, 1]`.
+        let (lower, upper) = search(v, axes[k]);
+        *segment = if lower == upper {
+            (lower, T::zero())
+        } else {
+            (lower, (v - axes[k][lower]) / (axes[k][upper] - axes[k][lower]))
+        };
+    }
+
+    let mut z = T::zero();
+    'corners: for corner in 0..1usize << D {
+        let (mut offset, mut weight) = (0, T::one());
+        for k in 0..D {
+            let (ind, alpha) = segments[k];
+            let upper = corner >> (D - 1 - k) & 1 == 1;
+            if upper && alpha == T::zero() {
+                continue 'corners;
+            }
+
+            offset = offset * axes[k].len() + ind + upper as usize;
+            weight = weight * if upper { alpha } else { T::one() - alpha };
+        }
+
+        z = z + weight * values[offset];
+    }
+
+    Ok(z)
+}
@@ -0,0 +1,303 @@
+//! N Dimensional Look Up Table
+//! ND LUT generalizes the 1D and 2D LUTs to any number of independent variables. The function values
+//! are sampled on a rectilinear grid, i.e. at every combination of the breakpoints of each axis, and are
+//! stored in a flat buffer in row major order, where the index along the last axis varies the fastest.
+//! For a lookup that is not directly present as a grid point, the values at the corners of the grid
+//! cell containing it are interpolated along each axis in turn.
+//! [Multilinear Interpolation](https://en.wikipedia.org/wiki/Multilinear_interpolation)
+//!
+//! Each of the axes has its own [`Extrapolation`] policy, which decides the behavior when the index on
+//! that axis lies outside the range of the breakpoints. The policies are applied in the order of axes.
+
+mod csv;
+mod interpolation;
+#[cfg(feature = "serde")]
+mod serialization;
+
+use crate::compression::{compress_curve, compress_surface};
+use crate::nd_lut::interpolation::{interpolate, is_object_constructible, Key};
+use crate::{
+    to_vec, AxisRange, Cache, CacheStats, Checked, Compressed, ConstructionError, Extrapolation, LocalCache,
+    LookUpError, Vec,
+};
+use num::Float;
+
+/// N Dimensional Linear interpolation with nearest neighbor extrapolation when indices are outside
+/// support region, and with caching support to enable fast lookups on frequently used values.
+/// D is the number of dimensions.
+/// T is the type of the breakpoints and the values, which can be any floating point type, and defaults
+/// to `f64`.
+/// C is the [`Cache`] of the table, which can be changed with [`NDLookUpTable::with_cache`].
+/// This structure owns a copy of the breakpoints and the values, so the grid can be built at runtime.
+#[derive(Debug)]
+pub struct NDLookUpTable<const D: usize, T = f64, C = LocalCache<[Key; D], T>> {
+    axes: [Vec<T>; D],                    // Breakpoints/sample points on each of the axes.
+    values: Vec<T>,                       // Function values at the grid points, in row major order.
+    extrapolation: [Extrapolation<T>; D], // Extrapolation policies for each of the axes.
+    cache: C,                             // A cache to support fast lookup for frequently used values.
+}
+
+impl<const D: usize, T: Float> NDLookUpTable<D, T> {
+    /// Constructs a `NDLookUpTable` object, given the breakpoints of each of the `axes`, and the
+    /// function `values` at the grid points in row major order.
+    /// ```
+    ///  use look_up_table::NDLookUpTable;
+    ///  // f(x, y, z) = x + 10y + 100z
+    ///  let values: Vec<f64> = (0..8).map(|i| ((i >> 2) + 10 * (i >> 1 & 1) + 100 * (i & 1)) as f64).collect();
+    ///  let lut = NDLookUpTable::new([&[0.0, 1.0], &[0.0, 1.0], &[0.0, 1.0]], &values).unwrap();
+    ///  assert!((lut.get(&[0.5, 0.5, 0.5]) - 55.5).abs() < 1e-12);
+    /// ```
+    ///
+    /// If the number of values doesn't match the number of grid points:
+    /// ```
+    ///  use look_up_table::{NDLookUpTable, ConstructionError::DimensionMismatch};
+    ///  let lut = NDLookUpTable::new([&[0.0, 1.0], &[0.0, 1.0, 2.0]], &[1.0; 5]);
+    ///  assert!(matches!(lut.err().unwrap(), DimensionMismatch { expected: 6, actual: 5 }));
+    /// ```
+    ///
+    /// If the breakpoints are not in increasing order, contain NANs or Infinities, or have less than
+    /// two values on any of the axes, then the same errors as the 1D and 2D tables are returned.
+    pub fn new(axes: [&[T]; D], values: &[T]) -> Result<NDLookUpTable<D, T>, ConstructionError> {
+        is_object_constructible(&axes, values)?;
+
+        let mut owned: [Vec<T>; D] = core::array::from_fn(|_| Vec::new());
+        for (axis, breakpoints) in owned.iter_mut().zip(axes) {
+            *axis = to_vec(breakpoints)?;
+        }
+
+        Ok(NDLookUpTable {
+            axes: owned,
+            values: to_vec(values)?,
+            extrapolation: [Extrapolation::Clamp; D],
+            cache: LocalCache::default(),
+        })
+    }
+}
+
+impl<const D: usize, T: Float, C: Cache<[Key; D], T>> NDLookUpTable<D, T, C> {
+    /// Returns the table with a different [`Cache`], which starts out empty.
+    pub fn with_cache<E: Cache<[Key; D], T> + Default>(self) -> NDLookUpTable<D, T, E> {
+        NDLookUpTable {
+            axes: self.axes,
+            values: self.values,
+            extrapolation: self.extrapolation,
+            cache: E::default(),
+        }
+    }
+
+    /// Returns the counts of the hits, misses and evictions of the cache of the table.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Returns the breakpoints of each of the axes, eg. to inspect a table built by sampling a function.
+    pub fn axes(&self) -> [&[T]; D] {
+        core::array::from_fn(|d| &self.axes[d][..])
+    }
+
+    /// Sets the policies for the lookups outside the range of the breakpoints, for each of the axes.
+    /// The default policy is [`Extrapolation::Clamp`] for all the axes.
+    pub fn set_extrapolation(&mut self, extrapolation: [Extrapolation<T>; D]) {
+        self.extrapolation = extrapolation;
+        self.cache.clear();
+    }
+
+    /// Returns an interpolated value for the given `index`, which has a value for each of the axes.
+    /// If the extrapolation policy of an axis is [`Extrapolation::Error`], and the index lies outside
+    /// the range of that axis, then NaN is returned.
+    pub fn get(&self, index: &[T; D]) -> T {
+        self.try_get(index).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value for the given `index`, or an error if the index lies outside the
+    /// range of the breakpoints on an axis, whose extrapolation policy is [`Extrapolation::Error`].
+    pub fn try_get(&self, index: &[T; D]) -> Result<T, LookUpError> {
+        // First do the cache lookup
+        let key = index.map(|v| v.integer_decode());
+
+        if let Some(z) = self.cache.get(&key) {
+            return Ok(z);
+        }
+
+        let axes = core::array::from_fn(|k| &self.axes[k][..]);
+        let z = interpolate(index, &axes, &self.values, &self.extrapolation)?;
+
+        // store the value in cache before returning, to speedup look up process in the future.
+        self.cache.insert(key, z);
+
+        Ok(z)
+    }
+
+    /// Returns an interpolated value for the given `index`, along with where the index lies relative to
+    /// the breakpoints of each of the axes.
+    pub fn get_checked(&self, index: &[T; D]) -> Result<Checked<T, D>, LookUpError> {
+        Ok(Checked {
+            value: self.try_get(index)?,
+            range: core::array::from_fn(|k| AxisRange::of(index[k], &self.axes[k])),
+        })
+    }
+}
+
+impl<T: Float, C: Cache<[Key; 1], T>> NDLookUpTable<1, T, C> {
+    /// Returns a table with the fewest breakpoints this finds, whose lookups are within `tolerance` of the
+    /// lookups of this table. The breakpoints are chosen with the
+    /// [Douglas-Peucker](https://en.wikipedia.org/wiki/Ramer%E2%80%93Douglas%E2%80%93Peucker_algorithm)
+    /// algorithm, with the error measured along the values. The first and the last breakpoints are always
+    /// kept, and with [`Extrapolation::Linear`], so are the second and the second to last ones, so that
+    /// the end segments, whose slopes are continued beyond the breakpoints, are unchanged. Hence the
+    /// error also bounds the extrapolated lookups. The new table has the same extrapolation policy, and
+    /// an empty cache.
+    /// ```
+    ///  use look_up_table::NDLookUpTable;
+    ///  let xs: Vec<f64> = (0..=500).map(|i| i as f64 / 100.0).collect();
+    ///  let ys: Vec<f64> = xs.iter().map(|x| x.sin()).collect();
+    ///  let lut = NDLookUpTable::new([&xs], &ys).unwrap();
+    ///
+    ///  let compressed = lut.compress(1e-3).unwrap();
+    ///  assert!(compressed.table.axes()[0].len() < 100);
+    ///  assert!(compressed.error <= 1e-3);
+    ///  assert!((compressed.table.get(&[2.345]) - lut.get(&[2.345])).abs() <= compressed.error + 1e-12);
+    /// ```
+    ///
+    /// If the `tolerance` is NAN or Infinity:
+    /// ```
+    ///  use look_up_table::{NDLookUpTable, ConstructionError::ContainingNansOrInfinities};
+    ///  let lut = NDLookUpTable::new([&[0.0, 1.0, 2.0]], &[0.0, 1.0, 0.0]).unwrap();
+    ///  assert!(matches!(lut.compress(f64::NAN).err().unwrap(), ContainingNansOrInfinities));
+    /// ```
+    ///
+    /// If the `tolerance` is negative:
+    /// ```
+    ///  use look_up_table::{NDLookUpTable, ConstructionError::NegativeTolerance};
+    ///  let lut = NDLookUpTable::new([&[0.0, 1.0, 2.0]], &[0.0, 1.0, 0.0]).unwrap();
+    ///  assert!(matches!(lut.compress(-0.1).err().unwrap(), NegativeTolerance));
+    /// ```
+    pub fn compress(&self, tolerance: T) -> Result<Compressed<NDLookUpTable<1, T>, T>, ConstructionError> {
+        let extrapolation = self.extrapolation[0];
+        compress_curve(&self.axes[0], &self.values, extrapolation, tolerance, |x, y| {
+            let mut table = NDLookUpTable::new([x], y)?;
+            table.set_extrapolation([extrapolation]);
+            Ok(table)
+        })
+    }
+}
+
+impl<T: Float, C: Cache<[Key; 2], T>> NDLookUpTable<2, T, C> {
+    /// Returns a table with the fewest rows and columns this finds, whose lookups are within `tolerance` of
+    /// the lookups of this table. Whole breakpoints of the axes are dropped, so the grid stays rectilinear:
+    /// first along the first axis, with the
+    /// [Douglas-Peucker](https://en.wikipedia.org/wiki/Ramer%E2%80%93Douglas%E2%80%93Peucker_algorithm)
+    /// algorithm applied to the values at every breakpoint of the second axis, and then along the second
+    /// axis, with the error measured against the values of this table. The first and the last
+    /// breakpoints of the axes are always kept, and along an axis with [`Extrapolation::Linear`], so are
+    /// the second and the second to last ones, so that the slopes continued beyond the breakpoints are
+    /// taken from the same rows or columns as this table. The error is measured within the range of the
+    /// breakpoints, and as the extrapolation along an axis also continues the differences of the rows or
+    /// columns left by dropping the breakpoints of the other axis, these can grow beyond the range. The new
+    /// table has the same extrapolation policies, and an empty cache.
+    /// ```
+    ///  use look_up_table::NDLookUpTable;
+    ///  let (xs, ys) = ([0.0, 1.0, 2.0, 3.0], [0.0, 1.0, 2.0]);
+    ///  // Linear along the first axis, and curved along the second one.
+    ///  let values: Vec<f64> = xs.iter().flat_map(|x| ys.iter().map(move |y| x + y * y)).collect();
+    ///  let lut = NDLookUpTable::new([&xs, &ys], &values).unwrap();
+    ///
+    ///  let compressed = lut.compress(0.1).unwrap();
+    ///  assert_eq!(compressed.table.axes(), [&[0.0, 3.0][..], &[0.0, 1.0, 2.0][..]]);
+    ///  assert_eq!(compressed.error, 0.0);
+    /// ```
+    pub fn compress(&self, tolerance: T) -> Result<Compressed<NDLookUpTable<2, T>, T>, ConstructionError> {
+        let ny = self.axes[1].len();
+        let value = |i, j| self.values[i * ny + j];
+        compress_surface(
+            [&self.axes[0], &self.axes[1]],
+            value,
+            self.extrapolation,
+            tolerance,
+            |axes, values| {
+                let mut table = NDLookUpTable::new(axes, values)?;
+                table.set_extrapolation(self.extrapolation);
+                Ok(table)
+            },
+        )
+    }
+}
+
+/// This struct allows reference slices to be used as the breakpoints and the values of an N
+/// dimensional lookup table, which can be defined at runtime or borrowed from other enclosing objects.
+#[derive(Debug)]
+pub struct NDLookUpTableRef<'a, const D: usize, T = f64, C = LocalCache<[Key; D], T>> {
+    axes: [&'a [T]; D],
+    values: &'a [T],
+    extrapolation: [Extrapolation<T>; D],
+    cache: C,
+}
+
+impl<'a, const D: usize, T: Float> NDLookUpTableRef<'a, D, T> {
+    /// Constructs a `NDLookUpTableRef` object, given the breakpoints of each of the `axes`, and the
+    /// function `values` at the grid points in row major order. The validation is the same as
+    /// [`NDLookUpTable::new`].
+    pub fn new(axes: [&'a [T]; D], values: &'a [T]) -> Result<NDLookUpTableRef<'a, D, T>, ConstructionError> {
+        is_object_constructible(&axes, values).map(|_| NDLookUpTableRef {
+            axes,
+            values,
+            extrapolation: [Extrapolation::Clamp; D],
+            cache: LocalCache::default(),
+        })
+    }
+}
+
+impl<'a, const D: usize, T: Float, C: Cache<[Key; D], T>> NDLookUpTableRef<'a, D, T, C> {
+    /// Returns the table with a different [`Cache`], which starts out empty.
+    pub fn with_cache<E: Cache<[Key; D], T> + Default>(self) -> NDLookUpTableRef<'a, D, T, E> {
+        NDLookUpTableRef {
+            axes: self.axes,
+            values: self.values,
+            extrapolation: self.extrapolation,
+            cache: E::default(),
+        }
+    }
+
+    /// Returns the counts of the hits, misses and evictions of the cache of the table.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Sets the policies for the lookups outside the range of the breakpoints, for each of the axes.
+    /// The default policy is [`Extrapolation::Clamp`] for all the axes.
+    pub fn set_extrapolation(&mut self, extrapolation: [Extrapolation<T>; D]) {
+        self.extrapolation = extrapolation;
+        self.cache.clear();
+    }
+
+    pub fn get(&self, index: &[T; D]) -> T {
+        self.try_get(index).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value for the given `index`, or an error if the index lies outside the
+    /// range of the breakpoints on an axis, whose extrapolation policy is [`Extrapolation::Error`].
+    pub fn try_get(&self, index: &[T; D]) -> Result<T, LookUpError> {
+        // First do the cache lookup
+        let key = index.map(|v| v.integer_decode());
+
+        if let Some(z) = self.cache.get(&key) {
+            return Ok(z);
+        }
+
+        let z = interpolate(index, &self.axes, self.values, &self.extrapolation)?;
+
+        // store the value in cache before returning, to speedup look up process in the future.
+        self.cache.insert(key, z);
+
+        Ok(z)
+    }
+
+    /// Returns an interpolated value for the given `index`, along with where the index lies relative to
+    /// the breakpoints of each of the axes.
+    pub fn get_checked(&self, index: &[T; D]) -> Result<Checked<T, D>, LookUpError> {
+        Ok(Checked {
+            value: self.try_get(index)?,
+            range: core::array::from_fn(|k| AxisRange::of(index[k], self.axes[k])),
+        })
+    }
+}
@@ -0,0 +1,54 @@
+use crate::nd_lut::interpolation::Key;
+use crate::serialization::{invalid, Array, Values};
+use crate::{Cache, Extrapolation, NDLookUpTable, Vec};
+use num::Float;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// The serialized form of a [`NDLookUpTable`], which is validated by the constructor before it becomes a
+/// table. The extrapolation can be left out, to use the default of the table on all the axes.
+#[derive(serde::Deserialize)]
+#[serde(rename = "NDLookUpTable", bound(deserialize = "T: Deserialize<'de>"))]
+struct Data<T, const D: usize> {
+    axes: Array<Values<T>, D>,
+    values: Values<T>,
+    #[serde(default)]
+    extrapolation: Option<Array<Extrapolation<T>, D>>,
+}
+
+/// The breakpoints of the axes, serialized as a sequence of sequences.
+struct Axes<'a, T>(&'a [Vec<T>]);
+
+impl<T: Serialize> Serialize for Axes<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|axis| &axis[..]))
+    }
+}
+
+impl<const D: usize, T: Float + Serialize, C> Serialize for NDLookUpTable<D, T, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut table = serializer.serialize_struct("NDLookUpTable", 3)?;
+        table.serialize_field("axes", &Axes(&self.axes))?;
+        table.serialize_field("values", &self.values[..])?;
+        table.serialize_field("extrapolation", &self.extrapolation[..])?;
+        table.end()
+    }
+}
+
+impl<'de, const D: usize, T, C> Deserialize<'de> for NDLookUpTable<D, T, C>
+where
+    T: Float + Deserialize<'de>,
+    C: Cache<[Key; D], T> + Default,
+{
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let data = Data::<T, D>::deserialize(deserializer)?;
+
+        let axes = core::array::from_fn(|k| &data.axes.0[k].0[..]);
+        let mut lut = NDLookUpTable::new(axes, &data.values.0).map_err(invalid)?;
+        if let Some(extrapolation) = data.extrapolation {
+            lut.set_extrapolation(extrapolation.0);
+        }
+
+        Ok(lut.with_cache())
+    }
+}
@@ -0,0 +1,71 @@
+use crate::csv::{write_curve, CsvError, Curve};
+use crate::oned_lut::interpolation::Key;
+use crate::{Cache, DynOneDLookUpTable, OneDLookUpTable};
+use core::fmt::{Display, Result as FmtResult, Write};
+use num::Float;
+
+impl<const N: usize, T: Float> OneDLookUpTable<N, T> {
+    /// Constructs a linearly interpolated `OneDLookUpTable` object from comma separated values, with a
+    /// line for each of the `N` breakpoints, which has the breakpoint in the first column and the value in
+    /// the second column. See [`crate::CsvError`] for the errors.
+    /// ```
+    ///  use look_up_table::{ConstructionError::DimensionMismatch, CsvError, OneDLookUpTable};
+    ///  let csv = "800, 90\n1500, 140\n2500, 175\n";
+    ///  let lut = OneDLookUpTable::<3>::from_csv(csv).unwrap();
+    ///  assert!((lut.get(&2000.0) - 157.5).abs() < f64::EPSILON);
+    ///
+    ///  let err = OneDLookUpTable::<4>::from_csv(csv).unwrap_err();
+    ///  assert!(matches!(err, CsvError::InvalidTable { line: 4, error: DimensionMismatch { expected: 4, actual: 3 }, .. }));
+    /// ```
+    pub fn from_csv(csv: &str) -> Result<OneDLookUpTable<N, T>, CsvError> {
+        let curve = Curve::read(csv)?;
+        curve.expect(N)?;
+
+        let (mut x, mut y) = ([T::zero(); N], [T::zero(); N]);
+        x.copy_from_slice(&curve.x);
+        y.copy_from_slice(&curve.y);
+
+        Self::new(x, y).map_err(|e| curve.locate(e))
+    }
+}
+
+impl<const N: usize, T: Float + Display, C: Cache<Key, T>> OneDLookUpTable<N, T, C> {
+    /// Writes the breakpoints and the values of the table as comma separated values, in the layout read
+    /// by [`OneDLookUpTable::from_csv`].
+    /// ```
+    ///  use look_up_table::OneDLookUpTable;
+    ///  let lut = OneDLookUpTable::new([1.0, 2.0], [0.5, 4.0]).unwrap();
+    ///  let mut csv = String::new();
+    ///  lut.to_csv(&mut csv).unwrap();
+    ///  assert_eq!(csv, "1,0.5\n2,4\n");
+    /// ```
+    pub fn to_csv<W: Write>(&self, out: &mut W) -> FmtResult {
+        write_curve(out, &self.x, &self.y)
+    }
+}
+
+impl<T: Float> DynOneDLookUpTable<T> {
+    /// Constructs a linearly interpolated `DynOneDLookUpTable` object from comma separated values, in the
+    /// same layout as [`OneDLookUpTable::from_csv`], with a line for each of any number of breakpoints.
+    /// ```
+    ///  use look_up_table::{ConstructionError::MinLengthError, CsvError, DynOneDLookUpTable};
+    ///  let lut = DynOneDLookUpTable::<f64>::from_csv("800, 90\n1500, 140\n2500, 175\n").unwrap();
+    ///  assert_eq!(lut.breakpoints(), [800.0, 1500.0, 2500.0]);
+    ///
+    ///  let err = DynOneDLookUpTable::<f64>::from_csv("800, 90\n").unwrap_err();
+    ///  assert!(matches!(err, CsvError::InvalidTable { line: 2, error: MinLengthError, .. }));
+    /// ```
+    pub fn from_csv(csv: &str) -> Result<DynOneDLookUpTable<T>, CsvError> {
+        let curve = Curve::read(csv)?;
+
+        Self::new(&curve.x, &curve.y).map_err(|e| curve.locate(e))
+    }
+}
+
+impl<T: Float + Display, C: Cache<Key, T>> DynOneDLookUpTable<T, C> {
+    /// Writes the breakpoints and the values of the table as comma separated values, in the layout read
+    /// by [`DynOneDLookUpTable::from_csv`].
+    pub fn to_csv<W: Write>(&self, out: &mut W) -> FmtResult {
+        write_curve(out, &self.x, &self.y)
+    }
+}
@@ -1,44 +1,423 @@
+use crate::axis::{AxisPosition, IndexFraction};
 use crate::error::ConstructionError;
-use crate::error::ConstructionError::{ContainingNansOrInfinities, IncreasingDimOrderError, MinLengthError};
-use crate::EPSILON;
+use crate::error::ConstructionError::{
+    ContainingNansOrInfinities, DimensionMismatch, IncreasingDimOrderError, MinLengthError,
+};
+use crate::extrapolation::{Bounded, Extrapolation};
+use crate::{constant, LookUpError, EPSILON};
+use num::Float;
 
 pub(super) type Key = (u64, i16, i8);
 
-pub(in crate::oned_lut) fn is_object_constructible(xs: &[f64], ys: &[f64]) -> Result<bool, ConstructionError> {
+/// Interpolation schemes supported by the one dimensional look up tables.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interpolation<T = f64> {
+    /// Piecewise linear interpolation between the neighbouring breakpoints.
+    #[default]
+    Linear,
+    /// Piecewise cubic interpolation, which is twice continuously differentiable across the breakpoints.
+    /// The behavior at the end points is governed by the given boundary condition.
+    /// [Cubic Spline](https://en.wikipedia.org/wiki/Spline_interpolation)
+    CubicSpline(SplineBoundary<T>),
+    /// Piecewise cubic hermite interpolation, with the slopes chosen as per Fritsch–Carlson so that the
+    /// shape of the data is preserved. The interpolated value never overshoots the values at the
+    /// neighbouring breakpoints, and it is monotone wherever the data is monotone.
+    /// [Monotone Cubic Interpolation](https://en.wikipedia.org/wiki/Monotone_cubic_interpolation)
+    Pchip,
+    /// Piecewise cubic hermite interpolation, where the slope at a breakpoint is a weighted average of
+    /// the neighbouring secant slopes, chosen to suppress the oscillations around outliers in the data.
+    /// Since the slopes are estimated from two segments on either side of a breakpoint, tables with
+    /// less than four breakpoints fall back to linear interpolation.
+    /// [Akima Spline](https://en.wikipedia.org/wiki/Akima_spline)
+    Akima,
+    /// The modified Akima interpolation, which also avoids overshoots when the data is constant over
+    /// more than two consecutive breakpoints. It falls back to linear interpolation in the same cases as
+    /// [`Interpolation::Akima`].
+    Makima,
+    /// Zero order hold, which returns the value at the nearest breakpoint to the left of the index.
+    /// An index exactly on a breakpoint returns the value at that breakpoint.
+    Previous,
+    /// Returns the value at the nearest breakpoint to the right of the index. An index exactly on a
+    /// breakpoint returns the value at that breakpoint.
+    Next,
+    /// Returns the value at the breakpoint closest to the index. An index exactly on a breakpoint
+    /// returns the value at that breakpoint, and an index exactly midway between two breakpoints
+    /// returns the value at the right one.
+    Nearest,
+}
+
+impl<T> Interpolation<T> {
+    /// Returns true if the scheme is evaluated in the cubic hermite form, and hence needs the slopes at
+    /// the breakpoints to be computed at construction.
+    pub(in crate::oned_lut) fn needs_slopes(&self) -> bool {
+        matches!(
+            self,
+            Interpolation::CubicSpline(_) | Interpolation::Pchip | Interpolation::Akima | Interpolation::Makima
+        )
+    }
+}
+
+/// Boundary conditions for the cubic spline interpolation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SplineBoundary<T = f64> {
+    /// The second derivative of the spline vanishes at the first and the last breakpoints.
+    Natural,
+    /// The first derivative of the spline at the first and the last breakpoints are given by the
+    /// user, in that order.
+    Clamped(T, T),
+}
+
+pub(crate) fn is_object_constructible<T: Float>(xs: &[T], ys: &[T]) -> Result<bool, ConstructionError> {
     if xs.len() < 2 || ys.len() < 2 {
         return Err(MinLengthError);
     }
 
-    if xs.iter().any(|v| v.is_nan() || v.is_infinite()) || ys.iter().any(|v| v.is_nan() || v.is_infinite()) {
+    are_values_valid(xs.len(), ys)?;
+    are_breakpoints_valid(xs)
+}
+
+/// Checks the breakpoints of an axis, which should be at least two finite values in increasing order.
+pub(crate) fn are_breakpoints_valid<T: Float>(xs: &[T]) -> Result<bool, ConstructionError> {
+    if xs.len() < 2 {
+        return Err(MinLengthError);
+    }
+
+    if xs.iter().any(|v| v.is_nan() || v.is_infinite()) {
         return Err(ContainingNansOrInfinities);
     }
 
-    if !xs.windows(2).all(|c| c[1] - c[0] > EPSILON) {
+    if !xs.windows(2).all(|c| c[1] - c[0] > constant(EPSILON)) {
         return Err(IncreasingDimOrderError);
     }
 
     Ok(true)
 }
 
-pub(in crate::oned_lut) fn interpolate(x: &f64, xs: &[f64], ys: &[f64]) -> f64 {
-    if *x < xs[0] {
-        return ys[0];
+/// Checks the values of a table, whose breakpoints are taken from an already validated axis of length `n`.
+pub(in crate::oned_lut) fn are_values_valid<T: Float>(n: usize, ys: &[T]) -> Result<bool, ConstructionError> {
+    if ys.len() != n {
+        return Err(DimensionMismatch {
+            expected: n,
+            actual: ys.len(),
+        });
+    }
+
+    if ys.iter().any(|v| v.is_nan() || v.is_infinite()) {
+        return Err(ContainingNansOrInfinities);
+    }
+
+    Ok(true)
+}
+
+/// Checks that the parameters of the interpolation scheme can be used to construct a table.
+pub(in crate::oned_lut) fn is_interpolation_valid<T: Float>(
+    method: &Interpolation<T>,
+) -> Result<bool, ConstructionError> {
+    match method {
+        Interpolation::CubicSpline(SplineBoundary::Clamped(start, end)) if !start.is_finite() || !end.is_finite() => {
+            Err(ContainingNansOrInfinities)
+        }
+        _ => Ok(true),
     }
+}
 
-    if *x > xs[xs.len() - 1] {
-        return ys[ys.len() - 1];
+/// Computes the first derivatives of the interpolating curve at each of the breakpoints, for the
+/// interpolation schemes which are evaluated in the cubic hermite form. The slopes are written into
+/// `slopes`, and `scratch` is used as a working buffer; both should be as long as `xs`.
+pub(in crate::oned_lut) fn compute_slopes<T: Float>(
+    method: &Interpolation<T>,
+    xs: &[T],
+    ys: &[T],
+    slopes: &mut [T],
+    scratch: &mut [T],
+) {
+    match method {
+        Interpolation::Linear | Interpolation::Previous | Interpolation::Next | Interpolation::Nearest => {}
+        Interpolation::CubicSpline(boundary) => spline_slopes(boundary, xs, ys, slopes, scratch),
+        Interpolation::Pchip => pchip_slopes(xs, ys, slopes),
+        Interpolation::Akima => akima_slopes(xs, ys, slopes, false),
+        Interpolation::Makima => akima_slopes(xs, ys, slopes, true),
     }
+}
 
-    let lub = match xs.binary_search_by(|val| val.partial_cmp(x).unwrap()) {
-        // perform interpolation only when the value is not found.
-        Ok(ind) => return ys[ind],
-        Err(ind) => ind,
+/// The cubic spline slopes are the solution of a tridiagonal system of equations, which expresses the
+/// continuity of the second derivative at the interior breakpoints, along with the boundary conditions
+/// at the ends. The system is solved with the Thomas algorithm, where `scratch` holds the modified
+/// super diagonal, and `slopes` holds the modified right hand side before the back substitution.
+fn spline_slopes<T: Float>(boundary: &SplineBoundary<T>, xs: &[T], ys: &[T], slopes: &mut [T], scratch: &mut [T]) {
+    let n = xs.len();
+    let h = |i: usize| xs[i + 1] - xs[i];
+    let delta = |i: usize| (ys[i + 1] - ys[i]) / h(i);
+
+    // Each row of the system is given as (sub diagonal, diagonal, super diagonal, right hand side).
+    let (zero, one, two, three) = (T::zero(), T::one(), constant::<T>(2.0), constant::<T>(3.0));
+    let row = |i: usize| -> (T, T, T, T) {
+        match (i, boundary) {
+            (0, SplineBoundary::Natural) => (zero, two, one, three * delta(0)),
+            (0, SplineBoundary::Clamped(start, _)) => (zero, one, zero, *start),
+            (i, SplineBoundary::Natural) if i == n - 1 => (one, two, zero, three * delta(n - 2)),
+            (i, SplineBoundary::Clamped(_, end)) if i == n - 1 => (zero, one, zero, *end),
+            (i, _) => (
+                h(i),
+                two * (h(i - 1) + h(i)),
+                h(i - 1),
+                three * (h(i) * delta(i - 1) + h(i - 1) * delta(i)),
+            ),
+        }
     };
-    let prev = lub - 1;
-    let alpha = (x - xs[prev]) / (xs[lub] - xs[prev]);
 
+    let (_, b, c, r) = row(0);
+    scratch[0] = c / b;
+    slopes[0] = r / b;
+    for i in 1..n {
+        let (a, b, c, r) = row(i);
+        let m = b - a * scratch[i - 1];
+        scratch[i] = c / m;
+        slopes[i] = (r - a * slopes[i - 1]) / m;
+    }
+
+    for i in (0..n - 1).rev() {
+        slopes[i] = slopes[i] - scratch[i] * slopes[i + 1];
+    }
+}
+
+/// The interior slopes are the weighted harmonic mean of the secant slopes on either side of a breakpoint,
+/// and zero at local extrema. The end slopes use a one sided three point estimate, which is limited so
+/// that it doesn't introduce an overshoot in the end segments.
+fn pchip_slopes<T: Float>(xs: &[T], ys: &[T], slopes: &mut [T]) {
+    let n = xs.len();
+    let h = |i: usize| xs[i + 1] - xs[i];
+    let delta = |i: usize| (ys[i + 1] - ys[i]) / h(i);
+    let (two, three) = (constant::<T>(2.0), constant::<T>(3.0));
+
+    if n == 2 {
+        slopes[0] = delta(0);
+        slopes[1] = delta(0);
+        return;
+    }
+
+    for (i, slope) in slopes.iter_mut().enumerate().take(n - 1).skip(1) {
+        let (d1, d2) = (delta(i - 1), delta(i));
+        *slope = if d1 * d2 <= T::zero() {
+            T::zero()
+        } else {
+            let w1 = two * h(i) + h(i - 1);
+            let w2 = h(i) + two * h(i - 1);
+            (w1 + w2) / (w1 / d1 + w2 / d2)
+        };
+    }
+
+    let end_slope = |h1: T, h2: T, d1: T, d2: T| {
+        let d = ((two * h1 + h2) * d1 - h1 * d2) / (h1 + h2);
+        if d * d1 <= T::zero() {
+            T::zero()
+        } else if d1 * d2 <= T::zero() && d.abs() > three * d1.abs() {
+            three * d1
+        } else {
+            d
+        }
+    };
+    slopes[0] = end_slope(h(0), h(1), delta(0), delta(1));
+    slopes[n - 1] = end_slope(h(n - 2), h(n - 3), delta(n - 2), delta(n - 3));
+}
+
+/// The secant slopes are extended by two linearly extrapolated values on either end, so that every
+/// breakpoint has two segments on each side to weigh. When `modified` is set, the weights additionally
+/// account for the mean of the secants, which avoids overshoots on flat regions.
+fn akima_slopes<T: Float>(xs: &[T], ys: &[T], slopes: &mut [T], modified: bool) {
+    let n = xs.len() as isize;
+    if n < 4 {
+        return;
+    }
+
+    let (two, three) = (constant::<T>(2.0), constant::<T>(3.0));
+    let secant = |i: isize| (ys[i as usize + 1] - ys[i as usize]) / (xs[i as usize + 1] - xs[i as usize]);
+    let m = |i: isize| match i {
+        -2 => three * secant(0) - two * secant(1),
+        -1 => two * secant(0) - secant(1),
+        i if i == n - 1 => two * secant(n - 2) - secant(n - 3),
+        i if i == n => three * secant(n - 2) - two * secant(n - 3),
+        i => secant(i),
+    };
+    let weight = |a: T, b: T| {
+        if modified {
+            (b - a).abs() + (b + a).abs() / two
+        } else {
+            (b - a).abs()
+        }
+    };
+
+    for (i, slope) in (0..n).zip(slopes.iter_mut()) {
+        let (m1, m2, m3, m4) = (m(i - 2), m(i - 1), m(i), m(i + 1));
+        let (w1, w2) = (weight(m3, m4), weight(m1, m2));
+        *slope = if w1 + w2 == T::zero() {
+            (m2 + m3) / two
+        } else {
+            (w1 * m2 + w2 * m3) / (w1 + w2)
+        };
+    }
+}
+
+/// Evaluates the cubic hermite polynomial on a segment of width `h`, at the normalized position `t`,
+/// given the values and the slopes at both the ends of the segment.
+#[inline]
+fn hermite<T: Float>(t: T, h: T, y1: T, y2: T, d1: T, d2: T) -> T {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let (two, three) = (constant::<T>(2.0), constant::<T>(3.0));
+
+    (two * t3 - three * t2 + T::one()) * y1
+        + (t3 - two * t2 + t) * h * d1
+        + (three * t2 - two * t3) * y2
+        + (t3 - t2) * h * d2
+}
+
+/// Evaluates the derivative of the cubic hermite polynomial with respect to the index, on a segment of
+/// width `h`, at the normalized position `t`.
+#[inline]
+fn hermite_slope<T: Float>(t: T, h: T, y1: T, y2: T, d1: T, d2: T) -> T {
+    let t2 = t * t;
+    let (two, three, four, six) = (constant::<T>(2.0), constant::<T>(3.0), constant::<T>(4.0), constant::<T>(6.0));
+
+    (six * t2 - six * t) * (y1 - y2) / h + (three * t2 - four * t + T::one()) * d1 + (three * t2 - two * t) * d2
+}
+
+pub(crate) fn interpolate<T: Float>(
+    x: &T,
+    xs: &[T],
+    ys: &[T],
+    slopes: &[T],
+    method: &Interpolation<T>,
+    extrapolation: &Extrapolation<T>,
+    position: Option<&AxisPosition<T>>,
+) -> Result<T, LookUpError> {
+    let x = match extrapolation.apply(*x, xs, 0)? {
+        Bounded::Index(x) => x,
+        Bounded::Value(y) => return Ok(y),
+    };
+
+    // Only the linear extrapolation leaves the index outside the range, in which case the end segment
+    // is continued.
+    let last = xs.len() - 1;
+    if x < xs[0] {
+        return Ok(ys[0] + (x - xs[0]) * (ys[1] - ys[0]) / (xs[1] - xs[0]));
+    }
+
+    if x > xs[last] {
+        return Ok(ys[last] + (x - xs[last]) * (ys[last] - ys[last - 1]) / (xs[last] - xs[last - 1]));
+    }
+
+    let (prev, lub) = AxisPosition::locate(position, x, xs);
+    // perform interpolation only when the value is not found.
+    if prev == lub {
+        return Ok(ys[prev]);
+    }
+
+    Ok(segment(prev, (x - xs[prev]) / (xs[lub] - xs[prev]), xs, ys, slopes, method))
+}
+
+/// Differentiates the interpolated curve at the index `x`. Strictly between the breakpoints, this is the
+/// derivative of the segment; on a breakpoint, it is the derivative of the segment to the right of it,
+/// except for the last breakpoint, where it is that of the segment to the left. Outside the range of the
+/// breakpoints, it is the derivative of the extrapolated curve: zero where it is constant, the slope of
+/// the end segment for the linear extrapolation, and the derivative at the wrapped index for the
+/// periodic one.
+pub(in crate::oned_lut) fn differentiate<T: Float>(
+    x: &T,
+    xs: &[T],
+    ys: &[T],
+    slopes: &[T],
+    method: &Interpolation<T>,
+    extrapolation: &Extrapolation<T>,
+) -> Result<T, LookUpError> {
+    let last = xs.len() - 1;
+    let is_clamped = matches!(extrapolation, Extrapolation::Clamp) && !(xs[0] <= *x && *x <= xs[last]);
+    let x = match extrapolation.apply(*x, xs, 0)? {
+        Bounded::Index(_) if is_clamped => return Ok(T::zero()),
+        Bounded::Index(x) => x,
+        Bounded::Value(y) if y.is_nan() => return Ok(y),
+        Bounded::Value(_) => return Ok(T::zero()),
+    };
+
+    // Only the linear extrapolation leaves the index outside the range, where the end segment is continued.
+    let secant = |prev: usize| (ys[prev + 1] - ys[prev]) / (xs[prev + 1] - xs[prev]);
+    if x < xs[0] {
+        return Ok(secant(0));
+    }
+
+    if x > xs[last] {
+        return Ok(secant(last - 1));
+    }
+
+    let (prev, lub) = AxisPosition::locate(None, x, xs);
+    let (prev, alpha) = match (prev == lub, prev == last) {
+        (false, _) => (prev, (x - xs[prev]) / (xs[lub] - xs[prev])),
+        (true, false) => (prev, T::zero()),
+        (true, true) => (prev - 1, T::one()),
+    };
+
+    Ok(match method {
+        Interpolation::Previous | Interpolation::Next | Interpolation::Nearest => T::zero(),
+        Interpolation::Linear => secant(prev),
+        Interpolation::Akima | Interpolation::Makima if xs.len() < 4 => secant(prev),
+        Interpolation::CubicSpline(_) | Interpolation::Pchip | Interpolation::Akima | Interpolation::Makima => {
+            let lub = prev + 1;
+            hermite_slope(alpha, xs[lub] - xs[prev], ys[prev], ys[lub], slopes[prev], slopes[lub])
+        }
+    })
+}
+
+/// Interpolates at the position given by a prelookup, relative to the breakpoints `xs`. The positions
+/// within the range of the breakpoints are evaluated directly on their segment, while the others are
+/// converted back to an index, to apply the extrapolation policy.
+pub(in crate::oned_lut) fn interpolate_with<T: Float>(
+    at: &IndexFraction<T>,
+    xs: &[T],
+    ys: &[T],
+    slopes: &[T],
+    method: &Interpolation<T>,
+    extrapolation: &Extrapolation<T>,
+) -> Result<T, LookUpError> {
+    let (index, fraction) = (at.index.min(xs.len() - 2), at.fraction);
+
+    if fraction == T::zero() {
+        Ok(ys[index])
+    } else if fraction == T::one() {
+        Ok(ys[index + 1])
+    } else if fraction > T::zero() && fraction < T::one() {
+        Ok(segment(index, fraction, xs, ys, slopes, method))
+    } else {
+        let x = xs[index] + fraction * (xs[index + 1] - xs[index]);
+        interpolate(&x, xs, ys, slopes, method, extrapolation, None)
+    }
+}
+
+/// Evaluates the interpolation scheme on the segment starting at the breakpoint `prev`, at the
+/// normalized position `alpha` within the segment.
+#[inline]
+pub(in crate::oned_lut) fn segment<T: Float>(
+    prev: usize,
+    alpha: T,
+    xs: &[T],
+    ys: &[T],
+    slopes: &[T],
+    method: &Interpolation<T>,
+) -> T {
+    let lub = prev + 1;
     let y1 = &ys[prev];
     let y2 = &ys[lub];
 
-    y1 + alpha * (y2 - y1)
+    match method {
+        Interpolation::Linear => *y1 + alpha * (*y2 - *y1),
+        Interpolation::Akima | Interpolation::Makima if xs.len() < 4 => *y1 + alpha * (*y2 - *y1),
+        Interpolation::Previous => *y1,
+        Interpolation::Next => *y2,
+        Interpolation::Nearest if alpha < constant(0.5) => *y1,
+        Interpolation::Nearest => *y2,
+        Interpolation::CubicSpline(_) | Interpolation::Pchip | Interpolation::Akima | Interpolation::Makima => {
+            hermite(alpha, xs[lub] - xs[prev], *y1, *y2, slopes[prev], slopes[lub])
+        }
+    }
 }
@@ -0,0 +1,221 @@
+use crate::constant;
+use crate::error::LookUpError;
+use crate::oned_lut::interpolation::segment;
+use crate::oned_lut::Interpolation;
+use num::Float;
+
+/// The number of times a curved segment is halved to find a solution, which leaves it narrower than the
+/// precision of the breakpoints.
+const BISECTIONS: usize = 64;
+
+/// How a curve is evaluated between its breakpoints, which decides how it is inverted.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Segments {
+    /// Straight lines between the breakpoints, which are inverted analytically.
+    Linear,
+    /// Cubic polynomials, which are split at their turning points into the pieces where they are
+    /// monotonic, and inverted numerically on each of the pieces.
+    Cubic,
+    /// Constant between the breakpoints, so only the breakpoints themselves are inverted.
+    Steps,
+}
+
+/// A curve given by its values at the breakpoints, and the interpolation scheme between them.
+pub(crate) trait Curve<T> {
+    /// Returns the breakpoints of the curve.
+    fn xs(&self) -> &[T];
+
+    /// Returns the value of the curve at the breakpoint `k`.
+    fn y(&self, k: usize) -> T;
+
+    /// Returns how the curve is evaluated between its breakpoints.
+    fn segments(&self) -> Segments;
+
+    /// Evaluates the curve on the segment starting at the breakpoint `prev`, at the normalized position
+    /// `alpha` within the segment.
+    fn segment(&self, prev: usize, alpha: T) -> T;
+}
+
+/// The curve of a one dimensional table.
+pub(in crate::oned_lut) struct Table<'a, T> {
+    pub(in crate::oned_lut) xs: &'a [T],
+    pub(in crate::oned_lut) ys: &'a [T],
+    pub(in crate::oned_lut) slopes: &'a [T],
+    pub(in crate::oned_lut) interpolation: &'a Interpolation<T>,
+}
+
+impl<T: Float> Curve<T> for Table<'_, T> {
+    fn xs(&self) -> &[T] {
+        self.xs
+    }
+
+    fn y(&self, k: usize) -> T {
+        self.ys[k]
+    }
+
+    fn segments(&self) -> Segments {
+        match self.interpolation {
+            Interpolation::Linear => Segments::Linear,
+            Interpolation::Previous | Interpolation::Next | Interpolation::Nearest => Segments::Steps,
+            _ => Segments::Cubic,
+        }
+    }
+
+    fn segment(&self, prev: usize, alpha: T) -> T {
+        segment(prev, alpha, self.xs, self.ys, self.slopes, self.interpolation)
+    }
+}
+
+/// Returns the index where the curve takes the `target` value, if the curve is strictly monotonic: its
+/// values at the breakpoints are, and none of its segments turns back between them.
+pub(crate) fn invert<T: Float>(target: T, curve: &impl Curve<T>) -> Result<T, LookUpError> {
+    let (xs, last) = (curve.xs(), curve.xs().len() - 1);
+    let rising = curve.y(1) > curve.y(0);
+    let is_ordered = |a: T, b: T| if rising { a < b } else { a > b };
+    let is_monotonic = (1..=last).all(|k| is_ordered(curve.y(k - 1), curve.y(k)))
+        && (0..last).all(|k| {
+            let (bounds, n) = pieces(curve, k);
+            (1..n).all(|i| !is_ordered(bounds[i].1, bounds[i - 1].1))
+        });
+    if curve.segments() == Segments::Steps || !is_monotonic {
+        return Err(LookUpError::NotMonotonic);
+    }
+
+    let (first, last_value) = (curve.y(0).min(curve.y(last)), curve.y(0).max(curve.y(last)));
+    if !(first <= target && target <= last_value) {
+        return Err(LookUpError::ValueOutOfRange);
+    }
+
+    // Search for the first breakpoint which isn't before the target, along the direction of the values.
+    let is_before = |k: usize| if rising { curve.y(k) < target } else { curve.y(k) > target };
+    let (mut lo, mut lub) = (0, last);
+    while lo < lub {
+        let mid = (lo + lub) / 2;
+        if is_before(mid) {
+            lo = mid + 1;
+        } else {
+            lub = mid;
+        }
+    }
+
+    if curve.y(lub) == target {
+        return Ok(xs[lub]);
+    }
+
+    let (y1, y2) = (curve.y(lub - 1), curve.y(lub));
+    Ok(solve(curve, lub - 1, target, [(T::zero(), y1), (T::one(), y2)]))
+}
+
+/// Returns the indices where the curve takes the `target` value, in increasing order: one on each of
+/// the pieces of the segments which are monotonic, and whose values at the ends enclose the target, and
+/// the breakpoints and the turning points at the target value. A segment which is constant at the target
+/// value only yields its ends, and so do the steps.
+pub(crate) fn solutions<'a, T: Float + 'a>(target: T, curve: impl Curve<T> + 'a) -> impl Iterator<Item = T> + 'a {
+    let (first, n) = ((curve.y(0) == target).then(|| curve.xs()[0]), curve.xs().len());
+    let rest = (0..n - 1).flat_map(move |k| roots(&curve, k, target).into_iter().flatten());
+
+    first.into_iter().chain(rest)
+}
+
+/// Returns the indices on the segment starting at `prev` where the curve takes the `target` value, except
+/// for the first breakpoint of the segment, in increasing order.
+fn roots<T: Float>(curve: &impl Curve<T>, prev: usize, target: T) -> [Option<T>; 4] {
+    // A cubic has up to three roots, besides the end of the segment.
+    let mut roots = [None; 4];
+    if curve.segments() != Segments::Steps {
+        let (bounds, n) = pieces(curve, prev);
+        let mut found = 0;
+        for i in 1..n {
+            let ((_, v0), (t1, v1)) = (bounds[i - 1], bounds[i]);
+            let root = if (v0 < target && target < v1) || (v1 < target && target < v0) {
+                Some(solve(curve, prev, target, [bounds[i - 1], bounds[i]]))
+            } else {
+                (i + 1 < n && v1 == target).then(|| position(curve.xs(), prev, t1))
+            };
+
+            if let Some(root) = root.filter(|_| found < 3) {
+                roots[found] = Some(root);
+                found += 1;
+            }
+        }
+    }
+    roots[3] = (curve.y(prev + 1) == target).then(|| curve.xs()[prev + 1]);
+
+    roots
+}
+
+/// Returns the normalized positions which split the segment starting at `prev` into the pieces where the
+/// curve is monotonic, along with the values of the curve at them, and their number: the ends of the
+/// segment, and the turning points of a cubic between them.
+fn pieces<T: Float>(curve: &impl Curve<T>, prev: usize) -> ([(T, T); 4], usize) {
+    let mut bounds = [(T::zero(), curve.y(prev)); 4];
+    let mut n = 1;
+    if curve.segments() == Segments::Cubic {
+        for t in turning_points(|t| curve.segment(prev, t)).into_iter().flatten() {
+            bounds[n] = (t, curve.segment(prev, t));
+            n += 1;
+        }
+    }
+    bounds[n] = (T::one(), curve.y(prev + 1));
+
+    (bounds, n + 1)
+}
+
+/// Returns the positions strictly between 0 and 1, in increasing order, where the derivative of the cubic
+/// polynomial `p` changes its sign. The polynomial is fitted through its values at four equally spaced
+/// positions, which is exact for any polynomial of up to the third degree.
+fn turning_points<T: Float>(p: impl Fn(T) -> T) -> [Option<T>; 2] {
+    let c = |v: f64| constant::<T>(v);
+    let third = T::one() / c(3.0);
+    let [p0, p1, p2, p3] = [p(T::zero()), p(third), p(T::one() - third), p(T::one())];
+
+    // The coefficients of the derivative `a t^2 + b t + d` of the polynomial.
+    let a = c(13.5) * (c(3.0) * (p1 - p2) + p3 - p0);
+    let b = c(9.0) * (c(2.0) * p0 - c(5.0) * p1 + c(4.0) * p2 - p3);
+    let d = (c(18.0) * p1 - c(11.0) * p0 - c(9.0) * p2 + c(2.0) * p3) / c(2.0);
+
+    let roots = if a.is_zero() {
+        [(!b.is_zero()).then(|| -d / b), None]
+    } else {
+        // A double root doesn't change the sign of the derivative.
+        let discriminant = b * b - c(4.0) * a * d;
+        if discriminant <= T::zero() {
+            [None; 2]
+        } else {
+            let q = -(b + b.signum() * discriminant.sqrt()) / c(2.0);
+            [Some((q / a).min(d / q)), Some((q / a).max(d / q))]
+        }
+    };
+
+    roots.map(|root| root.filter(|&t| T::zero() < t && t < T::one()))
+}
+
+/// Returns the index on the segment starting at `prev` where the curve takes the `target` value, on the
+/// piece of the segment between the normalized positions `t0` and `t1`, where the curve is monotonic, and
+/// its values `v0` and `v1` strictly enclose the target.
+fn solve<T: Float>(curve: &impl Curve<T>, prev: usize, target: T, [(t0, v0), (t1, v1)]: [(T, T); 2]) -> T {
+    let alpha = match curve.segments() {
+        Segments::Cubic => {
+            // Bisect the piece, keeping the target between the values at both the ends.
+            let (mut lo, mut hi) = (t0, t1);
+            for _ in 0..BISECTIONS {
+                let mid = (lo + hi) / constant(2.0);
+                if (curve.segment(prev, mid) < target) == (v1 > v0) {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+
+            (lo + hi) / constant(2.0)
+        }
+        _ => t0 + (t1 - t0) * (target - v0) / (v1 - v0),
+    };
+
+    position(curve.xs(), prev, alpha)
+}
+
+/// Returns the index at the normalized position `alpha` on the segment starting at `prev`.
+fn position<T: Float>(xs: &[T], prev: usize, alpha: T) -> T {
+    xs[prev] + alpha * (xs[prev + 1] - xs[prev])
+}
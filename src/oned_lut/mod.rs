@@ -6,34 +6,57 @@
 //! returned. On the other hand, if the value is not found, then an interpolation (most of the times
 //! it is linear to get better performance) of the values is performed to get an estimate of the
 //! actual function. In practice this gives a reasonable approximation of the function.
-//! Ofcourse when the values are out of bounds, then the last values are returned always.
+//! When the values are out of bounds, then by default the last values are returned, which can be
+//! changed by setting a different [`Extrapolation`] policy on the table.
 
+mod csv;
 mod interpolation;
+mod inverse;
+#[cfg(feature = "serde")]
+mod serialization;
 
-use super::oned_lut::interpolation::{interpolate, is_object_constructible, Key};
-use crate::error::ConstructionError;
-use core::cell::RefCell;
-
-#[cfg(not(feature = "no-std"))]
-use std::collections::HashMap;
-
-#[cfg(feature = "no-std")]
-use hashbrown::HashMap;
+use super::oned_lut::interpolation::{
+    are_values_valid, compute_slopes, differentiate, interpolate, interpolate_with, is_interpolation_valid, Key,
+};
+use super::oned_lut::inverse::Table;
+use crate::compression::{compress_curve, CompressionError};
+use crate::error::{ConstructionError, LookUpError};
+use crate::{
+    to_vec, zeroed, Axis, AxisPosition, AxisRange, Cache, CacheStats, Checked, Compressed, Extrapolation,
+    IndexFraction, LocalCache, Vec,
+};
 
 use num::Float;
 
-/// Linear interpolation with nearest neighbor extrapolation when index is outside support region,
-/// and with Caching support to enable fast lookups on same values.
+pub(crate) use interpolation::{are_breakpoints_valid, interpolate as interpolate_curve, is_object_constructible};
+pub use interpolation::{Interpolation, SplineBoundary};
+pub(crate) use inverse::{invert, solutions, Curve, Segments};
+
+/// Linear (or any other supported [`Interpolation`]) interpolation with nearest neighbor (or any other
+/// [`Extrapolation`]) extrapolation when index is outside support region, and with Caching support to
+/// enable fast lookups on same values.
 /// This structure is an owning structure in that, it owns the array values passed into it. It is
 /// useful for defining LUTs at compile time.
+/// T is the type of the breakpoints and the values, which can be any floating point type eg. `f32` on
+/// targets with only a single precision FPU, and defaults to `f64`.
+/// C is the [`Cache`] of the table, which can be changed with [`OneDLookUpTable::with_cache`], eg. to
+/// share the table across threads.
+/// ```
+///  use look_up_table::OneDLookUpTable;
+///  let lut = OneDLookUpTable::new([1.0f32, 2.0, 3.0], [2.0, 4.0, 8.0]).unwrap();
+///  assert!((lut.get(&2.5) - 6.0).abs() < f32::EPSILON);
+/// ```
 #[derive(Debug)]
-pub struct OneDLookUpTable<const N: usize> {
-    x: [f64; N],
-    y: [f64; N],
-    cache: RefCell<HashMap<Key, f64>>,
+pub struct OneDLookUpTable<const N: usize, T = f64, C = LocalCache<Key, T>> {
+    x: [T; N],
+    y: [T; N],
+    slopes: [T; N], // Slopes at the breakpoints, for the interpolation schemes which need them.
+    interpolation: Interpolation<T>,
+    extrapolation: Extrapolation<T>,
+    cache: C,
 }
 
-impl<const N: usize> OneDLookUpTable<N> {
+impl<const N: usize, T: Float> OneDLookUpTable<N, T> {
     /// Constructs a `OneDLookUpTable` object, given the input arrays `x` and `y` modelling the sample
     /// points of a uni-variate function.
     /// If the `x` values are not sorted in ascending order:
@@ -56,68 +79,761 @@ impl<const N: usize> OneDLookUpTable<N> {
     ///  let lut = OneDLookUpTable::new([1.0], [f64::NEG_INFINITY]);
     ///  assert!(matches!(lut.err().unwrap(), ConstructionError::MinLengthError))
     /// ```
-    pub fn new(x: [f64; N], y: [f64; N]) -> Result<OneDLookUpTable<N>, ConstructionError> {
-        is_object_constructible(&x, &y).map(|_| OneDLookUpTable {
+    pub fn new(x: [T; N], y: [T; N]) -> Result<OneDLookUpTable<N, T>, ConstructionError> {
+        Self::with_interpolation(x, y, Interpolation::Linear)
+    }
+
+    /// Constructs a `OneDLookUpTable` object, which uses the given `interpolation` scheme between the
+    /// breakpoints. Any coefficients needed by the scheme are computed once here, and the inputs are
+    /// validated in the same way as [`OneDLookUpTable::new`].
+    /// ```
+    ///  use look_up_table::{Interpolation, OneDLookUpTable, SplineBoundary};
+    ///  let spline = Interpolation::CubicSpline(SplineBoundary::Natural);
+    ///  let lut = OneDLookUpTable::with_interpolation([0.0f64, 1.0, 2.0], [0.0, 1.0, 0.0], spline).unwrap();
+    ///  assert!((lut.get(&0.5) - 0.6875).abs() < 1e-12);
+    /// ```
+    ///
+    /// If the clamped slopes of a cubic spline contain NANs or Infinities
+    /// ```
+    ///  use look_up_table::{ConstructionError, Interpolation, OneDLookUpTable, SplineBoundary};
+    ///  let spline = Interpolation::CubicSpline(SplineBoundary::Clamped(0.0, f64::NAN));
+    ///  let lut = OneDLookUpTable::with_interpolation([0.0, 1.0, 2.0], [0.0, 1.0, 0.0], spline);
+    ///  assert!(matches!(lut.err().unwrap(), ConstructionError::ContainingNansOrInfinities))
+    /// ```
+    pub fn with_interpolation(
+        x: [T; N],
+        y: [T; N],
+        interpolation: Interpolation<T>,
+    ) -> Result<OneDLookUpTable<N, T>, ConstructionError> {
+        is_object_constructible(&x, &y)?;
+
+        Self::with_validated_breakpoints(x, y, interpolation)
+    }
+
+    /// Constructs a `OneDLookUpTable` object, which takes a copy of its breakpoints from the shared `axis`.
+    /// The breakpoints are not validated again, only the values `y` and their count are.
+    /// ```
+    ///  use look_up_table::{Axis, Interpolation, OneDLookUpTable};
+    ///  let speed = Axis::new(&[1000.0f64, 2000.0, 3000.0]).unwrap();
+    ///  let torque = OneDLookUpTable::from_axis(&speed, [100.0, 180.0, 150.0], Interpolation::Linear).unwrap();
+    ///  assert!((torque.get_at(&speed.search(2500.0)) - 165.0).abs() < 1e-12);
+    /// ```
+    ///
+    /// If the axis doesn't have `N` breakpoints:
+    /// ```
+    ///  use look_up_table::{Axis, ConstructionError::DimensionMismatch, Interpolation, OneDLookUpTable};
+    ///  let speed = Axis::new(&[1000.0f64, 2000.0, 3000.0]).unwrap();
+    ///  let torque = OneDLookUpTable::from_axis(&speed, [100.0, 180.0], Interpolation::Linear);
+    ///  assert!(matches!(torque.err().unwrap(), DimensionMismatch { expected: 3, actual: 2 }));
+    /// ```
+    pub fn from_axis(
+        axis: &Axis<T>,
+        y: [T; N],
+        interpolation: Interpolation<T>,
+    ) -> Result<OneDLookUpTable<N, T>, ConstructionError> {
+        let xs = axis.breakpoints();
+        are_values_valid(xs.len(), &y)?;
+
+        Self::with_validated_breakpoints(core::array::from_fn(|i| xs[i]), y, interpolation)
+    }
+
+    fn with_validated_breakpoints(
+        x: [T; N],
+        y: [T; N],
+        interpolation: Interpolation<T>,
+    ) -> Result<OneDLookUpTable<N, T>, ConstructionError> {
+        is_interpolation_valid(&interpolation)?;
+
+        let mut slopes = [T::zero(); N];
+        compute_slopes(&interpolation, &x, &y, &mut slopes, &mut [T::zero(); N]);
+
+        Ok(OneDLookUpTable {
             x,
             y,
-            cache: RefCell::new(HashMap::new()),
+            slopes,
+            interpolation,
+            extrapolation: Extrapolation::Clamp,
+            cache: LocalCache::default(),
         })
     }
+}
+
+impl<const N: usize, T: Float, C: Cache<Key, T>> OneDLookUpTable<N, T, C> {
+    /// Returns the table with a different [`Cache`], which starts out empty. See [`crate::NoCache`] for an
+    /// example.
+    pub fn with_cache<D: Cache<Key, T> + Default>(self) -> OneDLookUpTable<N, T, D> {
+        OneDLookUpTable {
+            x: self.x,
+            y: self.y,
+            slopes: self.slopes,
+            interpolation: self.interpolation,
+            extrapolation: self.extrapolation,
+            cache: D::default(),
+        }
+    }
+
+    /// Returns the counts of the hits, misses and evictions of the cache of the table.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Sets the policy for the lookups outside the range of the breakpoints. The default policy is
+    /// [`Extrapolation::Clamp`].
+    /// ```
+    ///  use look_up_table::{Extrapolation, OneDLookUpTable};
+    ///  let mut lut = OneDLookUpTable::new([1.0f64, 2.0, 3.0], [2.0, 4.0, 8.0]).unwrap();
+    ///  lut.set_extrapolation(Extrapolation::Linear);
+    ///  assert!((lut.get(&4.0) - 12.0).abs() < f64::EPSILON);
+    /// ```
+    pub fn set_extrapolation(&mut self, extrapolation: Extrapolation<T>) {
+        self.extrapolation = extrapolation;
+        self.cache.clear();
+    }
 
     /// Returns an interpolated value for the given `index` or x value. If the `index`
     /// value is present in the array, it directly returns the corresponding y value without any
-    /// interpolation. If the `index` value lies outside the range, then it clamps the values to the
-    /// boundary values.
-    pub fn get(&self, index: &f64) -> f64 {
+    /// interpolation. If the `index` value lies outside the range, then the extrapolation policy
+    /// decides the value, which by default clamps the values to the boundary values. If the policy is
+    /// [`Extrapolation::Error`], then NaN is returned.
+    pub fn get(&self, index: &T) -> T {
         // Due to index traits requirements of returning references, we cannot use it to overload.
+        self.try_get(index).unwrap_or(T::nan())
+    }
 
-        // There could be a possibility that the values which are very close in real number line to
-        // have different bit patterns, so this code would do a full interpolation for nearly identical
-        // value lookups.
-        let ind = index.integer_decode();
-        if self.cache.borrow().contains_key(&ind) {
-            return *self.cache.borrow().get(&ind).unwrap();
-        }
+    /// Returns an interpolated value for the given `index` similar to [`OneDLookUpTable::get`], except
+    /// that an index outside the range of the breakpoints is reported as an error, when the
+    /// extrapolation policy is [`Extrapolation::Error`].
+    /// ```
+    ///  use look_up_table::{Extrapolation, LookUpError, OneDLookUpTable};
+    ///  let mut lut = OneDLookUpTable::new([1.0, 2.0, 3.0], [2.0, 4.0, 8.0]).unwrap();
+    ///  lut.set_extrapolation(Extrapolation::Error);
+    ///  assert_eq!(lut.try_get(&0.0), Err(LookUpError::OutOfRange { axis: 0 }));
+    /// ```
+    pub fn try_get(&self, index: &T) -> Result<T, LookUpError> {
+        self.view().try_get(index)
+    }
+
+    /// Returns an interpolated value for the given `index` similar to [`OneDLookUpTable::try_get`], along
+    /// with where the index lies relative to the breakpoints, so that the extrapolated values can be told
+    /// apart from the interpolated ones.
+    /// ```
+    ///  use look_up_table::{AxisRange, OneDLookUpTable};
+    ///  let lut = OneDLookUpTable::new([1.0, 2.0, 3.0], [2.0, 4.0, 8.0]).unwrap();
+    ///  let checked = lut.get_checked(&4.0).unwrap();
+    ///  assert_eq!((checked.value, checked.range), (8.0, [AxisRange::Above]));
+    /// ```
+    pub fn get_checked(&self, index: &T) -> Result<Checked<T, 1>, LookUpError> {
+        self.view().get_checked(index)
+    }
+
+    /// Returns an interpolated value for the index at the given `position`, reusing the search done on
+    /// the shared [`Axis`] of the table. The lookup bypasses the cache, since it is already cheap.
+    pub fn get_at(&self, position: &AxisPosition<T>) -> T {
+        self.try_get_at(position).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value for the index at the given `position` similar to
+    /// [`OneDLookUpTable::get_at`], or an error if the index lies outside the range of the breakpoints,
+    /// and the extrapolation policy is [`Extrapolation::Error`].
+    pub fn try_get_at(&self, position: &AxisPosition<T>) -> Result<T, LookUpError> {
+        self.view().try_get_at(position)
+    }
 
-        let y = interpolate(index, &self.x, &self.y);
+    /// Returns an interpolated value at the position given by a prelookup on an [`Axis`] with the same
+    /// breakpoints, which skips the search of the breakpoints. The positions outside the range of the
+    /// breakpoints are subject to the extrapolation policy, same as [`OneDLookUpTable::get`].
+    /// ```
+    ///  use look_up_table::{Axis, OneDLookUpTable};
+    ///  let axis = Axis::new(&[1.0f64, 2.0, 3.0]).unwrap();
+    ///  let lut = OneDLookUpTable::new([1.0, 2.0, 3.0], [2.0, 4.0, 8.0]).unwrap();
+    ///  assert!((lut.interpolate_with(&axis.prelookup(2.5)) - 6.0).abs() < f64::EPSILON);
+    /// ```
+    pub fn interpolate_with(&self, at: &IndexFraction<T>) -> T {
+        self.try_interpolate_with(at).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value at the position given by a prelookup similar to
+    /// [`OneDLookUpTable::interpolate_with`], or an error if the position lies outside the range of the
+    /// breakpoints, and the extrapolation policy is [`Extrapolation::Error`].
+    pub fn try_interpolate_with(&self, at: &IndexFraction<T>) -> Result<T, LookUpError> {
+        self.view().try_interpolate_with(at)
+    }
+
+    /// Returns the index at which the table takes the given `value`, i.e. the inverse of
+    /// [`OneDLookUpTable::get`], or NaN if there is no unique index, as described in
+    /// [`OneDLookUpTable::try_inverse`].
+    /// ```
+    ///  use look_up_table::OneDLookUpTable;
+    ///  let throttle_to_torque = OneDLookUpTable::new([0.0f64, 0.2, 0.5, 1.0], [0.0, 40.0, 130.0, 180.0]).unwrap();
+    ///  assert!((throttle_to_torque.inverse(&85.0) - 0.35).abs() < 1e-12);
+    ///  assert!(throttle_to_torque.inverse(&200.0).is_nan());
+    /// ```
+    pub fn inverse(&self, value: &T) -> T {
+        self.try_inverse(value).unwrap_or(T::nan())
+    }
+
+    /// Returns the index at which the table takes the given `value`, if the table is strictly increasing
+    /// or strictly decreasing, which is checked on every call: both its values at the breakpoints, and the
+    /// cubic segments between them, which can overshoot the values at their ends. The linear segments
+    /// are inverted analytically, and the cubic ones numerically. The extrapolation policy doesn't apply:
+    /// the `value` must lie within the range of the values of the table.
+    /// ```
+    ///  use look_up_table::{Interpolation, LookUpError, OneDLookUpTable};
+    ///  let lut = OneDLookUpTable::with_interpolation([1.0f64, 2.0, 3.0], [8.0, 4.0, 2.0], Interpolation::Pchip).unwrap();
+    ///  let x = lut.try_inverse(&5.0).unwrap();
+    ///  assert!((lut.get(&x) - 5.0).abs() < 1e-12);
+    ///  assert_eq!(lut.try_inverse(&1.0), Err(LookUpError::ValueOutOfRange));
+    /// ```
+    ///
+    /// If the values are not strictly monotonic, a cubic turns back between the breakpoints, or the
+    /// interpolation is one of the steps, which are constant between the breakpoints:
+    /// ```
+    ///  use look_up_table::{Interpolation, LookUpError, OneDLookUpTable, SplineBoundary};
+    ///  let lut = OneDLookUpTable::new([1.0, 2.0, 3.0], [2.0, 4.0, 3.0]).unwrap();
+    ///  assert_eq!(lut.try_inverse(&3.0), Err(LookUpError::NotMonotonic));
+    ///  // The natural spline dips between the first and the third breakpoints.
+    ///  let spline = Interpolation::CubicSpline(SplineBoundary::Natural);
+    ///  let lut = OneDLookUpTable::with_interpolation([0.0, 1.0, 2.0, 3.0], [0.0, 0.5, 1.0, 10.0], spline).unwrap();
+    ///  assert_eq!(lut.try_inverse(&0.9), Err(LookUpError::NotMonotonic));
+    /// ```
+    pub fn try_inverse(&self, value: &T) -> Result<T, LookUpError> {
+        self.view().try_inverse(value)
+    }
 
-        self.cache.borrow_mut().insert(ind, y);
+    /// Returns all the indices within the range of the breakpoints at which the table takes the given
+    /// `value`, in increasing order, for the tables whose values are not monotonic. The cubic segments are
+    /// split at their turning points, so a segment which overshoots the `value` between its breakpoints
+    /// yields each of its solutions. A segment which is constant at the `value` only yields its
+    /// breakpoints, and so do the steps, which take the `value` at the breakpoints where it is found.
+    /// ```
+    ///  use look_up_table::OneDLookUpTable;
+    ///  let lut = OneDLookUpTable::new([1.0, 2.0, 3.0, 4.0], [2.0, 4.0, 3.0, 3.0]).unwrap();
+    ///  assert_eq!(lut.inverse_all(&3.0).collect::<Vec<_>>(), [1.5, 3.0, 4.0]);
+    /// ```
+    pub fn inverse_all(&self, value: &T) -> impl Iterator<Item = T> + '_ {
+        self.view().inverse_all(value)
+    }
 
-        *self.cache.borrow().get(&ind).unwrap()
+    /// Returns the derivative of the table at the given `index`, with respect to the index, or NaN if the
+    /// index lies outside the range of the breakpoints, and the extrapolation policy is
+    /// [`Extrapolation::Error`]. See [`OneDLookUpTable::try_derivative`].
+    /// ```
+    ///  use look_up_table::OneDLookUpTable;
+    ///  let lut = OneDLookUpTable::new([0.0, 1.0, 3.0], [0.0, 2.0, 3.0]).unwrap();
+    ///  assert_eq!(lut.derivative(&0.5), 2.0);
+    ///  assert_eq!(lut.derivative(&2.0), 0.5);
+    /// ```
+    pub fn derivative(&self, index: &T) -> T {
+        self.try_derivative(index).unwrap_or(T::nan())
+    }
+
+    /// Returns the derivative of the table at the given `index`, with respect to the index, which is
+    /// evaluated analytically for the interpolation scheme of the table: piecewise constant for the
+    /// linear interpolation, continuous for the cubic schemes, and zero for the steps, whose jumps at the
+    /// breakpoints are not differentiable. On a breakpoint, the derivative is that of the segment to the
+    /// right of it, and at the last breakpoint that of the segment to its left, which only makes a
+    /// difference for the linear interpolation. Outside the range of the breakpoints, it is the
+    /// derivative of the extrapolated values: zero for [`Extrapolation::Clamp`] and
+    /// [`Extrapolation::Constant`], NaN for [`Extrapolation::NaN`], the slope of the end segment for
+    /// [`Extrapolation::Linear`], the derivative at the wrapped index for [`Extrapolation::Periodic`], and
+    /// an error for [`Extrapolation::Error`].
+    /// ```
+    ///  use look_up_table::{Extrapolation, OneDLookUpTable};
+    ///  let mut lut = OneDLookUpTable::new([0.0, 1.0, 3.0], [0.0, 2.0, 3.0]).unwrap();
+    ///  // The slope of the segment to the right of the breakpoint.
+    ///  assert_eq!(lut.try_derivative(&1.0), Ok(0.5));
+    ///  assert_eq!(lut.try_derivative(&3.0), Ok(0.5));
+    ///  assert_eq!(lut.try_derivative(&5.0), Ok(0.0));
+    ///  lut.set_extrapolation(Extrapolation::Linear);
+    ///  assert_eq!(lut.try_derivative(&-1.0), Ok(2.0));
+    /// ```
+    pub fn try_derivative(&self, index: &T) -> Result<T, LookUpError> {
+        self.view().try_derivative(index)
+    }
+
+    /// Returns a [`DynOneDLookUpTable`] with the fewest breakpoints this finds, whose lookups are within
+    /// `tolerance` of the lookups of this table, along with the largest difference. The breakpoints are
+    /// chosen in the same way as [`crate::NDLookUpTable::<1>::compress`], and the number of them is only
+    /// known at runtime, so the compressed table is a dynamic one, with the same interpolation and
+    /// extrapolation as this table. As the error of dropping a breakpoint is measured along the straight
+    /// segment replacing it, only the tables with [`Interpolation::Linear`] can be compressed, and the
+    /// other schemes are reported as a [`CompressionError::NonLinearInterpolation`]: the curve of a spline
+    /// or a PCHIP between the kept breakpoints depends on the dropped ones, and a step doesn't pass through
+    /// its values between the breakpoints, so the error wouldn't be bounded by the one at the dropped
+    /// breakpoints.
+    /// ```
+    ///  use look_up_table::OneDLookUpTable;
+    ///  let lut = OneDLookUpTable::new([0.0, 1.0, 2.0, 3.0, 4.0], [0.0, 1.0, 2.0, 3.5, 5.0]).unwrap();
+    ///
+    ///  let compressed = lut.compress(0.2).unwrap();
+    ///  assert_eq!(compressed.table.breakpoints(), [0.0, 2.0, 4.0]);
+    ///  assert_eq!(compressed.error, 0.0);
+    /// ```
+    ///
+    /// If the `tolerance` is negative:
+    /// ```
+    ///  use look_up_table::{CompressionError::InvalidTable, ConstructionError::NegativeTolerance, OneDLookUpTable};
+    ///  let lut = OneDLookUpTable::new([0.0, 1.0, 2.0], [0.0, 1.0, 0.0]).unwrap();
+    ///  assert!(matches!(lut.compress(-0.1).err().unwrap(), InvalidTable(NegativeTolerance)));
+    /// ```
+    ///
+    /// If the interpolation is not linear:
+    /// ```
+    ///  use look_up_table::{CompressionError::NonLinearInterpolation, Interpolation, OneDLookUpTable};
+    ///  let lut = OneDLookUpTable::with_interpolation([0.0, 1.0, 2.0], [0.0, 1.0, 0.0], Interpolation::Pchip).unwrap();
+    ///  assert!(matches!(lut.compress(0.1).err().unwrap(), NonLinearInterpolation));
+    /// ```
+    pub fn compress(&self, tolerance: T) -> Result<Compressed<DynOneDLookUpTable<T>, T>, CompressionError> {
+        self.view().compress(tolerance)
+    }
+
+    fn view(&self) -> View<'_, T, C> {
+        View {
+            xs: &self.x,
+            ys: &self.y,
+            slopes: &self.slopes,
+            interpolation: &self.interpolation,
+            extrapolation: &self.extrapolation,
+            cache: &self.cache,
+        }
     }
 }
 
 /// This struct allows reference arrays/slices to be used as lookup functions, which can be defined
 /// at runtime or borrow the slices from other enclosing objects.
 #[derive(Debug)]
-pub struct OneDLookUpTableRef<'a, 'b> {
-    xs: &'a [f64],
-    ys: &'b [f64],
-    cache: RefCell<HashMap<Key, f64>>,
+pub struct OneDLookUpTableRef<'a, 'b, T = f64, C = LocalCache<Key, T>> {
+    xs: &'a [T],
+    ys: &'b [T],
+    slopes: Vec<T>,
+    interpolation: Interpolation<T>,
+    extrapolation: Extrapolation<T>,
+    cache: C,
 }
 
-impl<'a, 'b> OneDLookUpTableRef<'a, 'b> {
-    pub fn new(xs: &'a [f64], ys: &'b [f64]) -> Result<OneDLookUpTableRef<'a, 'b>, ConstructionError> {
-        is_object_constructible(xs, ys).map(|_| OneDLookUpTableRef {
+impl<'a, 'b, T: Float> OneDLookUpTableRef<'a, 'b, T> {
+    pub fn new(xs: &'a [T], ys: &'b [T]) -> Result<OneDLookUpTableRef<'a, 'b, T>, ConstructionError> {
+        Self::with_interpolation(xs, ys, Interpolation::Linear)
+    }
+
+    /// Constructs a `OneDLookUpTableRef` object, which uses the given `interpolation` scheme between
+    /// the breakpoints. Any coefficients needed by the scheme are computed once here.
+    pub fn with_interpolation(
+        xs: &'a [T],
+        ys: &'b [T],
+        interpolation: Interpolation<T>,
+    ) -> Result<OneDLookUpTableRef<'a, 'b, T>, ConstructionError> {
+        is_object_constructible(xs, ys)?;
+
+        Self::with_validated_breakpoints(xs, ys, interpolation)
+    }
+
+    /// Constructs a `OneDLookUpTableRef` object, which takes its breakpoints from the shared `axis`. The
+    /// breakpoints are not validated again, only the values `ys` and their count are. See [`Axis`] for an
+    /// example.
+    pub fn from_axis(
+        axis: &Axis<'a, T>,
+        ys: &'b [T],
+        interpolation: Interpolation<T>,
+    ) -> Result<OneDLookUpTableRef<'a, 'b, T>, ConstructionError> {
+        are_values_valid(axis.breakpoints().len(), ys)?;
+
+        Self::with_validated_breakpoints(axis.breakpoints(), ys, interpolation)
+    }
+
+    fn with_validated_breakpoints(
+        xs: &'a [T],
+        ys: &'b [T],
+        interpolation: Interpolation<T>,
+    ) -> Result<OneDLookUpTableRef<'a, 'b, T>, ConstructionError> {
+        is_interpolation_valid(&interpolation)?;
+
+        let slopes = if interpolation.needs_slopes() {
+            let mut slopes = zeroed(xs.len())?;
+            compute_slopes(&interpolation, xs, ys, &mut slopes, &mut zeroed(xs.len())?);
+            slopes
+        } else {
+            Vec::new()
+        };
+
+        Ok(OneDLookUpTableRef {
             xs,
             ys,
-            cache: RefCell::new(HashMap::new()),
+            slopes,
+            interpolation,
+            extrapolation: Extrapolation::Clamp,
+            cache: LocalCache::default(),
         })
     }
+}
 
-    pub fn get(&self, index: &f64) -> f64 {
+impl<'a, 'b, T: Float, C: Cache<Key, T>> OneDLookUpTableRef<'a, 'b, T, C> {
+    /// Returns the table with a different [`Cache`], which starts out empty.
+    pub fn with_cache<D: Cache<Key, T> + Default>(self) -> OneDLookUpTableRef<'a, 'b, T, D> {
+        OneDLookUpTableRef {
+            xs: self.xs,
+            ys: self.ys,
+            slopes: self.slopes,
+            interpolation: self.interpolation,
+            extrapolation: self.extrapolation,
+            cache: D::default(),
+        }
+    }
+
+    /// Returns the counts of the hits, misses and evictions of the cache of the table.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Sets the policy for the lookups outside the range of the breakpoints. The default policy is
+    /// [`Extrapolation::Clamp`].
+    pub fn set_extrapolation(&mut self, extrapolation: Extrapolation<T>) {
+        self.extrapolation = extrapolation;
+        self.cache.clear();
+    }
+
+    pub fn get(&self, index: &T) -> T {
+        self.try_get(index).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value for the given `index`, or an error if the index lies outside the
+    /// range of the breakpoints, and the extrapolation policy is [`Extrapolation::Error`].
+    pub fn try_get(&self, index: &T) -> Result<T, LookUpError> {
+        self.view().try_get(index)
+    }
+
+    /// Returns an interpolated value for the given `index`, along with where the index lies relative to
+    /// the breakpoints.
+    pub fn get_checked(&self, index: &T) -> Result<Checked<T, 1>, LookUpError> {
+        self.view().get_checked(index)
+    }
+
+    /// Returns an interpolated value for the index at the given `position`, reusing the search done on
+    /// the shared [`Axis`] of the table. The lookup bypasses the cache, since it is already cheap.
+    pub fn get_at(&self, position: &AxisPosition<T>) -> T {
+        self.try_get_at(position).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value for the index at the given `position` similar to
+    /// [`OneDLookUpTableRef::get_at`], or an error if the index lies outside the range of the breakpoints,
+    /// and the extrapolation policy is [`Extrapolation::Error`].
+    pub fn try_get_at(&self, position: &AxisPosition<T>) -> Result<T, LookUpError> {
+        self.view().try_get_at(position)
+    }
+
+    /// Returns an interpolated value at the position given by a prelookup on an [`Axis`] with the same
+    /// breakpoints, which skips the search of the breakpoints.
+    pub fn interpolate_with(&self, at: &IndexFraction<T>) -> T {
+        self.try_interpolate_with(at).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value at the position given by a prelookup, or an error if the position
+    /// lies outside the range of the breakpoints, and the extrapolation policy is
+    /// [`Extrapolation::Error`].
+    pub fn try_interpolate_with(&self, at: &IndexFraction<T>) -> Result<T, LookUpError> {
+        self.view().try_interpolate_with(at)
+    }
+
+    /// Returns the index at which the table takes the given `value`, or NaN if there is no unique index.
+    /// See [`OneDLookUpTable::inverse`].
+    pub fn inverse(&self, value: &T) -> T {
+        self.try_inverse(value).unwrap_or(T::nan())
+    }
+
+    /// Returns the index at which the table takes the given `value`, or an error if the values of the
+    /// table are not strictly monotonic, or don't enclose the `value`. See [`OneDLookUpTable::try_inverse`].
+    pub fn try_inverse(&self, value: &T) -> Result<T, LookUpError> {
+        self.view().try_inverse(value)
+    }
+
+    /// Returns all the indices within the range of the breakpoints at which the table takes the given
+    /// `value`, in increasing order. See [`OneDLookUpTable::inverse_all`].
+    pub fn inverse_all(&self, value: &T) -> impl Iterator<Item = T> + '_ {
+        self.view().inverse_all(value)
+    }
+
+    /// Returns the derivative of the table at the given `index`, with respect to the index, or NaN if the
+    /// index lies outside the range of the breakpoints, and the extrapolation policy is
+    /// [`Extrapolation::Error`]. See [`OneDLookUpTable::derivative`].
+    pub fn derivative(&self, index: &T) -> T {
+        self.try_derivative(index).unwrap_or(T::nan())
+    }
+
+    /// Returns the derivative of the table at the given `index`, with respect to the index, or an error if
+    /// the index lies outside the range of the breakpoints, and the extrapolation policy is
+    /// [`Extrapolation::Error`]. See [`OneDLookUpTable::try_derivative`].
+    pub fn try_derivative(&self, index: &T) -> Result<T, LookUpError> {
+        self.view().try_derivative(index)
+    }
+
+    /// Returns a [`DynOneDLookUpTable`] with the fewest breakpoints this finds, whose lookups are within
+    /// `tolerance` of the lookups of this table, along with the largest difference, or an error if the
+    /// interpolation is not linear. See [`OneDLookUpTable::compress`].
+    pub fn compress(&self, tolerance: T) -> Result<Compressed<DynOneDLookUpTable<T>, T>, CompressionError> {
+        self.view().compress(tolerance)
+    }
+
+    fn view(&self) -> View<'_, T, C> {
+        View {
+            xs: self.xs,
+            ys: self.ys,
+            slopes: &self.slopes,
+            interpolation: &self.interpolation,
+            extrapolation: &self.extrapolation,
+            cache: &self.cache,
+        }
+    }
+}
+
+/// Linear (or any other supported [`Interpolation`]) interpolation, which owns a copy of its breakpoints
+/// and values like a [`OneDLookUpTable`], but whose number of breakpoints is only known at runtime, eg.
+/// for the tables built by sampling a function adaptively.
+/// ```
+///  use look_up_table::DynOneDLookUpTable;
+///  let xs: Vec<f64> = (0..=10).map(|i| i as f64).collect();
+///  let ys: Vec<f64> = xs.iter().map(|x| x * x).collect();
+///  let lut = DynOneDLookUpTable::new(&xs, &ys).unwrap();
+///  assert!((lut.get(&2.5) - 6.5).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug)]
+pub struct DynOneDLookUpTable<T = f64, C = LocalCache<Key, T>> {
+    x: Vec<T>,
+    y: Vec<T>,
+    slopes: Vec<T>, // Slopes at the breakpoints, for the interpolation schemes which need them.
+    interpolation: Interpolation<T>,
+    extrapolation: Extrapolation<T>,
+    cache: C,
+}
+
+impl<T: Float> DynOneDLookUpTable<T> {
+    /// Constructs a `DynOneDLookUpTable` object, given the breakpoints `xs` and the values `ys`, which are
+    /// validated in the same way as [`OneDLookUpTable::new`]. In `no-std` builds, more than 1000
+    /// breakpoints are reported as a `MaxLengthError`.
+    pub fn new(xs: &[T], ys: &[T]) -> Result<DynOneDLookUpTable<T>, ConstructionError> {
+        Self::with_interpolation(xs, ys, Interpolation::Linear)
+    }
+
+    /// Constructs a `DynOneDLookUpTable` object, which uses the given `interpolation` scheme between the
+    /// breakpoints. Any coefficients needed by the scheme are computed once here.
+    pub fn with_interpolation(
+        xs: &[T],
+        ys: &[T],
+        interpolation: Interpolation<T>,
+    ) -> Result<DynOneDLookUpTable<T>, ConstructionError> {
+        is_object_constructible(xs, ys)?;
+        is_interpolation_valid(&interpolation)?;
+
+        let slopes = if interpolation.needs_slopes() {
+            let mut slopes = zeroed(xs.len())?;
+            compute_slopes(&interpolation, xs, ys, &mut slopes, &mut zeroed(xs.len())?);
+            slopes
+        } else {
+            Vec::new()
+        };
+
+        Ok(DynOneDLookUpTable {
+            x: to_vec(xs)?,
+            y: to_vec(ys)?,
+            slopes,
+            interpolation,
+            extrapolation: Extrapolation::Clamp,
+            cache: LocalCache::default(),
+        })
+    }
+}
+
+impl<T: Float, C: Cache<Key, T>> DynOneDLookUpTable<T, C> {
+    /// Returns the table with a different [`Cache`], which starts out empty.
+    pub fn with_cache<D: Cache<Key, T> + Default>(self) -> DynOneDLookUpTable<T, D> {
+        DynOneDLookUpTable {
+            x: self.x,
+            y: self.y,
+            slopes: self.slopes,
+            interpolation: self.interpolation,
+            extrapolation: self.extrapolation,
+            cache: D::default(),
+        }
+    }
+
+    /// Returns the counts of the hits, misses and evictions of the cache of the table.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Returns the breakpoints of the table, eg. to inspect a table built by sampling a function.
+    pub fn breakpoints(&self) -> &[T] {
+        &self.x
+    }
+
+    /// Sets the policy for the lookups outside the range of the breakpoints. The default policy is
+    /// [`Extrapolation::Clamp`].
+    pub fn set_extrapolation(&mut self, extrapolation: Extrapolation<T>) {
+        self.extrapolation = extrapolation;
+        self.cache.clear();
+    }
+
+    /// Returns an interpolated value for the given `index`, same as [`OneDLookUpTable::get`].
+    pub fn get(&self, index: &T) -> T {
+        self.try_get(index).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value for the given `index`, or an error if the index lies outside the
+    /// range of the breakpoints, and the extrapolation policy is [`Extrapolation::Error`].
+    pub fn try_get(&self, index: &T) -> Result<T, LookUpError> {
+        self.view().try_get(index)
+    }
+
+    /// Returns an interpolated value for the given `index`, along with where the index lies relative to
+    /// the breakpoints.
+    pub fn get_checked(&self, index: &T) -> Result<Checked<T, 1>, LookUpError> {
+        self.view().get_checked(index)
+    }
+
+    /// Returns the index at which the table takes the given `value`, or NaN if there is no unique index.
+    /// See [`OneDLookUpTable::inverse`].
+    pub fn inverse(&self, value: &T) -> T {
+        self.try_inverse(value).unwrap_or(T::nan())
+    }
+
+    /// Returns the index at which the table takes the given `value`, or an error if the values of the
+    /// table are not strictly monotonic, or don't enclose the `value`. See [`OneDLookUpTable::try_inverse`].
+    pub fn try_inverse(&self, value: &T) -> Result<T, LookUpError> {
+        self.view().try_inverse(value)
+    }
+
+    /// Returns all the indices within the range of the breakpoints at which the table takes the given
+    /// `value`, in increasing order. See [`OneDLookUpTable::inverse_all`].
+    pub fn inverse_all(&self, value: &T) -> impl Iterator<Item = T> + '_ {
+        self.view().inverse_all(value)
+    }
+
+    /// Returns the derivative of the table at the given `index`, with respect to the index, or NaN if the
+    /// index lies outside the range of the breakpoints, and the extrapolation policy is
+    /// [`Extrapolation::Error`]. See [`OneDLookUpTable::derivative`].
+    pub fn derivative(&self, index: &T) -> T {
+        self.try_derivative(index).unwrap_or(T::nan())
+    }
+
+    /// Returns the derivative of the table at the given `index`, with respect to the index, or an error if
+    /// the index lies outside the range of the breakpoints, and the extrapolation policy is
+    /// [`Extrapolation::Error`]. See [`OneDLookUpTable::try_derivative`].
+    pub fn try_derivative(&self, index: &T) -> Result<T, LookUpError> {
+        self.view().try_derivative(index)
+    }
+
+    /// Returns a [`DynOneDLookUpTable`] with the fewest breakpoints this finds, whose lookups are within
+    /// `tolerance` of the lookups of this table, along with the largest difference, or an error if the
+    /// interpolation is not linear. See [`OneDLookUpTable::compress`].
+    pub fn compress(&self, tolerance: T) -> Result<Compressed<DynOneDLookUpTable<T>, T>, CompressionError> {
+        self.view().compress(tolerance)
+    }
+
+    fn view(&self) -> View<'_, T, C> {
+        View {
+            xs: &self.x,
+            ys: &self.y,
+            slopes: &self.slopes,
+            interpolation: &self.interpolation,
+            extrapolation: &self.extrapolation,
+            cache: &self.cache,
+        }
+    }
+}
+
+/// The fields of a table which its lookups need, borrowed from an owned, a borrowed or a dynamic table, so
+/// that they share the bodies of their methods.
+struct View<'t, T, C> {
+    xs: &'t [T],
+    ys: &'t [T],
+    slopes: &'t [T],
+    interpolation: &'t Interpolation<T>,
+    extrapolation: &'t Extrapolation<T>,
+    cache: &'t C,
+}
+
+impl<'t, T: Float, C: Cache<Key, T>> View<'t, T, C> {
+    fn try_get(&self, index: &T) -> Result<T, LookUpError> {
         // There could be a possibility that the values which are very close in real number line to
         // have different bit patterns, so this code would do a full interpolation for nearly identical
         // value lookups.
         let ind = index.integer_decode();
-        if self.cache.borrow().contains_key(&ind) {
-            return *self.cache.borrow().get(&ind).unwrap();
+        if let Some(y) = self.cache.get(&ind) {
+            return Ok(y);
         }
 
-        let y = interpolate(index, self.xs, self.ys);
+        let y = interpolate(
+            index,
+            self.xs,
+            self.ys,
+            self.slopes,
+            self.interpolation,
+            self.extrapolation,
+            None,
+        )?;
+
+        self.cache.insert(ind, y);
+
+        Ok(y)
+    }
 
-        self.cache.borrow_mut().insert(ind, y);
+    fn get_checked(&self, index: &T) -> Result<Checked<T, 1>, LookUpError> {
+        Ok(Checked {
+            value: self.try_get(index)?,
+            range: [AxisRange::of(*index, self.xs)],
+        })
+    }
+
+    fn try_get_at(&self, position: &AxisPosition<T>) -> Result<T, LookUpError> {
+        interpolate(
+            &position.value(),
+            self.xs,
+            self.ys,
+            self.slopes,
+            self.interpolation,
+            self.extrapolation,
+            Some(position),
+        )
+    }
+
+    fn try_interpolate_with(&self, at: &IndexFraction<T>) -> Result<T, LookUpError> {
+        interpolate_with(at, self.xs, self.ys, self.slopes, self.interpolation, self.extrapolation)
+    }
 
-        *self.cache.borrow().get(&ind).unwrap()
+    fn try_inverse(&self, value: &T) -> Result<T, LookUpError> {
+        invert(*value, &self.curve())
+    }
+
+    fn inverse_all(self, value: &T) -> impl Iterator<Item = T> + 't {
+        solutions(*value, self.curve())
+    }
+
+    fn try_derivative(&self, index: &T) -> Result<T, LookUpError> {
+        differentiate(index, self.xs, self.ys, self.slopes, self.interpolation, self.extrapolation)
+    }
+
+    /// Compresses the curve of a linearly interpolated table into a dynamic table, with the same
+    /// extrapolation policy, as described in [`OneDLookUpTable::compress`].
+    fn compress(&self, tolerance: T) -> Result<Compressed<DynOneDLookUpTable<T>, T>, CompressionError> {
+        if *self.interpolation != Interpolation::Linear {
+            return Err(CompressionError::NonLinearInterpolation);
+        }
+
+        let extrapolation = *self.extrapolation;
+        let compressed = compress_curve(self.xs, self.ys, extrapolation, tolerance, |x, y| {
+            let mut table = DynOneDLookUpTable::new(x, y)?;
+            table.set_extrapolation(extrapolation);
+            Ok(table)
+        })?;
+
+        Ok(compressed)
+    }
+
+    fn curve(&self) -> Table<'t, T> {
+        Table {
+            xs: self.xs,
+            ys: self.ys,
+            slopes: self.slopes,
+            interpolation: self.interpolation,
+        }
     }
 }
@@ -0,0 +1,85 @@
+use crate::oned_lut::interpolation::Key;
+use crate::serialization::{invalid, Array, Values};
+use crate::{Cache, DynOneDLookUpTable, Extrapolation, Interpolation, OneDLookUpTable};
+use num::Float;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// The serialized form of a [`OneDLookUpTable`], which is validated by the constructor before it becomes a
+/// table. The interpolation and the extrapolation can be left out, to use the defaults of the table.
+#[derive(serde::Deserialize)]
+#[serde(rename = "OneDLookUpTable", bound(deserialize = "T: Deserialize<'de>"))]
+struct Data<T, const N: usize> {
+    x: Array<T, N>,
+    y: Array<T, N>,
+    #[serde(default)]
+    interpolation: Interpolation<T>,
+    #[serde(default)]
+    extrapolation: Extrapolation<T>,
+}
+
+/// The serialized form of a [`DynOneDLookUpTable`], which is the same as that of a [`OneDLookUpTable`],
+/// except that the breakpoints can be of any number.
+#[derive(serde::Deserialize)]
+#[serde(rename = "DynOneDLookUpTable", bound(deserialize = "T: Deserialize<'de>"))]
+struct DynData<T> {
+    x: Values<T>,
+    y: Values<T>,
+    #[serde(default)]
+    interpolation: Interpolation<T>,
+    #[serde(default)]
+    extrapolation: Extrapolation<T>,
+}
+
+impl<const N: usize, T: Float + Serialize, C> Serialize for OneDLookUpTable<N, T, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut table = serializer.serialize_struct("OneDLookUpTable", 4)?;
+        table.serialize_field("x", &self.x[..])?;
+        table.serialize_field("y", &self.y[..])?;
+        table.serialize_field("interpolation", &self.interpolation)?;
+        table.serialize_field("extrapolation", &self.extrapolation)?;
+        table.end()
+    }
+}
+
+impl<'de, const N: usize, T, C> Deserialize<'de> for OneDLookUpTable<N, T, C>
+where
+    T: Float + Deserialize<'de>,
+    C: Cache<Key, T> + Default,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = Data::<T, N>::deserialize(deserializer)?;
+
+        let mut lut = OneDLookUpTable::with_interpolation(data.x.0, data.y.0, data.interpolation).map_err(invalid)?;
+        lut.set_extrapolation(data.extrapolation);
+
+        Ok(lut.with_cache())
+    }
+}
+
+impl<T: Float + Serialize, C> Serialize for DynOneDLookUpTable<T, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut table = serializer.serialize_struct("DynOneDLookUpTable", 4)?;
+        table.serialize_field("x", &self.x[..])?;
+        table.serialize_field("y", &self.y[..])?;
+        table.serialize_field("interpolation", &self.interpolation)?;
+        table.serialize_field("extrapolation", &self.extrapolation)?;
+        table.end()
+    }
+}
+
+impl<'de, T, C> Deserialize<'de> for DynOneDLookUpTable<T, C>
+where
+    T: Float + Deserialize<'de>,
+    C: Cache<Key, T> + Default,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = DynData::<T>::deserialize(deserializer)?;
+
+        let mut lut =
+            DynOneDLookUpTable::with_interpolation(&data.x.0, &data.y.0, data.interpolation).map_err(invalid)?;
+        lut.set_extrapolation(data.extrapolation);
+
+        Ok(lut.with_cache())
+    }
+}
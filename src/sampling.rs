@@ -0,0 +1,360 @@
+//! Sampling
+//! The tables usually replace a function which is too expensive to evaluate at runtime. Rather than
+//! tabulating the function by hand, a table can be built by sampling the function over a domain:
+//! * Uniformly, at the fixed number of breakpoints of a [`OneDLookUpTable`] or a [`TwoDLookUpTable`].
+//! * Adaptively, into a [`DynOneDLookUpTable`] or a [`DynTwoDLookUpTable`], by splitting the intervals
+//!   between the breakpoints until the linear interpolation is within a tolerance of the function.
+//!
+//! The number of breakpoints found by the adaptive sampling is only known at runtime, while the owned
+//! [`OneDLookUpTable`] and [`TwoDLookUpTable`] fix it at compile time. So the adaptive constructors return
+//! the dynamic tables, which own breakpoints of any length, and have the same `get` as the owned tables.
+//! ```
+//!  use look_up_table::DynOneDLookUpTable;
+//!  let lut = DynOneDLookUpTable::from_fn_adaptive(f64::sqrt, 0.0..=4.0, 1e-3).unwrap();
+//!  for x in [0.01, 0.3, 1.7, 3.9] {
+//!      assert!((lut.get(&x) - x.sqrt()).abs() < 2e-3);
+//!  }
+//! ```
+
+use crate::{
+    constant, push, zeroed, ConstructionError, DynOneDLookUpTable, DynTwoDLookUpTable, OneDLookUpTable,
+    TwoDLookUpTable, Vec, EPSILON,
+};
+use core::ops::RangeInclusive;
+use num::Float;
+
+const INITIAL_INTERVALS: usize = 4;
+const MAX_SPLITS: u8 = 10;
+
+impl<const N: usize, T: Float> OneDLookUpTable<N, T> {
+    /// Constructs a linearly interpolated `OneDLookUpTable` object by sampling the function `f` at `N`
+    /// equally spaced breakpoints, from the start to the end of the `domain`. The samples are validated in
+    /// the same way as [`OneDLookUpTable::new`].
+    /// ```
+    ///  use look_up_table::OneDLookUpTable;
+    ///  let lut = OneDLookUpTable::<5>::from_fn(|x| x * x, 0.0..=2.0).unwrap();
+    ///  assert!((lut.get(&1.0) - 1.0).abs() < f64::EPSILON);
+    ///  assert!((lut.get(&1.25) - 1.625).abs() < f64::EPSILON);
+    /// ```
+    pub fn from_fn(f: impl Fn(T) -> T, domain: RangeInclusive<T>) -> Result<OneDLookUpTable<N, T>, ConstructionError> {
+        let x: [T; N] = core::array::from_fn(|i| uniform(&domain, i, N));
+
+        Self::new(x, x.map(f))
+    }
+}
+
+impl<const M: usize, const N: usize, T: Float> TwoDLookUpTable<M, N, T> {
+    /// Constructs a `TwoDLookUpTable` object by sampling the function `f` on a grid of `M` equally spaced
+    /// breakpoints over the domain `x`, and `N` equally spaced breakpoints over the domain `y`. The samples
+    /// are validated in the same way as [`TwoDLookUpTable::new`].
+    /// ```
+    ///  use look_up_table::TwoDLookUpTable;
+    ///  let lut = TwoDLookUpTable::<3, 2>::from_fn(|x, y| x * y, 0.0..=2.0, 1.0..=2.0).unwrap();
+    ///  assert!((lut.get(&1.5, &1.5) - 2.25).abs() < f64::EPSILON);
+    /// ```
+    pub fn from_fn(
+        f: impl Fn(T, T) -> T,
+        x: RangeInclusive<T>,
+        y: RangeInclusive<T>,
+    ) -> Result<TwoDLookUpTable<M, N, T>, ConstructionError> {
+        let xs: [T; M] = core::array::from_fn(|i| uniform(&x, i, M));
+        let ys: [T; N] = core::array::from_fn(|j| uniform(&y, j, N));
+
+        Self::new(xs, ys, xs.map(|x| ys.map(|y| f(x, y))))
+    }
+}
+
+impl<T: Float> DynOneDLookUpTable<T> {
+    /// Constructs a linearly interpolated `DynOneDLookUpTable` object by sampling the function `f` over the `domain`,
+    /// with the breakpoints placed so that the linear interpolation is within `tolerance` of the function.
+    /// The sampling starts from 4 equally spaced intervals, and compares the function with the interpolated
+    /// value in the middle of each of the intervals. The intervals where the error exceeds the tolerance are
+    /// split in half, up to 10 times, so the breakpoints are never closer than 1/4096th of the domain. As the
+    /// error is only measured at these points, a feature of the function narrower than the intervals around
+    /// it can be missed. In `no-std` builds, a tolerance which needs more than 1000 breakpoints is reported
+    /// as a `MaxLengthError`.
+    /// ```
+    ///  use look_up_table::DynOneDLookUpTable;
+    ///  // A straight line needs no more than the initial breakpoints.
+    ///  let lut = DynOneDLookUpTable::from_fn_adaptive(|x: f64| 2.0 * x, 0.0..=1.0, 1e-6).unwrap();
+    ///  assert!((lut.get(&0.1) - 0.2).abs() < 1e-12);
+    ///  assert_eq!(lut.breakpoints(), [0.0, 0.25, 0.5, 0.75, 1.0]);
+    /// ```
+    ///
+    /// If the function is not finite anywhere it is sampled:
+    /// ```
+    ///  use look_up_table::{DynOneDLookUpTable, ConstructionError::ContainingNansOrInfinities};
+    ///  let lut = DynOneDLookUpTable::from_fn_adaptive(|x: f64| x.ln(), -1.0..=1.0, 1e-3);
+    ///  assert!(matches!(lut.err().unwrap(), ContainingNansOrInfinities));
+    /// ```
+    ///
+    /// If the `tolerance` is negative:
+    /// ```
+    ///  use look_up_table::{DynOneDLookUpTable, ConstructionError::NegativeTolerance};
+    ///  let lut = DynOneDLookUpTable::from_fn_adaptive(f64::sqrt, 0.0..=4.0, -1e-3);
+    ///  assert!(matches!(lut.err().unwrap(), NegativeTolerance));
+    /// ```
+    pub fn from_fn_adaptive(
+        f: impl Fn(T) -> T,
+        domain: RangeInclusive<T>,
+        tolerance: T,
+    ) -> Result<DynOneDLookUpTable<T>, ConstructionError> {
+        is_tolerance_valid(tolerance)?;
+
+        let (mut xs, mut ys) = (Vec::new(), Vec::new());
+        push(&mut xs, *domain.start())?;
+        push(&mut ys, sample(f(*domain.start()))?)?;
+
+        // The intervals still to be checked, with the leftmost one last. The left end of an interval is
+        // the last breakpoint, so only its right end, and the number of times it was split are kept.
+        let mut intervals = Vec::new();
+        for i in (1..=INITIAL_INTERVALS).rev() {
+            let x = uniform(&domain, i, INITIAL_INTERVALS + 1);
+            push(&mut intervals, (x, sample(f(x))?, 0))?;
+        }
+
+        while let Some((x1, y1, splits)) = intervals.pop() {
+            let (x0, y0) = (xs[xs.len() - 1], ys[ys.len() - 1]);
+            if is_splittable(x0, x1, splits) {
+                let (x, y) = (mid(x0, x1), sample(f(mid(x0, x1)))?);
+                if (y - mid(y0, y1)).abs() > tolerance {
+                    push(&mut intervals, (x1, y1, splits + 1))?;
+                    push(&mut intervals, (x, y, splits + 1))?;
+                    continue;
+                }
+            }
+
+            push(&mut xs, x1)?;
+            push(&mut ys, y1)?;
+        }
+
+        Self::new(&xs, &ys)
+    }
+}
+
+impl<T: Float> DynTwoDLookUpTable<T> {
+    /// Constructs a bilinearly interpolated `DynTwoDLookUpTable` object by sampling the function `f` over
+    /// the domains `x` and `y`, with the breakpoints placed so that the bilinear interpolation is within
+    /// `tolerance` of the function. The intervals of each of the axes are split in the same way as
+    /// [`DynOneDLookUpTable::from_fn_adaptive`], with the error also compared at the centre of each of the
+    /// cells. As the grid is rectilinear, a breakpoint added for one of the cells spans the whole table,
+    /// and in `no-std` builds, a tolerance which needs more than 1000 values is reported as a
+    /// `MaxLengthError`.
+    /// ```
+    ///  use look_up_table::DynTwoDLookUpTable;
+    ///  let f = |x: f64, y: f64| (x * y).sin();
+    ///  let lut = DynTwoDLookUpTable::from_fn_adaptive(f, 0.0..=2.0, 0.0..=2.0, 1e-2).unwrap();
+    ///  for (x, y) in [(0.3, 1.9), (1.1, 1.1), (1.9, 0.2)] {
+    ///      assert!((lut.get(&x, &y) - f(x, y)).abs() < 2e-2);
+    ///  }
+    /// ```
+    pub fn from_fn_adaptive(
+        f: impl Fn(T, T) -> T,
+        x: RangeInclusive<T>,
+        y: RangeInclusive<T>,
+        tolerance: T,
+    ) -> Result<DynTwoDLookUpTable<T>, ConstructionError> {
+        is_tolerance_valid(tolerance)?;
+
+        let mut grid = Grid::new(&f, &x, &y)?;
+        while grid.refine(&f, tolerance)? {}
+
+        let mut rows = Vec::new();
+        for row in grid.values.chunks(grid.ys.len()) {
+            push(&mut rows, row)?;
+        }
+
+        Self::new(&grid.xs, &grid.ys, &rows)
+    }
+}
+
+/// A rectilinear grid of the samples of a function, with the values in row major order, and the number of
+/// times each of the intervals between the breakpoints was split.
+struct Grid<T> {
+    xs: Vec<T>,
+    ys: Vec<T>,
+    values: Vec<T>,
+    splits: [Vec<u8>; 2],
+}
+
+/// A breakpoint of a refined grid, which is either one of the breakpoints of the grid, or the middle of one
+/// of its intervals.
+#[derive(Clone, Copy)]
+enum Line {
+    Breakpoint(usize),
+    Middle(usize),
+}
+
+impl<T: Float> Grid<T> {
+    fn new(f: &impl Fn(T, T) -> T, x: &RangeInclusive<T>, y: &RangeInclusive<T>) -> Result<Grid<T>, ConstructionError> {
+        let mut grid = Grid {
+            xs: Vec::new(),
+            ys: Vec::new(),
+            values: Vec::new(),
+            splits: [Vec::new(), Vec::new()],
+        };
+
+        for i in 0..=INITIAL_INTERVALS {
+            push(&mut grid.xs, uniform(x, i, INITIAL_INTERVALS + 1))?;
+            push(&mut grid.ys, uniform(y, i, INITIAL_INTERVALS + 1))?;
+        }
+        for splits in &mut grid.splits {
+            (0..INITIAL_INTERVALS).try_for_each(|_| push(splits, 0))?;
+        }
+        for &x in &grid.xs {
+            for &y in &grid.ys {
+                push(&mut grid.values, sample(f(x, y))?)?;
+            }
+        }
+
+        Ok(grid)
+    }
+
+    /// Splits the intervals where the error of the interpolation exceeds the tolerance, and returns whether
+    /// any of them were split.
+    fn refine(&mut self, f: &impl Fn(T, T) -> T, tolerance: T) -> Result<bool, ConstructionError> {
+        let (nx, ny) = (self.xs.len(), self.ys.len());
+        let value = |i: usize, j: usize| self.values[i * ny + j];
+        let is_x_splittable = |i: usize| is_splittable(self.xs[i], self.xs[i + 1], self.splits[0][i]);
+        let is_y_splittable = |j: usize| is_splittable(self.ys[j], self.ys[j + 1], self.splits[1][j]);
+
+        // The function in the middle of the x intervals at each of the y breakpoints, in the middle of the
+        // y intervals at each of the x breakpoints, and at the centre of each of the cells.
+        let mut along_x = zeroed((nx - 1) * ny)?;
+        let mut along_y = zeroed(nx * (ny - 1))?;
+        let mut centre = zeroed((nx - 1) * (ny - 1))?;
+        let (mut split_x, mut split_y) = (Vec::new(), Vec::new());
+
+        for i in 0..nx - 1 {
+            let mut split = false;
+            if is_x_splittable(i) {
+                for j in 0..ny {
+                    along_x[i * ny + j] = sample(f(mid(self.xs[i], self.xs[i + 1]), self.ys[j]))?;
+                    split |= (along_x[i * ny + j] - mid(value(i, j), value(i + 1, j))).abs() > tolerance;
+                }
+            }
+            push(&mut split_x, split)?;
+        }
+
+        for j in 0..ny - 1 {
+            let mut split = false;
+            if is_y_splittable(j) {
+                for i in 0..nx {
+                    along_y[i * (ny - 1) + j] = sample(f(self.xs[i], mid(self.ys[j], self.ys[j + 1])))?;
+                    split |= (along_y[i * (ny - 1) + j] - mid(value(i, j), value(i, j + 1))).abs() > tolerance;
+                }
+            }
+            push(&mut split_y, split)?;
+        }
+
+        for i in 0..nx - 1 {
+            for j in 0..ny - 1 {
+                if !is_x_splittable(i) && !is_y_splittable(j) {
+                    continue;
+                }
+
+                let (x, y) = (mid(self.xs[i], self.xs[i + 1]), mid(self.ys[j], self.ys[j + 1]));
+                centre[i * (ny - 1) + j] = sample(f(x, y))?;
+                let interpolated = mid(mid(value(i, j), value(i + 1, j)), mid(value(i, j + 1), value(i + 1, j + 1)));
+                if (centre[i * (ny - 1) + j] - interpolated).abs() > tolerance && !split_x[i] && !split_y[j] {
+                    split_x[i] = is_x_splittable(i);
+                    split_y[j] = is_y_splittable(j);
+                }
+            }
+        }
+
+        if !split_x.contains(&true) && !split_y.contains(&true) {
+            return Ok(false);
+        }
+
+        let (lines_x, xs, splits_x) = split(&self.xs, &self.splits[0], &split_x)?;
+        let (lines_y, ys, splits_y) = split(&self.ys, &self.splits[1], &split_y)?;
+        let mut values = Vec::new();
+        for &lx in &lines_x {
+            for &ly in &lines_y {
+                let v = match (lx, ly) {
+                    (Line::Breakpoint(i), Line::Breakpoint(j)) => value(i, j),
+                    (Line::Middle(i), Line::Breakpoint(j)) => along_x[i * ny + j],
+                    (Line::Breakpoint(i), Line::Middle(j)) => along_y[i * (ny - 1) + j],
+                    (Line::Middle(i), Line::Middle(j)) => centre[i * (ny - 1) + j],
+                };
+                push(&mut values, v)?;
+            }
+        }
+
+        *self = Grid {
+            xs,
+            ys,
+            values,
+            splits: [splits_x, splits_y],
+        };
+
+        Ok(true)
+    }
+}
+
+/// Returns the breakpoints of an axis with the marked intervals split in half, along with where each of
+/// them comes from, and the number of times each of the new intervals was split.
+#[allow(clippy::type_complexity)]
+fn split<T: Float>(
+    vs: &[T],
+    splits: &[u8],
+    marked: &[bool],
+) -> Result<(Vec<Line>, Vec<T>, Vec<u8>), ConstructionError> {
+    let (mut lines, mut breakpoints, mut counts) = (Vec::new(), Vec::new(), Vec::new());
+    for k in 0..vs.len() {
+        push(&mut lines, Line::Breakpoint(k))?;
+        push(&mut breakpoints, vs[k])?;
+        if k == vs.len() - 1 {
+            break;
+        }
+
+        if marked[k] {
+            push(&mut lines, Line::Middle(k))?;
+            push(&mut breakpoints, mid(vs[k], vs[k + 1]))?;
+            push(&mut counts, splits[k] + 1)?;
+            push(&mut counts, splits[k] + 1)?;
+        } else {
+            push(&mut counts, splits[k])?;
+        }
+    }
+
+    Ok((lines, breakpoints, counts))
+}
+
+/// Returns the `i`th of `n` equally spaced breakpoints from the start to the end of the domain.
+fn uniform<T: Float>(domain: &RangeInclusive<T>, i: usize, n: usize) -> T {
+    let (start, end) = (*domain.start(), *domain.end());
+    if i + 1 == n {
+        end
+    } else {
+        start + (end - start) * T::from(i).unwrap() / T::from(n - 1).unwrap()
+    }
+}
+
+/// Returns whether the interval between the breakpoints can be split once more.
+fn is_splittable<T: Float>(v0: T, v1: T, splits: u8) -> bool {
+    splits < MAX_SPLITS && mid(v0, v1) - v0 > constant(EPSILON)
+}
+
+fn mid<T: Float>(v0: T, v1: T) -> T {
+    (v0 + v1) / constant(2.0)
+}
+
+/// Checks that the tolerance is finite, and not negative, which no interpolation could be within.
+pub(crate) fn is_tolerance_valid<T: Float>(tolerance: T) -> Result<(), ConstructionError> {
+    if tolerance < T::zero() {
+        Err(ConstructionError::NegativeTolerance)
+    } else {
+        sample(tolerance).map(|_| ())
+    }
+}
+
+/// Checks a sample of the function, so that a non finite value between the breakpoints isn't overlooked.
+fn sample<T: Float>(value: T) -> Result<T, ConstructionError> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(ConstructionError::ContainingNansOrInfinities)
+    }
+}
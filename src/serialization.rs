@@ -0,0 +1,94 @@
+//! Serialization
+//! With the `serde` feature, the owned and the dynamic tables can be serialized and deserialized, eg. to keep the
+//! calibrations in version controlled files. A table is serialized as its breakpoints, values and
+//! configuration; the cache is not serialized, and a deserialized table starts with an empty cache.
+//! Deserialization goes through the constructors of the tables, so a file with invalid breakpoints or
+//! values is rejected with the message of the corresponding [`ConstructionError`].
+//! ```
+//!  use look_up_table::OneDLookUpTable;
+//!  let lut: OneDLookUpTable<3> =
+//!      serde_json::from_str(r#"{ "x": [1.0, 2.0, 3.0], "y": [2.0, 4.0, 8.0], "extrapolation": "Linear" }"#).unwrap();
+//!  assert!((lut.get(&4.0) - 12.0).abs() < f64::EPSILON);
+//!
+//!  let err = serde_json::from_str::<OneDLookUpTable<3>>(r#"{ "x": [1.0, 3.0, 2.0], "y": [2.0, 4.0, 8.0] }"#);
+//!  assert!(err.unwrap_err().to_string().contains("strictly increasing order"));
+//! ```
+//!
+//! The sequences are deserialized without allocating, so that the tables can also be deserialized in
+//! `no-std` builds.
+
+use crate::{ConstructionError, Vec};
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+
+/// A sequence of exactly `N` values, deserialized into an array.
+pub(crate) struct Array<T, const N: usize>(pub(crate) [T; N]);
+
+impl<'de, T: Deserialize<'de>, const N: usize> Deserialize<'de> for Array<T, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for ArrayVisitor<T, N> {
+            type Value = Array<T, N>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a sequence of {N} values")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut values: [Option<T>; N] = core::array::from_fn(|_| None);
+                for (i, value) in values.iter_mut().enumerate() {
+                    *value = Some(seq.next_element()?.ok_or_else(|| Error::invalid_length(i, &self))?);
+                }
+
+                if seq.next_element::<T>()?.is_some() {
+                    return Err(Error::invalid_length(N + 1, &self));
+                }
+
+                Ok(Array(values.map(|v| v.unwrap())))
+            }
+        }
+
+        deserializer.deserialize_seq(ArrayVisitor(PhantomData))
+    }
+}
+
+/// A sequence of any number of values, deserialized into a buffer, which is bounded in no-std builds.
+pub(crate) struct Values<T>(pub(crate) Vec<T>);
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Values<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ValuesVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for ValuesVisitor<T> {
+            type Value = Values<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a sequence of values")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut values = Vec::new();
+                while let Some(value) = seq.next_element()? {
+                    cfg_if::cfg_if! {
+                        if #[cfg(feature="no-std")] {
+                            values.push(value).map_err(|_| invalid(ConstructionError::MaxLengthError))?;
+                        } else {
+                            values.push(value);
+                        }
+                    }
+                }
+
+                Ok(Values(values))
+            }
+        }
+
+        deserializer.deserialize_seq(ValuesVisitor(PhantomData))
+    }
+}
+
+/// Reports a table which can't be constructed from the deserialized values.
+pub(crate) fn invalid<E: Error>(error: ConstructionError) -> E {
+    E::custom(error)
+}
@@ -0,0 +1,89 @@
+use crate::csv::{write_surface, CsvError, Surface};
+use crate::twod_lut::Key;
+use crate::{push, zeroed, Cache, DynTwoDLookUpTable, TwoDLookUpTable, Vec};
+use core::fmt::{Display, Result as FmtResult, Write};
+use num::Float;
+
+impl<const M: usize, const N: usize, T: Float> TwoDLookUpTable<M, N, T> {
+    /// Constructs a `TwoDLookUpTable` object from comma separated values, with a header line holding the
+    /// `M` x breakpoints from the second column on, followed by a line for each of the `N` y breakpoints,
+    /// which has the breakpoint in the first column and the values at each of the x breakpoints in the
+    /// following columns. So the lines of the file are the columns of the `surface`. See
+    /// [`crate::CsvError`] for the errors.
+    /// ```
+    ///  use look_up_table::{CsvError, TwoDLookUpTable};
+    ///  let csv = "\
+    ///  load\\speed, 1000, 2000, 3000
+    ///  0.0,          10,   20,   30
+    ///  1.0,          90,  160,  150
+    ///  ";
+    ///  let lut = TwoDLookUpTable::<3, 2>::from_csv(csv).unwrap();
+    ///  assert!((lut.get(&1500.0, &0.5) - 70.0).abs() < f64::EPSILON);
+    ///
+    ///  let err = TwoDLookUpTable::<3, 2>::from_csv("x, 1, 2, 3\n0, 1, 2\n").unwrap_err();
+    ///  assert!(matches!(err, CsvError::ColumnCountMismatch { line: 2, expected: 4, actual: 3 }));
+    /// ```
+    pub fn from_csv(csv: &str) -> Result<TwoDLookUpTable<M, N, T>, CsvError> {
+        let surface = Surface::read(csv)?;
+        surface.expect(M, N)?;
+
+        let (mut xs, mut ys) = ([T::zero(); M], [T::zero(); N]);
+        xs.copy_from_slice(&surface.x);
+        ys.copy_from_slice(&surface.y);
+        let values = core::array::from_fn(|i| core::array::from_fn(|j| surface.value(i, j)));
+
+        Self::new(xs, ys, values).map_err(|e| surface.locate(e))
+    }
+}
+
+impl<const M: usize, const N: usize, T: Float + Display, C: Cache<Key, T>> TwoDLookUpTable<M, N, T, C> {
+    /// Writes the breakpoints and the surface of the table as comma separated values, in the layout read
+    /// by [`TwoDLookUpTable::from_csv`].
+    /// ```
+    ///  use look_up_table::TwoDLookUpTable;
+    ///  let lut = TwoDLookUpTable::new([1.0, 2.0], [0.0, 0.5], [[1.0, 2.0], [3.0, 4.0]]).unwrap();
+    ///  let mut csv = String::new();
+    ///  lut.to_csv(&mut csv).unwrap();
+    ///  assert_eq!(csv, ",1,2\n0,1,3\n0.5,2,4\n");
+    /// ```
+    pub fn to_csv<W: Write>(&self, out: &mut W) -> FmtResult {
+        write_surface(out, &self.x, &self.y, |i, j| self.surface[i][j])
+    }
+}
+
+impl<T: Float> DynTwoDLookUpTable<T> {
+    /// Constructs a `DynTwoDLookUpTable` object from comma separated values, in the same layout as
+    /// [`TwoDLookUpTable::from_csv`], with any number of x breakpoints in the header line, and any number of
+    /// lines for the y breakpoints.
+    /// ```
+    ///  use look_up_table::DynTwoDLookUpTable;
+    ///  let lut = DynTwoDLookUpTable::<f64>::from_csv("load\\speed, 1000, 2000, 3000\n0.0, 10, 20, 30\n1.0, 90, 160, 150\n").unwrap();
+    ///  assert_eq!(lut.axes(), [&[1000.0, 2000.0, 3000.0][..], &[0.0, 1.0][..]]);
+    ///  assert!((lut.get(&1500.0, &0.5) - 70.0).abs() < f64::EPSILON);
+    /// ```
+    pub fn from_csv(csv: &str) -> Result<DynTwoDLookUpTable<T>, CsvError> {
+        let surface = Surface::read(csv)?;
+        let (nx, ny) = (surface.x.len(), surface.y.len());
+
+        // The lines of the file are the columns of the surface, which has a row for each x breakpoint.
+        let mut values = zeroed(nx * ny).map_err(|e| surface.locate(e))?;
+        for i in 0..nx {
+            (0..ny).for_each(|j| values[i * ny + j] = surface.value(i, j));
+        }
+        let mut rows = Vec::new();
+        for i in 0..nx {
+            push(&mut rows, &values[i * ny..(i + 1) * ny]).map_err(|e| surface.locate(e))?;
+        }
+
+        Self::new(&surface.x, &surface.y, &rows).map_err(|e| surface.locate(e))
+    }
+}
+
+impl<T: Float + Display, C: Cache<Key, T>> DynTwoDLookUpTable<T, C> {
+    /// Writes the breakpoints and the surface of the table as comma separated values, in the layout read
+    /// by [`DynTwoDLookUpTable::from_csv`].
+    pub fn to_csv<W: Write>(&self, out: &mut W) -> FmtResult {
+        let ny = self.y.len();
+        write_surface(out, &self.x, &self.y, |i, j| self.surface[i * ny + j])
+    }
+}
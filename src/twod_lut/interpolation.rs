@@ -1,22 +1,79 @@
+use crate::axis::{AxisPosition, IndexFraction};
+use crate::extrapolation::{Bounded, Extrapolation};
 use crate::twod_lut::SurfaceValueGetter;
-use crate::ConstructionError::{ContainingNansOrInfinities, IncreasingDimOrderError, MinLengthError};
-use crate::{ConstructionError, EPSILON};
+use crate::ConstructionError::{
+    ContainingNansOrInfinities, IncreasingDimOrderError, MinLengthError, RowCountMismatch, RowLengthMismatch,
+};
+use crate::{constant, ConstructionError, LookUpError, EPSILON};
 use core::borrow::Borrow;
 use core::iter::Iterator;
-use core::ops::Sub;
 use itertools::Itertools;
+use num::Float;
 
-pub(super) fn is_object_constructible<I, J, K>(xs: I, ys: J, surface: K) -> Result<bool, ConstructionError>
+/// Interpolation schemes supported by the two dimensional look up tables.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SurfaceInterpolation {
+    /// Linear interpolation along each of the axes, between the four corners of the grid cell.
+    /// [Bilinear Interpolation](https://en.wikipedia.org/wiki/Bilinear_interpolation)
+    #[default]
+    Bilinear,
+    /// Cubic hermite interpolation along each of the axes, between the sixteen grid points around the
+    /// grid cell, so that the surface and its gradient are continuous across the grid lines.
+    /// The gradients at the grid points are estimated by finite differences: the three point difference
+    /// of the neighbouring values at the interior breakpoints, and the one sided difference over the
+    /// boundary segment at the first and the last breakpoints, since there are no values beyond the edges.
+    /// Hence an axis with only two breakpoints is interpolated linearly. Outside the grid, the
+    /// extrapolation policies apply as usual: the default clamping returns the values on the edges of
+    /// the surface, and the linear extrapolation continues the boundary segment linearly, same as
+    /// bilinear interpolation.
+    /// [Bicubic Interpolation](https://en.wikipedia.org/wiki/Bicubic_interpolation)
+    Bicubic,
+}
+
+impl SurfaceInterpolation {
+    /// Returns the interpolation scheme used along each of the axes.
+    pub(super) fn per_axis(&self) -> [AxisInterpolation; 2] {
+        match self {
+            SurfaceInterpolation::Bilinear => [AxisInterpolation::Linear; 2],
+            SurfaceInterpolation::Bicubic => [AxisInterpolation::Cubic; 2],
+        }
+    }
+}
+
+/// Interpolation schemes along a single axis of a two dimensional look up table, so that each of the axes
+/// can use a different scheme, eg. linear interpolation along a continuous axis, and a step along an axis
+/// of discrete values. Along each axis, an index exactly on a breakpoint uses the values at that
+/// breakpoint, and an index outside the range, which is only left by the linear extrapolation, continues
+/// the boundary segment linearly.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisInterpolation {
+    /// Linear interpolation between the neighbouring breakpoints.
+    #[default]
+    Linear,
+    /// Cubic hermite interpolation with finite difference slopes, same as
+    /// [`SurfaceInterpolation::Bicubic`] along one axis.
+    Cubic,
+    /// Uses the values at the nearest breakpoint to the left of the index.
+    Previous,
+    /// Uses the values at the nearest breakpoint to the right of the index.
+    Next,
+    /// Uses the values at the breakpoint closest to the index, and the right one when the index is
+    /// exactly midway between two breakpoints.
+    Nearest,
+}
+
+pub(super) fn is_object_constructible<T, I, J, K>(xs: I, ys: J, surface: K) -> Result<bool, ConstructionError>
 where
+    T: Float,
     I: IntoIterator + Clone,
     J: IntoIterator + Clone,
     K: IntoIterator + Clone,
-    I::Item: Borrow<f64> + Sub + Clone,
-    <<I as IntoIterator>::Item as Sub>::Output: PartialOrd<f64>,
-    J::Item: Borrow<f64> + Sub + Clone,
-    <<J as IntoIterator>::Item as Sub>::Output: PartialOrd<f64>,
+    I::Item: Borrow<T> + Clone,
+    J::Item: Borrow<T> + Clone,
     K::Item: IntoIterator,
-    <<K as IntoIterator>::Item as IntoIterator>::Item: Borrow<f64> + Sub + Clone,
+    <<K as IntoIterator>::Item as IntoIterator>::Item: Borrow<T>,
 {
     if xs.clone().into_iter().count() < 2 || ys.clone().into_iter().count() < 2 {
         return Err(MinLengthError);
@@ -33,8 +90,9 @@ where
 
     let itxs = xs.into_iter().tuple_windows::<(_, _)>();
     let itys = ys.into_iter().tuple_windows::<(_, _)>();
-    if !itertools::all(itxs, |(prev, curr)| curr - prev > EPSILON)
-        || !itertools::all(itys, |(prev, curr)| curr - prev > EPSILON)
+    let increasing = |prev: T, curr: T| curr - prev > constant(EPSILON);
+    if !itertools::all(itxs, |(prev, curr)| increasing(*prev.borrow(), *curr.borrow()))
+        || !itertools::all(itys, |(prev, curr)| increasing(*prev.borrow(), *curr.borrow()))
     {
         return Err(IncreasingDimOrderError);
     }
@@ -42,53 +100,308 @@ where
     Ok(true)
 }
 
-/// Given an index value, try to find the lower and upper bound indices and return them.
-/// If the index is out of bounds, return both values as boundary values. If the index directly
-/// matches the values present in the array, then return the same value as lower and upper bounds.
-#[inline]
-fn get_indices(v: &f64, vs: &[f64]) -> (usize, usize) {
-    if *v < vs[0] {
-        (0, 0)
-    } else if *v > vs[vs.len() - 1] {
-        (vs.len() - 1, vs.len() - 1)
-    } else {
-        match vs.binary_search_by(|val| val.partial_cmp(v).unwrap()) {
-            Ok(ind) => (ind, ind),
-            Err(ind) => (ind - 1, ind),
-        }
+/// Checks that the surface has `m` rows, with `n` values in each of them.
+pub(super) fn is_shape_valid<T>(m: usize, n: usize, surface: &[&[T]]) -> Result<bool, ConstructionError> {
+    if surface.len() != m {
+        return Err(RowCountMismatch {
+            expected: m,
+            actual: surface.len(),
+        });
+    }
+
+    if let Some((row, values)) = surface.iter().enumerate().find(|(_, values)| values.len() != n) {
+        return Err(RowLengthMismatch {
+            row,
+            expected: n,
+            actual: values.len(),
+        });
+    }
+
+    Ok(true)
+}
+
+/// Checks the surface of a table, whose breakpoints are taken from already validated axes. The surface
+/// should have a row for each of the `m` x breakpoints, and a value in each row for each of the `n`
+/// y breakpoints.
+pub(super) fn is_surface_valid<T: Float>(m: usize, n: usize, surface: &[&[T]]) -> Result<bool, ConstructionError> {
+    is_shape_valid(m, n, surface)?;
+
+    if surface
+        .iter()
+        .any(|row| row.iter().any(|v| v.is_nan() || v.is_infinite()))
+    {
+        return Err(ContainingNansOrInfinities);
     }
+
+    Ok(true)
 }
 
-pub(super) fn interpolate(x: &f64, y: &f64, xs: &[f64], ys: &[f64], obj: &dyn SurfaceValueGetter) -> f64 {
+pub(crate) fn interpolate<T: Float>(
+    [x, y]: [T; 2],
+    [xs, ys]: [&[T]; 2],
+    methods: &[AxisInterpolation; 2],
+    extrapolation: &[Extrapolation<T>; 2],
+    obj: &dyn SurfaceValueGetter<T>,
+    positions: [Option<&AxisPosition<T>>; 2],
+) -> Result<T, LookUpError> {
+    // Apply the extrapolation policies, in the order of the axes.
+    let x = match extrapolation[0].apply(x, xs, 0)? {
+        Bounded::Index(x) => x,
+        Bounded::Value(z) => return Ok(z),
+    };
+    let y = match extrapolation[1].apply(y, ys, 1)? {
+        Bounded::Index(y) => y,
+        Bounded::Value(z) => return Ok(z),
+    };
+
     // Retrieve the lower and upper bound indices for x and y axes.
-    let (x1_ind, x2_ind) = get_indices(x, xs);
-    let (y1_ind, y2_ind) = get_indices(y, ys);
-    let (x1, x2, y1, y2) = (xs[x1_ind], xs[x2_ind], ys[y1_ind], ys[y2_ind]);
+    let (x1_ind, x2_ind) = AxisPosition::locate(positions[0], x, xs);
+    let (y1_ind, y2_ind) = AxisPosition::locate(positions[1], y, ys);
+    let alpha = |v: T, vs: &[T], lo: usize, hi: usize| {
+        if lo == hi {
+            T::zero()
+        } else {
+            (v - vs[lo]) / (vs[hi] - vs[lo])
+        }
+    };
+
+    Ok(evaluate(
+        methods,
+        [xs, ys],
+        obj,
+        (x1_ind, x2_ind, alpha(x, xs, x1_ind, x2_ind)),
+        (y1_ind, y2_ind, alpha(y, ys, y1_ind, y2_ind)),
+    ))
+}
+
+/// Interpolates at the positions given by the prelookups on each of the axes. The positions within the
+/// range of the breakpoints are evaluated directly on their quad, while the others are converted back to
+/// the indices, to apply the extrapolation policies.
+pub(super) fn interpolate_with<T: Float>(
+    at: [&IndexFraction<T>; 2],
+    [xs, ys]: [&[T]; 2],
+    methods: &[AxisInterpolation; 2],
+    extrapolation: &[Extrapolation<T>; 2],
+    obj: &dyn SurfaceValueGetter<T>,
+) -> Result<T, LookUpError> {
+    let segment = |at: &IndexFraction<T>, vs: &[T]| {
+        let (index, fraction) = (at.index.min(vs.len() - 2), at.fraction);
+        if fraction == T::zero() {
+            Some((index, index, T::zero()))
+        } else if fraction == T::one() {
+            Some((index + 1, index + 1, T::zero()))
+        } else if fraction > T::zero() && fraction < T::one() {
+            Some((index, index + 1, fraction))
+        } else {
+            None
+        }
+    };
+
+    if let (Some(x), Some(y)) = (segment(at[0], xs), segment(at[1], ys)) {
+        return Ok(evaluate(methods, [xs, ys], obj, x, y));
+    }
+
+    let index = |at: &IndexFraction<T>, vs: &[T]| {
+        let i = at.index.min(vs.len() - 2);
+        vs[i] + at.fraction * (vs[i + 1] - vs[i])
+    };
+
+    let index = [index(at[0], xs), index(at[1], ys)];
+
+    interpolate(index, [xs, ys], methods, extrapolation, obj, [None; 2])
+}
+
+/// Interpolates with the given schemes along each of the axes, on the grid cell given by the lower and upper bound indices on each
+/// of the axes, and the normalized positions within them.
+fn evaluate<T: Float>(
+    methods: &[AxisInterpolation; 2],
+    [xs, ys]: [&[T]; 2],
+    obj: &dyn SurfaceValueGetter<T>,
+    x: (usize, usize, T),
+    y: (usize, usize, T),
+) -> T {
+    if *methods == [AxisInterpolation::Linear; 2] {
+        return quad(obj, x, y);
+    }
+
+    sum(obj, &weights(&methods[0], x, xs, false), &weights(&methods[1], y, ys, false))
+}
+
+/// Differentiates the interpolated surface at the `index` along each of the axes. Strictly between the
+/// breakpoints of an axis, this is the derivative of the segment along it; on a breakpoint, it is the
+/// derivative of the segment to the right of it, except for the last breakpoint, where it is that of the
+/// segment to the left. Outside the range of the breakpoints of an axis, it is the derivative of the
+/// extrapolated surface: zero along a clamped axis, and zero along both the axes where the surface is a
+/// constant, the slope of the boundary segment for the linear extrapolation, and the derivative at the
+/// wrapped index for the periodic one.
+pub(super) fn differentiate<T: Float>(
+    index: [T; 2],
+    axes: [&[T]; 2],
+    methods: &[AxisInterpolation; 2],
+    extrapolation: &[Extrapolation<T>; 2],
+    obj: &dyn SurfaceValueGetter<T>,
+) -> Result<[T; 2], LookUpError> {
+    // Apply the extrapolation policies, in the order of the axes.
+    let (mut bounded, mut is_clamped) = (index, [false; 2]);
+    for axis in 0..2 {
+        let (v, vs) = (index[axis], axes[axis]);
+        is_clamped[axis] =
+            matches!(extrapolation[axis], Extrapolation::Clamp) && !(vs[0] <= v && v <= vs[vs.len() - 1]);
+        bounded[axis] = match extrapolation[axis].apply(v, vs, axis)? {
+            Bounded::Index(v) => v,
+            Bounded::Value(z) if z.is_nan() => return Ok([z; 2]),
+            Bounded::Value(_) => return Ok([T::zero(); 2]),
+        };
+    }
+
+    let position = |v: T, vs: &[T]| {
+        let (lo, hi) = AxisPosition::locate(None, v, vs);
+        (lo, hi, if lo == hi { T::zero() } else { (v - vs[lo]) / (vs[hi] - vs[lo]) })
+    };
+    // The segment to differentiate along an axis, which for an index on a breakpoint is the one to its right,
+    // or to its left for the last breakpoint.
+    let segment = |(lo, hi, alpha): (usize, usize, T), vs: &[T]| match (lo == hi, hi == vs.len() - 1) {
+        (false, _) => (lo, hi, alpha),
+        (true, false) => (lo, lo + 1, T::zero()),
+        (true, true) => (lo - 1, hi, T::one()),
+    };
+
+    let (x, y) = (position(bounded[0], axes[0]), position(bounded[1], axes[1]));
+    let along = |axis: usize| {
+        if is_clamped[axis] {
+            return T::zero();
+        }
+
+        let wx = weights(&methods[0], if axis == 0 { segment(x, axes[0]) } else { x }, axes[0], axis == 0);
+        let wy = weights(&methods[1], if axis == 1 { segment(y, axes[1]) } else { y }, axes[1], axis == 1);
+        sum(obj, &wx, &wy)
+    };
+
+    Ok([along(0), along(1)])
+}
+
+/// Returns the weighted sum of the grid points around a cell, skipping the ones which don't contribute.
+pub(super) fn sum<T: Float>(obj: &dyn SurfaceValueGetter<T>, wx: &Weights<T>, wy: &Weights<T>) -> T {
+    let mut z = T::zero();
+    for (i, &w_i) in wx.weights.iter().enumerate().filter(|(_, w)| !w.is_zero()) {
+        for (j, &w_j) in wy.weights.iter().enumerate().filter(|(_, w)| !w.is_zero()) {
+            z = z + w_i * w_j * obj.get(wx.start + i, wy.start + j);
+        }
+    }
+
+    z
+}
+
+/// The contributions of the breakpoints of an axis to an interpolated value, given as the weights of
+/// up to four consecutive breakpoints from `start`.
+pub(super) struct Weights<T> {
+    start: usize,
+    weights: [T; 4],
+}
+
+/// Computes the weights of the breakpoints of an axis for the interpolation scheme `method`, at the
+/// normalized position `alpha` between the breakpoints `lo` and `hi`, or the weights of the derivative with
+/// respect to the index if `derivative` is set, in which case `lo` and `hi` must differ. For the cubic
+/// scheme, the slopes at the breakpoints are the finite differences of the neighbouring values. Positions
+/// outside the segment, which are only left by the linear extrapolation, are interpolated linearly.
+pub(super) fn weights<T: Float>(
+    method: &AxisInterpolation,
+    (lo, hi, alpha): (usize, usize, T),
+    vs: &[T],
+    derivative: bool,
+) -> Weights<T> {
+    let start = lo.saturating_sub(1);
+    let mut weights = [T::zero(); 4];
+    let mut add = |node: usize, w: T| weights[node - start] = weights[node - start] + w;
+    let is_linear = alpha < T::zero() || alpha > T::one() || *method == AxisInterpolation::Linear;
+
+    match method {
+        _ if lo == hi => add(lo, T::one()),
+        _ if is_linear && derivative => {
+            let h = vs[hi] - vs[lo];
+            add(lo, -T::one() / h);
+            add(hi, T::one() / h);
+        }
+        _ if is_linear => {
+            add(lo, T::one() - alpha);
+            add(hi, alpha);
+        }
+        // The steps are constant between the breakpoints.
+        _ if derivative && *method != AxisInterpolation::Cubic => {}
+        AxisInterpolation::Previous => add(lo, T::one()),
+        AxisInterpolation::Next => add(hi, T::one()),
+        AxisInterpolation::Nearest if alpha < constant(0.5) => add(lo, T::one()),
+        AxisInterpolation::Nearest => add(hi, T::one()),
+        _ => {
+            let (t, h, last) = (alpha, vs[hi] - vs[lo], vs.len() - 1);
+            let (t2, t3) = (t * t, t * t * t);
+            let (two, three) = (constant::<T>(2.0), constant::<T>(3.0));
+
+            // The coefficients of the values and the slopes at both the ends of the segment, in the cubic
+            // hermite form, or their derivatives with respect to the index.
+            let [y_lo, y_hi, d_lo, d_hi] = if derivative {
+                let six = constant::<T>(6.0);
+                [
+                    (six * t2 - six * t) / h,
+                    (six * t - six * t2) / h,
+                    three * t2 - constant::<T>(4.0) * t + T::one(),
+                    three * t2 - two * t,
+                ]
+            } else {
+                [
+                    two * t3 - three * t2 + T::one(),
+                    three * t2 - two * t3,
+                    (t3 - two * t2 + t) * h,
+                    (t3 - t2) * h,
+                ]
+            };
+            add(lo, y_lo);
+            add(hi, y_hi);
+
+            // The slope at each end of the segment is a combination of the neighbouring values, which is
+            // distributed over them along with the coefficient of the slope.
+            for (k, c) in [(lo, d_lo), (hi, d_hi)] {
+                if k == 0 || k == last {
+                    let (a, b) = if k == 0 { (0, 1) } else { (last - 1, last) };
+                    let d = vs[b] - vs[a];
+                    add(b, c / d);
+                    add(a, -c / d);
+                } else {
+                    let (hl, hr) = (vs[k] - vs[k - 1], vs[k + 1] - vs[k]);
+                    let s = hl + hr;
+                    add(k + 1, c * hl / (hr * s));
+                    add(k - 1, -c * hr / (hl * s));
+                    add(k, c * (hr / (hl * s) - hl / (hr * s)));
+                }
+            }
+        }
+    }
+
+    Weights { start, weights }
+}
+
+/// Interpolates within the quad given by the lower and upper bound indices on each of the axes, and the
+/// normalized positions within them.
+fn quad<T: Float>(obj: &dyn SurfaceValueGetter<T>, x: (usize, usize, T), y: (usize, usize, T)) -> T {
+    let ((x1_ind, x2_ind, alpha_x), (y1_ind, y2_ind, alpha_y)) = (x, y);
 
     // These represent the four corners of the quad, within which the interpolation is to be done.
-    let fq11 = obj.get(y1_ind, x1_ind);
-    let fq12 = obj.get(y1_ind, x2_ind);
-    let fq21 = obj.get(y2_ind, x1_ind);
-    let fq22 = obj.get(y2_ind, x2_ind);
-
-    // if both the indices are out of range, then return the corner point
-    // if one of the indices is out of range or maps to an exact breakpoint,
-    // then perform interpolation only in other direction.
+    let fq11 = obj.get(x1_ind, y1_ind);
+    let fq12 = obj.get(x1_ind, y2_ind);
+    let fq21 = obj.get(x2_ind, y1_ind);
+    let fq22 = obj.get(x2_ind, y2_ind);
+
+    // if both the indices map to exact breakpoints, then return the corner point
+    // if one of the indices maps to an exact breakpoint, then perform interpolation only in other
+    // direction.
     // else perform interpolation on both the axes.
-    if fq11 == fq22 {
+    if x1_ind == x2_ind && y1_ind == y2_ind {
         fq11
-    } else if fq11 == fq21 {
-        let alpha = (x - x1) / (x2 - x1);
-
-        fq11 + alpha * (fq12 - fq11)
-    } else if fq11 == fq12 {
-        let alpha = (y - y1) / (y2 - y1);
-
-        fq11 + alpha * (fq21 - fq11)
+    } else if y1_ind == y2_ind {
+        fq11 + alpha_x * (fq21 - fq11)
+    } else if x1_ind == x2_ind {
+        fq11 + alpha_y * (fq12 - fq11)
     } else {
-        let alpha_x = (x - x1) / (x2 - x1);
-        let alpha_y = (y - y1) / (y2 - y1);
-
         let fxy1 = fq11 + alpha_x * (fq21 - fq11);
         let fxy2 = fq12 + alpha_x * (fq22 - fq12);
 
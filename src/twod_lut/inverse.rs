@@ -0,0 +1,94 @@
+use crate::axis::search;
+use crate::extrapolation::Bounded;
+use crate::oned_lut::{invert, solutions, Curve, Segments};
+use crate::twod_lut::interpolation::{sum, weights, Weights};
+use crate::twod_lut::SurfaceValueGetter;
+use crate::{AxisInterpolation, Extrapolation, LookUpError};
+use num::Float;
+
+/// The slice of a surface at an index `x`, which is a curve along the y axis, interpolated with the
+/// scheme of the surface along it. The x axis is searched, and its extrapolation policy applied, once
+/// when the slice is taken, so evaluating the slice only weighs the values of the surface around it.
+pub(super) struct Column<'a, T> {
+    ys: &'a [T],
+    method: AxisInterpolation,
+    across: Across<T>,
+    obj: &'a dyn SurfaceValueGetter<T>,
+}
+
+/// How the slice is taken across the x axis.
+enum Across<T> {
+    /// The weights of the x breakpoints around the index.
+    Weights(Weights<T>),
+    /// The value of the whole slice, when the extrapolation policy of the x axis gives one.
+    Value(T),
+}
+
+impl<'a, T: Float> Column<'a, T> {
+    /// Returns the slice at `x`, or an error if `x` lies outside the range of the breakpoints, and the
+    /// extrapolation policy of the x axis is [`Extrapolation::Error`].
+    pub(super) fn new(
+        x: T,
+        [xs, ys]: [&'a [T]; 2],
+        methods: &[AxisInterpolation; 2],
+        extrapolation: &[Extrapolation<T>; 2],
+        obj: &'a dyn SurfaceValueGetter<T>,
+    ) -> Result<Column<'a, T>, LookUpError> {
+        let across = match extrapolation[0].apply(x, xs, 0)? {
+            Bounded::Index(x) => {
+                let (lo, hi) = search(x, xs);
+                let alpha = if lo == hi { T::zero() } else { (x - xs[lo]) / (xs[hi] - xs[lo]) };
+                Across::Weights(weights(&methods[0], (lo, hi, alpha), xs, false))
+            }
+            Bounded::Value(z) => Across::Value(z),
+        };
+
+        Ok(Column {
+            ys,
+            method: methods[1],
+            across,
+            obj,
+        })
+    }
+
+    /// Returns the `y` where the slice takes the value `z`, if it is strictly monotonic.
+    pub(super) fn invert(&self, z: T) -> Result<T, LookUpError> {
+        invert(z, self)
+    }
+
+    /// Returns all the `y` where the slice takes the value `z`, in increasing order.
+    pub(super) fn solutions(self, z: T) -> impl Iterator<Item = T> + 'a {
+        solutions(z, self)
+    }
+
+    /// Returns the value of the slice between the y breakpoints `lo` and `hi`, at the normalized position
+    /// `alpha` within them.
+    fn at(&self, y: (usize, usize, T)) -> T {
+        match &self.across {
+            Across::Weights(wx) => sum(self.obj, wx, &weights(&self.method, y, self.ys, false)),
+            Across::Value(z) => *z,
+        }
+    }
+}
+
+impl<T: Float> Curve<T> for Column<'_, T> {
+    fn xs(&self) -> &[T] {
+        self.ys
+    }
+
+    fn y(&self, k: usize) -> T {
+        self.at((k, k, T::zero()))
+    }
+
+    fn segments(&self) -> Segments {
+        match self.method {
+            AxisInterpolation::Linear => Segments::Linear,
+            AxisInterpolation::Previous | AxisInterpolation::Next | AxisInterpolation::Nearest => Segments::Steps,
+            AxisInterpolation::Cubic => Segments::Cubic,
+        }
+    }
+
+    fn segment(&self, prev: usize, alpha: T) -> T {
+        self.at((prev, prev + 1, alpha))
+    }
+}
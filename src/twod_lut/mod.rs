@@ -6,25 +6,41 @@
 //! areas.
 //! [Bilinear Interpolation](https://en.wikipedia.org/wiki/Bilinear_interpolation)
 //!
+//! For smoother surfaces, the tables can also use [`SurfaceInterpolation::Bicubic`] interpolation, and
+//! each of the axes can have its own [`AxisInterpolation`] scheme.
+//!
+//! Each of the axes has its own [`Extrapolation`] policy, which decides the behavior when the index on
+//! that axis lies outside the range of the breakpoints. The policies are applied in the order x, y.
 
+mod csv;
 mod interpolation;
+mod inverse;
+#[cfg(feature = "serde")]
+mod serialization;
+
+use crate::twod_lut::interpolation::{
+    differentiate, interpolate, interpolate_with, is_object_constructible, is_shape_valid, is_surface_valid,
+};
+use crate::twod_lut::inverse::Column;
 
-use crate::twod_lut::interpolation::{interpolate, is_object_constructible};
 use cfg_if::cfg_if;
+pub(crate) use interpolation::interpolate as interpolate_surface;
+pub use interpolation::{AxisInterpolation, SurfaceInterpolation};
 
 cfg_if! {
     if #[cfg(feature="no-std")] {
         use crate::MAX_FUNCTION_POINTS;
-        use hashbrown::HashMap;
         use crate::ConstructionError::MaxLengthError;
     } else {
-        use std::collections::HashMap;
         use std::borrow::Cow;
     }
 }
 
-use crate::{ConstructionError, Vec};
-use core::cell::RefCell;
+use crate::compression::{compress_surface, CompressionError};
+use crate::{
+    push, to_vec, Axis, AxisPosition, AxisRange, Cache, CacheStats, Checked, Compressed, ConstructionError,
+    Extrapolation, IndexFraction, LocalCache, LookUpError, Vec,
+};
 use num::Float;
 
 type Key = ((u64, i16, i8), (u64, i16, i8));
@@ -33,26 +49,32 @@ type Key = ((u64, i16, i8), (u64, i16, i8));
 /// types - eg. [[f64; M]; N], of &[[f64;M]], or &[&[f64]], or Cow<&'_ [f64;M]> or Cow<&'_ Cow<&'_ [f64]>> etc.
 /// Each of the Lookuptable objects catering to these objects should implement this trait, so that the
 /// interpolate function can be uniform.
-trait SurfaceValueGetter {
-    fn get(&self, x: usize, y: usize) -> f64;
+pub(crate) trait SurfaceValueGetter<T> {
+    fn get(&self, x: usize, y: usize) -> T;
 }
 
 /// Type alias for a surface - a 2D array, where M is the height(rows) and N is the width(columns).
-pub type SurfaceType<const M: usize, const N: usize> = [[f64; N]; M];
+/// The value at `surface[i][j]` corresponds to the breakpoints `x[i]` and `y[j]`.
+pub type SurfaceType<const M: usize, const N: usize, T = f64> = [[T; N]; M];
 
 /// Two Dimensional Linear interpolation with nearest neighbor extrapolation when indices are outside
 /// support region, and with caching support to enable fast lookups on frequently used values.
 /// M is the number of rows and signifies height
 /// N is the number of columns and signifies width
+/// T is the type of the breakpoints and the surface values, which can be any floating point type, and
+/// defaults to `f64`.
+/// C is the [`Cache`] of the table, which can be changed with [`TwoDLookUpTable::with_cache`].
 #[derive(Debug)]
-pub struct TwoDLookUpTable<const M: usize, const N: usize> {
-    x: [f64; M],                       // Breakpoints/sample points on x-axis
-    y: [f64; N],                       // Breakpoints/sample points on y-axis
-    surface: SurfaceType<M, N>,        // Corresponding function values for x and y indices.
-    cache: RefCell<HashMap<Key, f64>>, // A cache to support fast lookup for frequently used values.
+pub struct TwoDLookUpTable<const M: usize, const N: usize, T = f64, C = LocalCache<Key, T>> {
+    x: [T; M],                             // Breakpoints/sample points on x-axis
+    y: [T; N],                             // Breakpoints/sample points on y-axis
+    surface: SurfaceType<M, N, T>,         // Corresponding function values for x and y indices.
+    interpolation: [AxisInterpolation; 2], // Interpolation schemes along x and y axes.
+    extrapolation: [Extrapolation<T>; 2],  // Extrapolation policies for x and y axes.
+    cache: C,                              // A cache to support fast lookup for frequently used values.
 }
 
-impl<const M: usize, const N: usize> TwoDLookUpTable<M, N> {
+impl<const M: usize, const N: usize, T: Float> TwoDLookUpTable<M, N, T> {
     /// Constructs a `OneDLookUpTable` object, given the input arrays `x` and `y` modelling the sample
     /// points of a uni-variate function.
     /// If the `x` or `y` values are not sorted in ascending order:
@@ -76,17 +98,134 @@ impl<const M: usize, const N: usize> TwoDLookUpTable<M, N> {
     ///  assert!(matches!(lut.err().unwrap(), MinLengthError));
     /// ```
     pub fn new(
-        xs: [f64; M],
-        ys: [f64; N],
-        surface: SurfaceType<M, N>,
-    ) -> Result<TwoDLookUpTable<M, N>, ConstructionError> {
-        is_object_constructible(&xs, &ys, &surface).map(|_| TwoDLookUpTable {
+        xs: [T; M],
+        ys: [T; N],
+        surface: SurfaceType<M, N, T>,
+    ) -> Result<TwoDLookUpTable<M, N, T>, ConstructionError> {
+        Self::with_interpolation(xs, ys, surface, [AxisInterpolation::Linear; 2])
+    }
+
+    /// Constructs a `TwoDLookUpTable` object, which uses the given `interpolation` schemes along the x
+    /// and y axes. The inputs are validated in the same way as [`TwoDLookUpTable::new`].
+    /// ```
+    ///  use look_up_table::{AxisInterpolation, TwoDLookUpTable};
+    ///  let interpolation = [AxisInterpolation::Linear, AxisInterpolation::Previous];
+    ///  let (speed, gear) = ([1000.0f64, 2000.0], [1.0, 2.0, 3.0]);
+    ///  let map = TwoDLookUpTable::with_interpolation(speed, gear, [[1.0, 2.0, 3.0], [3.0, 4.0, 5.0]], interpolation);
+    ///  assert!((map.unwrap().get(&1500.0, &2.5) - 3.0).abs() < 1e-12);
+    /// ```
+    pub fn with_interpolation(
+        xs: [T; M],
+        ys: [T; N],
+        surface: SurfaceType<M, N, T>,
+        interpolation: [AxisInterpolation; 2],
+    ) -> Result<TwoDLookUpTable<M, N, T>, ConstructionError> {
+        is_object_constructible::<T, _, _, _>(&xs, &ys, &surface).map(|_| TwoDLookUpTable {
             x: xs,
             y: ys,
             surface,
-            cache: RefCell::new(HashMap::new()),
+            interpolation,
+            extrapolation: [Extrapolation::Clamp; 2],
+            cache: LocalCache::default(),
+        })
+    }
+
+    /// Constructs a `TwoDLookUpTable` object, which takes a copy of its breakpoints from the shared axes
+    /// `x_axis` and `y_axis`. The breakpoints are not validated again, only the `surface` values and its
+    /// dimensions are, which should match the number of breakpoints of the axes.
+    /// ```
+    ///  use look_up_table::{Axis, TwoDLookUpTable};
+    ///  let (speed, load) = (Axis::new(&[1000.0f64, 2000.0]).unwrap(), Axis::new(&[0.0, 0.5, 1.0]).unwrap());
+    ///  let map = TwoDLookUpTable::from_axes(&speed, &load, [[0.0, 1.0, 2.0], [10.0, 11.0, 12.0]]).unwrap();
+    ///  assert!((map.get_at(&speed.search(1500.0), &load.search(0.75)) - 6.5).abs() < 1e-12);
+    /// ```
+    ///
+    /// If an axis doesn't have as many breakpoints as the surface has rows or values in each row:
+    /// ```
+    ///  use look_up_table::{Axis, ConstructionError::RowLengthMismatch, TwoDLookUpTable};
+    ///  let (speed, load) = (Axis::new(&[1000.0f64, 2000.0]).unwrap(), Axis::new(&[0.0, 0.5, 1.0]).unwrap());
+    ///  let map = TwoDLookUpTable::from_axes(&speed, &load, [[0.0, 1.0], [10.0, 11.0]]);
+    ///  assert!(matches!(map.err().unwrap(), RowLengthMismatch { row: 0, expected: 3, actual: 2 }));
+    /// ```
+    pub fn from_axes(
+        x_axis: &Axis<T>,
+        y_axis: &Axis<T>,
+        surface: SurfaceType<M, N, T>,
+    ) -> Result<TwoDLookUpTable<M, N, T>, ConstructionError> {
+        let (xs, ys) = (x_axis.breakpoints(), y_axis.breakpoints());
+        let rows: [&[T]; M] = core::array::from_fn(|i| &surface[i][..]);
+        is_surface_valid(xs.len(), ys.len(), &rows)?;
+
+        Ok(TwoDLookUpTable {
+            x: core::array::from_fn(|i| xs[i]),
+            y: core::array::from_fn(|j| ys[j]),
+            surface,
+            interpolation: [AxisInterpolation::Linear; 2],
+            extrapolation: [Extrapolation::Clamp; 2],
+            cache: LocalCache::default(),
         })
     }
+}
+
+impl<const M: usize, const N: usize, T: Float, C: Cache<Key, T>> TwoDLookUpTable<M, N, T, C> {
+    /// Returns the table with a different [`Cache`], which starts out empty. See [`crate::ShardedCache`]
+    /// for an example.
+    pub fn with_cache<D: Cache<Key, T> + Default>(self) -> TwoDLookUpTable<M, N, T, D> {
+        TwoDLookUpTable {
+            x: self.x,
+            y: self.y,
+            surface: self.surface,
+            interpolation: self.interpolation,
+            extrapolation: self.extrapolation,
+            cache: D::default(),
+        }
+    }
+
+    /// Returns the counts of the hits, misses and evictions of the cache of the table.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Sets the interpolation scheme between the grid points. The default scheme is
+    /// [`SurfaceInterpolation::Bilinear`].
+    /// ```
+    ///  use look_up_table::{SurfaceInterpolation, TwoDLookUpTable};
+    ///  let xs = [0.0f64, 1.0, 2.0, 3.0];
+    ///  let mut lut = TwoDLookUpTable::new(xs, xs, xs.map(|x| xs.map(|y| x * x + y))).unwrap();
+    ///  lut.set_interpolation(SurfaceInterpolation::Bicubic);
+    ///  assert!((lut.get(&1.5, &0.5) - 2.75).abs() < 1e-12);
+    /// ```
+    pub fn set_interpolation(&mut self, interpolation: SurfaceInterpolation) {
+        self.interpolation = interpolation.per_axis();
+        self.cache.clear();
+    }
+
+    /// Sets the interpolation schemes along each of the axes, eg. to interpolate linearly along a
+    /// continuous x axis, and to step along a discrete y axis.
+    /// ```
+    ///  use look_up_table::{AxisInterpolation, TwoDLookUpTable};
+    ///  let mut lut = TwoDLookUpTable::new([0.0f64, 10.0], [1.0, 2.0], [[0.0, 100.0], [10.0, 200.0]]).unwrap();
+    ///  lut.set_axis_interpolation(AxisInterpolation::Linear, AxisInterpolation::Previous);
+    ///  assert!((lut.get(&5.0, &1.9) - 5.0).abs() < f64::EPSILON);
+    /// ```
+    pub fn set_axis_interpolation(&mut self, x: AxisInterpolation, y: AxisInterpolation) {
+        self.interpolation = [x, y];
+        self.cache.clear();
+    }
+
+    /// Sets the policies for the lookups outside the range of the breakpoints, for each of the axes.
+    /// The default policy is [`Extrapolation::Clamp`] for both the axes.
+    /// ```
+    ///  use look_up_table::{Extrapolation, TwoDLookUpTable};
+    ///  let mut lut = TwoDLookUpTable::new([1.0f64, 2.0], [1.0, 2.0], [[1.0, 2.0], [3.0, 4.0]]).unwrap();
+    ///  lut.set_extrapolation(Extrapolation::Linear, Extrapolation::Constant(0.0));
+    ///  assert!((lut.get(&3.0, &1.0) - 5.0).abs() < f64::EPSILON);
+    ///  assert!(lut.get(&1.5, &3.0).abs() < f64::EPSILON);
+    /// ```
+    pub fn set_extrapolation(&mut self, x: Extrapolation<T>, y: Extrapolation<T>) {
+        self.extrapolation = [x, y];
+        self.cache.clear();
+    }
 
     /// Returns an interpolated value for the given `x` and `y` indices. If the values are directly
     /// present in the array, it directly returns the corresponding `surface` value without any
@@ -94,70 +233,361 @@ impl<const M: usize, const N: usize> TwoDLookUpTable<M, N> {
     /// region, or directly present in the array, then does the interpolation in the other direction.
     /// If both indices are not present in the arrays, but are within the bounds, then does the
     /// linear interpolation in each direction to arrive at the final value.
-    pub fn get(&self, x: &f64, y: &f64) -> f64 {
-        // First do the cache lookup
-        let key = (x.integer_decode(), y.integer_decode());
+    /// If the extrapolation policy of an axis is [`Extrapolation::Error`], and the index lies outside
+    /// the range of that axis, then NaN is returned.
+    pub fn get(&self, x: &T, y: &T) -> T {
+        self.try_get(x, y).unwrap_or(T::nan())
+    }
 
-        if self.cache.borrow().contains_key(&key) {
-            return *self.cache.borrow().get(&key).unwrap();
-        }
+    /// Returns an interpolated value for the given `x` and `y` indices similar to
+    /// [`TwoDLookUpTable::get`], except that an index outside the range of the breakpoints is reported
+    /// as an error, when the extrapolation policy of its axis is [`Extrapolation::Error`].
+    pub fn try_get(&self, x: &T, y: &T) -> Result<T, LookUpError> {
+        self.view().try_get(x, y)
+    }
 
-        let z = interpolate(x, y, &self.x, &self.y, self);
+    /// Returns an interpolated value for the given `x` and `y` indices similar to
+    /// [`TwoDLookUpTable::try_get`], along with where the indices lie relative to the breakpoints of each
+    /// of the axes, so that the extrapolated values can be told apart from the interpolated ones.
+    pub fn get_checked(&self, x: &T, y: &T) -> Result<Checked<T, 2>, LookUpError> {
+        self.view().get_checked(x, y)
+    }
 
-        // store the value in cache before returning, to speedup look up process in the future.
-        self.cache.borrow_mut().insert(key, z);
+    /// Returns an interpolated value for the indices at the given positions, reusing the searches done
+    /// on the shared [`Axis`] objects of the table. The lookup bypasses the cache, since it is already
+    /// cheap.
+    pub fn get_at(&self, x: &AxisPosition<T>, y: &AxisPosition<T>) -> T {
+        self.try_get_at(x, y).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value for the indices at the given positions similar to
+    /// [`TwoDLookUpTable::get_at`], or an error if an index lies outside the range of the breakpoints,
+    /// and the extrapolation policy of its axis is [`Extrapolation::Error`].
+    pub fn try_get_at(&self, x: &AxisPosition<T>, y: &AxisPosition<T>) -> Result<T, LookUpError> {
+        self.view().try_get_at(x, y)
+    }
+
+    /// Returns an interpolated value at the positions given by the prelookups on the [`Axis`] objects with
+    /// the same breakpoints as the x and y axes, which skips the search of the breakpoints. The positions
+    /// outside the range of the breakpoints are subject to the extrapolation policies, same as
+    /// [`TwoDLookUpTable::get`].
+    /// ```
+    ///  use look_up_table::{Axis, TwoDLookUpTable};
+    ///  let axis = Axis::new(&[1.0f64, 2.0]).unwrap();
+    ///  let lut = TwoDLookUpTable::new([1.0, 2.0], [1.0, 2.0], [[1.0, 2.0], [3.0, 4.0]]).unwrap();
+    ///  let (x, y) = (axis.prelookup(1.5), axis.prelookup(1.25));
+    ///  assert!((lut.interpolate_with(&x, &y) - 2.25).abs() < f64::EPSILON);
+    /// ```
+    pub fn interpolate_with(&self, x: &IndexFraction<T>, y: &IndexFraction<T>) -> T {
+        self.try_interpolate_with(x, y).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value at the positions given by the prelookups similar to
+    /// [`TwoDLookUpTable::interpolate_with`], or an error if a position lies outside the range of the
+    /// breakpoints, and the extrapolation policy of its axis is [`Extrapolation::Error`].
+    pub fn try_interpolate_with(&self, x: &IndexFraction<T>, y: &IndexFraction<T>) -> Result<T, LookUpError> {
+        self.view().try_interpolate_with(x, y)
+    }
 
-        *self.cache.borrow().get(&key).unwrap()
+    /// Returns the `y` index at which the table takes the value `z` for the given `x` index, i.e. the
+    /// inverse of [`TwoDLookUpTable::get`] along the y axis, or NaN if there is no unique index, as
+    /// described in [`TwoDLookUpTable::try_inverse_y`].
+    /// ```
+    ///  use look_up_table::TwoDLookUpTable;
+    ///  // The torque for the engine speed and the throttle.
+    ///  let lut = TwoDLookUpTable::new([1000.0f64, 2000.0], [0.0, 0.5, 1.0], [[0.0, 60.0, 100.0], [0.0, 80.0, 140.0]]).unwrap();
+    ///  assert!((lut.inverse_y(&1500.0, &105.0) - 0.85).abs() < 1e-12);
+    ///  assert!(lut.inverse_y(&1500.0, &150.0).is_nan());
+    /// ```
+    pub fn inverse_y(&self, x: &T, z: &T) -> T {
+        self.try_inverse_y(x, z).unwrap_or(T::nan())
+    }
+
+    /// Returns the `y` index at which the table takes the value `z` for the given `x` index. The slice of
+    /// the surface at `x` is interpolated with the schemes of the table, and is then inverted in the same
+    /// way as [`crate::OneDLookUpTable::try_inverse`], so it must be strictly increasing or strictly
+    /// decreasing, including between the breakpoints of a cubic y axis, and enclose `z`. As the slice
+    /// depends on `x`, this is checked on every call, but the x axis is only searched once. The
+    /// extrapolation policy of the x axis applies to `x`, while that of the y axis doesn't apply.
+    /// ```
+    ///  use look_up_table::{Extrapolation, LookUpError, TwoDLookUpTable};
+    ///  let mut lut = TwoDLookUpTable::new([0.0f64, 1.0], [0.0, 1.0, 2.0], [[0.0, 1.0, 0.0], [0.0, 2.0, 4.0]]).unwrap();
+    ///  assert_eq!(lut.try_inverse_y(&1.0, &3.0), Ok(1.5));
+    ///  // The slice at x = 0.5 is [0.0, 1.5, 2.0], at x = 0.0 it rises and falls.
+    ///  assert_eq!(lut.try_inverse_y(&0.5, &1.75), Ok(1.5));
+    ///  assert_eq!(lut.try_inverse_y(&0.0, &0.5), Err(LookUpError::NotMonotonic));
+    ///  lut.set_extrapolation(Extrapolation::Error, Extrapolation::Clamp);
+    ///  assert!(matches!(lut.try_inverse_y(&2.0, &1.0), Err(LookUpError::OutOfRange { axis: 0, .. })));
+    /// ```
+    pub fn try_inverse_y(&self, x: &T, z: &T) -> Result<T, LookUpError> {
+        self.view().try_inverse_y(x, z)
+    }
+
+    /// Returns all the `y` indices within the range of the breakpoints at which the table takes the value
+    /// `z` for the given `x` index, in increasing order, for the slices which are not monotonic. The
+    /// solutions are found in the same way as [`crate::OneDLookUpTable::inverse_all`], and an error is
+    /// only returned if `x` lies outside the range of the breakpoints, and the extrapolation policy of
+    /// the x axis is [`Extrapolation::Error`].
+    /// ```
+    ///  use look_up_table::TwoDLookUpTable;
+    ///  let lut = TwoDLookUpTable::new([0.0f64, 1.0], [0.0, 1.0, 2.0], [[0.0, 2.0, 0.0], [0.0, 4.0, 0.0]]).unwrap();
+    ///  assert_eq!(lut.inverse_y_all(&0.5, &1.5).unwrap().collect::<Vec<_>>(), [0.5, 1.5]);
+    /// ```
+    pub fn inverse_y_all(&self, x: &T, z: &T) -> Result<impl Iterator<Item = T> + '_, LookUpError> {
+        self.view().inverse_y_all(x, z)
+    }
+
+    /// Returns the partial derivatives of the table at the given `x` and `y` indices, with respect to each
+    /// of the indices, or NaNs if an index lies outside the range of the breakpoints, and the extrapolation
+    /// policy of its axis is [`Extrapolation::Error`]. See [`TwoDLookUpTable::try_gradient`].
+    /// ```
+    ///  use look_up_table::TwoDLookUpTable;
+    ///  let lut = TwoDLookUpTable::new([0.0, 2.0], [0.0, 1.0], [[0.0, 1.0], [4.0, 7.0]]).unwrap();
+    ///  assert_eq!(lut.gradient(&1.0, &0.5), [2.5, 2.0]);
+    /// ```
+    pub fn gradient(&self, x: &T, y: &T) -> [T; 2] {
+        self.try_gradient(x, y).unwrap_or([T::nan(); 2])
+    }
+
+    /// Returns the partial derivatives `[∂z/∂x, ∂z/∂y]` of the table at the given `x` and `y` indices, which
+    /// are evaluated analytically for the interpolation scheme of each of the axes: piecewise constant
+    /// along a linear axis, continuous along a cubic one, and zero along the steps. On a breakpoint, the
+    /// derivative along its axis is that of the segment to the right of it, and at the last breakpoint
+    /// that of the segment to its left. Outside the range of the breakpoints of an axis, the derivatives
+    /// are those of the extrapolated surface: zero along an axis with [`Extrapolation::Clamp`], zero along
+    /// both the axes for [`Extrapolation::Constant`], NaN for [`Extrapolation::NaN`], the slope of the
+    /// boundary segment for [`Extrapolation::Linear`], those at the wrapped index for
+    /// [`Extrapolation::Periodic`], and an error for [`Extrapolation::Error`].
+    /// ```
+    ///  use look_up_table::{Extrapolation, LookUpError, TwoDLookUpTable};
+    ///  let mut lut = TwoDLookUpTable::new([0.0, 1.0, 2.0], [0.0, 1.0], [[0.0, 1.0], [1.0, 2.0], [3.0, 4.0]]).unwrap();
+    ///  // The slope along x of the segment to the right of the breakpoint.
+    ///  assert_eq!(lut.try_gradient(&1.0, &0.5), Ok([2.0, 1.0]));
+    ///  assert_eq!(lut.try_gradient(&3.0, &0.5), Ok([0.0, 1.0]));
+    ///  lut.set_extrapolation(Extrapolation::Linear, Extrapolation::Error);
+    ///  assert_eq!(lut.try_gradient(&3.0, &0.5), Ok([2.0, 1.0]));
+    ///  assert!(matches!(lut.try_gradient(&0.5, &2.0), Err(LookUpError::OutOfRange { axis: 1, .. })));
+    /// ```
+    pub fn try_gradient(&self, x: &T, y: &T) -> Result<[T; 2], LookUpError> {
+        self.view().try_gradient(x, y)
+    }
+
+    /// Returns a [`DynTwoDLookUpTable`] with the fewest rows and columns this finds, whose lookups are
+    /// within `tolerance` of the lookups of this table, along with the largest difference. The breakpoints
+    /// are dropped in the same way as [`crate::NDLookUpTable::<2>::compress`], and the number of them is
+    /// only known at runtime, so the compressed table is a dynamic one, with the same interpolation and
+    /// extrapolation as this table. As the error of dropping a breakpoint is measured along the straight
+    /// segments replacing it, only the tables with [`AxisInterpolation::Linear`] along both of the axes
+    /// can be compressed, and the other schemes are reported as a
+    /// [`CompressionError::NonLinearInterpolation`]: the cubic slopes at the kept breakpoints depend on the
+    /// dropped ones, and a step doesn't pass through its values between the breakpoints, so the error
+    /// wouldn't be bounded by the one at the dropped breakpoints.
+    /// ```
+    ///  use look_up_table::TwoDLookUpTable;
+    ///  // Linear along x, and curved along y.
+    ///  let lut = TwoDLookUpTable::<4, 3>::from_fn(|x, y| x + y * y, 0.0..=3.0, 0.0..=2.0).unwrap();
+    ///
+    ///  let compressed = lut.compress(0.1).unwrap();
+    ///  assert_eq!(compressed.table.axes(), [&[0.0, 3.0][..], &[0.0, 1.0, 2.0][..]]);
+    ///  assert_eq!(compressed.table.get(&1.5, &0.5), lut.get(&1.5, &0.5));
+    /// ```
+    ///
+    /// If the `tolerance` is negative:
+    /// ```
+    ///  use look_up_table::{CompressionError::InvalidTable, ConstructionError::NegativeTolerance, TwoDLookUpTable};
+    ///  let lut = TwoDLookUpTable::new([0.0, 1.0], [0.0, 1.0], [[0.0, 1.0], [1.0, 2.0]]).unwrap();
+    ///  assert!(matches!(lut.compress(-0.1).err().unwrap(), InvalidTable(NegativeTolerance)));
+    /// ```
+    ///
+    /// If the interpolation is not linear along both of the axes:
+    /// ```
+    ///  use look_up_table::{AxisInterpolation, CompressionError::NonLinearInterpolation, TwoDLookUpTable};
+    ///  let mut lut = TwoDLookUpTable::new([0.0, 1.0], [0.0, 1.0], [[0.0, 1.0], [1.0, 2.0]]).unwrap();
+    ///  lut.set_axis_interpolation(AxisInterpolation::Linear, AxisInterpolation::Previous);
+    ///  assert!(matches!(lut.compress(0.1).err().unwrap(), NonLinearInterpolation));
+    /// ```
+    pub fn compress(&self, tolerance: T) -> Result<Compressed<DynTwoDLookUpTable<T>, T>, CompressionError> {
+        self.view().compress(tolerance)
+    }
+
+    fn view(&self) -> View<'_, T, C> {
+        View {
+            xs: &self.x,
+            ys: &self.y,
+            interpolation: &self.interpolation,
+            extrapolation: &self.extrapolation,
+            cache: &self.cache,
+            surface: self,
+        }
     }
 }
 
-impl<const M: usize, const N: usize> SurfaceValueGetter for TwoDLookUpTable<M, N> {
-    fn get(&self, x: usize, y: usize) -> f64 {
+impl<const M: usize, const N: usize, T: Float, C> SurfaceValueGetter<T> for TwoDLookUpTable<M, N, T, C> {
+    fn get(&self, x: usize, y: usize) -> T {
         self.surface[x][y]
     }
 }
 
-impl SurfaceValueGetter for TwoDLookUpTableRef<'_, '_, '_> {
-    fn get(&self, x: usize, y: usize) -> f64 {
-        self.surface[x][y]
+impl<T: Float, C> SurfaceValueGetter<T> for TwoDLookUpTableRef<'_, '_, '_, T, C> {
+    fn get(&self, x: usize, y: usize) -> T {
+        match self.orientation {
+            SurfaceOrientation::RowPerX => self.surface[x][y],
+            SurfaceOrientation::RowPerY => self.surface[y][x],
+        }
+    }
+}
+
+/// The layout of the rows of a surface, which is referenced by a [`TwoDLookUpTableRef`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurfaceOrientation {
+    /// The surface has a row for each of the x breakpoints, so that the value at `surface[i][j]`
+    /// corresponds to the breakpoints `x[i]` and `y[j]`, same as the surface of a [`TwoDLookUpTable`].
+    RowPerX,
+    /// The surface has a row for each of the y breakpoints, so that the value at `surface[j][i]`
+    /// corresponds to the breakpoints `x[i]` and `y[j]`, eg. when the rows are the curves for each of the
+    /// y breakpoints.
+    RowPerY,
+}
+
+impl SurfaceOrientation {
+    /// Returns the number of the rows, and the number of the values in each row, of a surface with `m`
+    /// x breakpoints and `n` y breakpoints.
+    fn shape(&self, m: usize, n: usize) -> (usize, usize) {
+        match self {
+            SurfaceOrientation::RowPerX => (m, n),
+            SurfaceOrientation::RowPerY => (n, m),
+        }
     }
 }
 
 #[derive(Debug)]
-pub struct TwoDLookUpTableRef<'a, 'b, 'c> {
-    xs: &'a [f64],
-    ys: &'b [f64],
-    surface: Vec<&'c [f64]>,
-    cache: RefCell<HashMap<Key, f64>>,
-    xy_swapped: bool,
+pub struct TwoDLookUpTableRef<'a, 'b, 'c, T = f64, C = LocalCache<Key, T>> {
+    xs: &'a [T],
+    ys: &'b [T],
+    surface: Vec<&'c [T]>,
+    interpolation: [AxisInterpolation; 2],
+    extrapolation: [Extrapolation<T>; 2],
+    cache: C,
+    orientation: SurfaceOrientation,
 }
 
-impl<'a, 'b, 'c> TwoDLookUpTableRef<'a, 'b, 'c> {
+impl<'a, 'b, 'c, T: Float> TwoDLookUpTableRef<'a, 'b, 'c, T> {
+    /// Constructs a `TwoDLookUpTableRef` object from a surface with owned or borrowed rows, laid out as
+    /// given by the `orientation`. The validation is the same as [`TwoDLookUpTableRef::new`].
     #[cfg(not(feature = "no-std"))]
     #[allow(clippy::ptr_arg)]
     pub fn from_cow(
-        xs: &'a [f64],
-        ys: &'b [f64],
-        surface: &'static Cow<'static, [Cow<'static, [f64]>]>,
+        xs: &'a [T],
+        ys: &'b [T],
+        surface: &'static Cow<'static, [Cow<'static, [T]>]>,
+        orientation: SurfaceOrientation,
     ) -> Result<Self, ConstructionError> {
-        //TODO: Unify the code for the functions from and new
+        let vec = surface.iter().map(|v| &v[..]).collect();
 
-        let mut vec = Vec::new();
-        surface.iter().for_each(|v| vec.push(&v[0..v.len()]));
+        Self::with_rows(xs, ys, vec, orientation, [AxisInterpolation::Linear; 2])
+    }
 
-        // Since we are dealing with dynamic slices, align the xs and ys if the lengths are not aligned
-        // according to the surface dimensions. If the lengths are same, then we assume that the xs and
-        // ys are passed in the correct order.
-        is_object_constructible(xs.iter(), ys.iter(), vec.clone().into_iter()).map(|_| TwoDLookUpTableRef {
+    /// Constructs a `TwoDLookUpTableRef` object, given the breakpoints `xs` and `ys`, and the `surface`
+    /// values laid out as given by the `orientation`.
+    /// ```
+    ///  use look_up_table::{SurfaceOrientation, TwoDLookUpTableRef};
+    ///  let (xs, ys) = ([1.0f64, 2.0, 3.0], [10.0, 20.0]);
+    ///  let rows: [&[f64]; 2] = [&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]];
+    ///  let lut = TwoDLookUpTableRef::new(&xs, &ys, &rows, SurfaceOrientation::RowPerY).unwrap();
+    ///  assert!((lut.get(&2.5, &15.0) - 4.0).abs() < 1e-12);
+    /// ```
+    ///
+    /// If the number of the rows, or the number of the values in a row don't match the breakpoints:
+    /// ```
+    ///  use look_up_table::{ConstructionError, SurfaceOrientation, TwoDLookUpTableRef};
+    ///  let (xs, ys) = ([1.0f64, 2.0, 3.0], [10.0, 20.0]);
+    ///  let rows: [&[f64]; 2] = [&[1.0, 2.0, 3.0], &[4.0, 5.0]];
+    ///  let lut = TwoDLookUpTableRef::new(&xs, &ys, &rows, SurfaceOrientation::RowPerY);
+    ///  assert!(matches!(
+    ///      lut.err().unwrap(),
+    ///      ConstructionError::RowLengthMismatch { row: 1, expected: 3, actual: 2 }
+    ///  ));
+    ///  let lut = TwoDLookUpTableRef::new(&xs, &ys, &rows, SurfaceOrientation::RowPerX);
+    ///  assert!(matches!(lut.err().unwrap(), ConstructionError::RowCountMismatch { expected: 3, actual: 2 }));
+    /// ```
+    ///
+    /// Otherwise, the same errors as [`TwoDLookUpTable::new`] are returned.
+    pub fn new(
+        xs: &'a [T],
+        ys: &'b [T],
+        surface: &'c [&'c [T]],
+        orientation: SurfaceOrientation,
+    ) -> Result<Self, ConstructionError> {
+        Self::with_interpolation(xs, ys, surface, orientation, [AxisInterpolation::Linear; 2])
+    }
+
+    /// Constructs a `TwoDLookUpTableRef` object, which uses the given `interpolation` schemes along the x
+    /// and y axes. The inputs are validated in the same way as [`TwoDLookUpTableRef::new`].
+    pub fn with_interpolation(
+        xs: &'a [T],
+        ys: &'b [T],
+        surface: &'c [&'c [T]],
+        orientation: SurfaceOrientation,
+        interpolation: [AxisInterpolation; 2],
+    ) -> Result<Self, ConstructionError> {
+        let vec = Self::rows(xs, ys, surface)?;
+
+        Self::with_rows(xs, ys, vec, orientation, interpolation)
+    }
+
+    fn with_rows(
+        xs: &'a [T],
+        ys: &'b [T],
+        rows: Vec<&'c [T]>,
+        orientation: SurfaceOrientation,
+        interpolation: [AxisInterpolation; 2],
+    ) -> Result<Self, ConstructionError> {
+        let (m, n) = orientation.shape(xs.len(), ys.len());
+        is_shape_valid(m, n, &rows)?;
+
+        is_object_constructible::<T, _, _, _>(xs.iter(), ys.iter(), rows.clone()).map(|_| TwoDLookUpTableRef {
+            xs,
+            ys,
+            surface: rows,
+            interpolation,
+            extrapolation: [Extrapolation::Clamp; 2],
+            cache: LocalCache::default(),
+            orientation,
+        })
+    }
+
+    /// Constructs a `TwoDLookUpTableRef` object, which takes its breakpoints from the shared axes
+    /// `x_axis` and `y_axis`. The breakpoints are not validated again, only the `surface` values and
+    /// its dimensions are.
+    /// The surface has a row for each of the x breakpoints, i.e. [`SurfaceOrientation::RowPerX`].
+    /// ```
+    ///  use look_up_table::{Axis, TwoDLookUpTableRef};
+    ///  let (speed, load) = (Axis::new(&[1000.0f64, 2000.0]).unwrap(), Axis::new(&[0.0, 0.5, 1.0]).unwrap());
+    ///  let map = TwoDLookUpTableRef::from_axes(&speed, &load, &[&[0.0, 1.0, 2.0], &[10.0, 11.0, 12.0]]).unwrap();
+    ///  assert!((map.get_at(&speed.search(1500.0), &load.search(0.75)) - 6.5).abs() < 1e-12);
+    /// ```
+    pub fn from_axes(
+        x_axis: &Axis<'a, T>,
+        y_axis: &Axis<'b, T>,
+        surface: &'c [&'c [T]],
+    ) -> Result<Self, ConstructionError> {
+        let (xs, ys) = (x_axis.breakpoints(), y_axis.breakpoints());
+        let vec = Self::rows(xs, ys, surface)?;
+
+        is_surface_valid(xs.len(), ys.len(), &vec).map(|_| TwoDLookUpTableRef {
             xs,
             ys,
             surface: vec,
-            cache: RefCell::new(HashMap::new()),
-            xy_swapped: xs.len() != ys.len() && xs.len() == surface.len(),
+            interpolation: [AxisInterpolation::Linear; 2],
+            extrapolation: [Extrapolation::Clamp; 2],
+            cache: LocalCache::default(),
+            orientation: SurfaceOrientation::RowPerX,
         })
     }
 
-    pub fn new(xs: &'a [f64], ys: &'b [f64], surface: &'c [&'c [f64]]) -> Result<Self, ConstructionError> {
+    /// Collects the rows of the surface, checking the lengths against the capacity in no-std builds.
+    #[allow(unused_variables)]
+    fn rows(xs: &[T], ys: &[T], surface: &'c [&'c [T]]) -> Result<Vec<&'c [T]>, ConstructionError> {
         #[cfg(feature = "no-std")]
         if xs.len() > MAX_FUNCTION_POINTS
             || ys.len() > MAX_FUNCTION_POINTS
@@ -170,43 +600,439 @@ impl<'a, 'b, 'c> TwoDLookUpTableRef<'a, 'b, 'c> {
         let mut vec = Vec::new();
         cfg_if! {
             if #[cfg(feature="no-std")] {
-                surface.iter().for_each(|v| vec.push(&v[0..v.len()]).unwrap());
+                for v in surface {
+                    vec.push(&v[0..v.len()]).map_err(|_| MaxLengthError)?;
+                }
             } else {
                 // #[cfg(feature = "std")]
                 surface.iter().for_each(|v| vec.push(&v[0..v.len()]));
             }
         }
 
-        // Since we are dealing with dynamic slices, align the xs and ys if the lengths are not aligned
-        // according to the surface dimensions. If the lengths are same, then we assume that the xs and
-        // ys are passed in the correct order.
-        is_object_constructible(xs.iter(), ys.iter(), vec.clone().into_iter()).map(|_| TwoDLookUpTableRef {
-            xs,
-            ys,
-            surface: vec,
-            cache: RefCell::new(HashMap::new()),
-            xy_swapped: xs.len() != ys.len() && xs.len() == surface.len(),
+        Ok(vec)
+    }
+}
+
+impl<'a, 'b, 'c, T: Float, C: Cache<Key, T>> TwoDLookUpTableRef<'a, 'b, 'c, T, C> {
+    /// Returns the table with a different [`Cache`], which starts out empty.
+    pub fn with_cache<D: Cache<Key, T> + Default>(self) -> TwoDLookUpTableRef<'a, 'b, 'c, T, D> {
+        TwoDLookUpTableRef {
+            xs: self.xs,
+            ys: self.ys,
+            surface: self.surface,
+            interpolation: self.interpolation,
+            extrapolation: self.extrapolation,
+            cache: D::default(),
+            orientation: self.orientation,
+        }
+    }
+
+    /// Returns the counts of the hits, misses and evictions of the cache of the table.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Sets the interpolation scheme between the grid points. The default scheme is
+    /// [`SurfaceInterpolation::Bilinear`].
+    pub fn set_interpolation(&mut self, interpolation: SurfaceInterpolation) {
+        self.interpolation = interpolation.per_axis();
+        self.cache.clear();
+    }
+
+    /// Sets the interpolation schemes along each of the axes, eg. to interpolate linearly along a
+    /// continuous x axis, and to step along a discrete y axis.
+    pub fn set_axis_interpolation(&mut self, x: AxisInterpolation, y: AxisInterpolation) {
+        self.interpolation = [x, y];
+        self.cache.clear();
+    }
+
+    /// Sets the policies for the lookups outside the range of the breakpoints, for each of the axes.
+    /// The default policy is [`Extrapolation::Clamp`] for both the axes.
+    pub fn set_extrapolation(&mut self, x: Extrapolation<T>, y: Extrapolation<T>) {
+        self.extrapolation = [x, y];
+        self.cache.clear();
+    }
+
+    pub fn get(&self, x: &T, y: &T) -> T {
+        self.try_get(x, y).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value for the given `x` and `y` indices, or an error if an index lies
+    /// outside the range of the breakpoints, and the extrapolation policy of its axis is
+    /// [`Extrapolation::Error`].
+    pub fn try_get(&self, x: &T, y: &T) -> Result<T, LookUpError> {
+        self.view().try_get(x, y)
+    }
+
+    /// Returns an interpolated value for the given `x` and `y` indices, along with where the indices lie
+    /// relative to the breakpoints of each of the axes.
+    pub fn get_checked(&self, x: &T, y: &T) -> Result<Checked<T, 2>, LookUpError> {
+        self.view().get_checked(x, y)
+    }
+
+    /// Returns an interpolated value for the indices at the given positions, reusing the searches done
+    /// on the shared [`Axis`] objects of the table. The lookup bypasses the cache, since it is already
+    /// cheap.
+    pub fn get_at(&self, x: &AxisPosition<T>, y: &AxisPosition<T>) -> T {
+        self.try_get_at(x, y).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value for the indices at the given positions similar to
+    /// [`TwoDLookUpTableRef::get_at`], or an error if an index lies outside the range of the breakpoints,
+    /// and the extrapolation policy of its axis is [`Extrapolation::Error`].
+    pub fn try_get_at(&self, x: &AxisPosition<T>, y: &AxisPosition<T>) -> Result<T, LookUpError> {
+        self.view().try_get_at(x, y)
+    }
+
+    /// Returns an interpolated value at the positions given by the prelookups on the [`Axis`] objects with
+    /// the same breakpoints as the x and y axes, which skips the search of the breakpoints.
+    pub fn interpolate_with(&self, x: &IndexFraction<T>, y: &IndexFraction<T>) -> T {
+        self.try_interpolate_with(x, y).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value at the positions given by the prelookups, or an error if a position
+    /// lies outside the range of the breakpoints, and the extrapolation policy of its axis is
+    /// [`Extrapolation::Error`].
+    pub fn try_interpolate_with(&self, x: &IndexFraction<T>, y: &IndexFraction<T>) -> Result<T, LookUpError> {
+        self.view().try_interpolate_with(x, y)
+    }
+
+    /// Returns the `y` index at which the table takes the value `z` for the given `x` index, or NaN if
+    /// there is no unique index. See [`TwoDLookUpTable::inverse_y`].
+    pub fn inverse_y(&self, x: &T, z: &T) -> T {
+        self.try_inverse_y(x, z).unwrap_or(T::nan())
+    }
+
+    /// Returns the `y` index at which the table takes the value `z` for the given `x` index, or an error if
+    /// the slice at `x` is not strictly monotonic, or doesn't enclose `z`. See
+    /// [`TwoDLookUpTable::try_inverse_y`].
+    pub fn try_inverse_y(&self, x: &T, z: &T) -> Result<T, LookUpError> {
+        self.view().try_inverse_y(x, z)
+    }
+
+    /// Returns all the `y` indices within the range of the breakpoints at which the table takes the value
+    /// `z` for the given `x` index, in increasing order. See [`TwoDLookUpTable::inverse_y_all`].
+    pub fn inverse_y_all(&self, x: &T, z: &T) -> Result<impl Iterator<Item = T> + '_, LookUpError> {
+        self.view().inverse_y_all(x, z)
+    }
+
+    /// Returns the partial derivatives of the table at the given `x` and `y` indices, with respect to each
+    /// of the indices, or NaNs if the lookup is an error. See [`TwoDLookUpTable::gradient`].
+    pub fn gradient(&self, x: &T, y: &T) -> [T; 2] {
+        self.try_gradient(x, y).unwrap_or([T::nan(); 2])
+    }
+
+    /// Returns the partial derivatives `[∂z/∂x, ∂z/∂y]` of the table at the given `x` and `y` indices, or an
+    /// error if an index lies outside the range of the breakpoints, and the extrapolation policy of its
+    /// axis is [`Extrapolation::Error`]. See [`TwoDLookUpTable::try_gradient`].
+    pub fn try_gradient(&self, x: &T, y: &T) -> Result<[T; 2], LookUpError> {
+        self.view().try_gradient(x, y)
+    }
+
+    /// Returns a [`DynTwoDLookUpTable`] with the fewest rows and columns this finds, whose lookups are
+    /// within `tolerance` of the lookups of this table, along with the largest difference, or an error if
+    /// the interpolation is not linear along both of the axes. See [`TwoDLookUpTable::compress`].
+    pub fn compress(&self, tolerance: T) -> Result<Compressed<DynTwoDLookUpTable<T>, T>, CompressionError> {
+        self.view().compress(tolerance)
+    }
+
+    fn view(&self) -> View<'_, T, C> {
+        View {
+            xs: self.xs,
+            ys: self.ys,
+            interpolation: &self.interpolation,
+            extrapolation: &self.extrapolation,
+            cache: &self.cache,
+            surface: self,
+        }
+    }
+}
+
+/// Bilinear (or any other supported [`AxisInterpolation`]) interpolation, which owns a copy of its
+/// breakpoints and surface like a [`TwoDLookUpTable`], but whose numbers of breakpoints are only known at
+/// runtime, eg. for the tables built by sampling a function adaptively. The value at `surface[i][j]`
+/// corresponds to the breakpoints `x[i]` and `y[j]`.
+/// ```
+///  use look_up_table::DynTwoDLookUpTable;
+///  let (xs, ys) = ([1.0f64, 2.0, 3.0], [10.0, 20.0]);
+///  let rows: [&[f64]; 3] = [&[1.0, 2.0], &[3.0, 4.0], &[5.0, 6.0]];
+///  let lut = DynTwoDLookUpTable::new(&xs, &ys, &rows).unwrap();
+///  assert!((lut.get(&2.5, &15.0) - 4.5).abs() < 1e-12);
+/// ```
+#[derive(Debug)]
+pub struct DynTwoDLookUpTable<T = f64, C = LocalCache<Key, T>> {
+    x: Vec<T>,                             // Breakpoints/sample points on x-axis
+    y: Vec<T>,                             // Breakpoints/sample points on y-axis
+    surface: Vec<T>,                       // Function values in row major order, with a row for each x.
+    interpolation: [AxisInterpolation; 2], // Interpolation schemes along x and y axes.
+    extrapolation: [Extrapolation<T>; 2],  // Extrapolation policies for x and y axes.
+    cache: C,                              // A cache to support fast lookup for frequently used values.
+}
+
+impl<T: Float> DynTwoDLookUpTable<T> {
+    /// Constructs a `DynTwoDLookUpTable` object, given the breakpoints `xs` and `ys`, and the `surface`
+    /// with a row for each of the x breakpoints. The breakpoints and the values are validated first, same
+    /// as [`TwoDLookUpTable::new`], and then the shape of the surface, same as [`TwoDLookUpTableRef::new`]
+    /// with [`SurfaceOrientation::RowPerX`]. In `no-std` builds, a surface with more than 1000 values is
+    /// reported as a `MaxLengthError`.
+    /// ```
+    ///  use look_up_table::{ConstructionError, DynTwoDLookUpTable};
+    ///  let rows: [&[f64]; 2] = [&[1.0, 2.0], &[3.0]];
+    ///  let lut = DynTwoDLookUpTable::new(&[1.0, 2.0], &[10.0, 20.0], &rows);
+    ///  assert!(matches!(
+    ///      lut.err().unwrap(),
+    ///      ConstructionError::RowLengthMismatch { row: 1, expected: 2, actual: 1 }
+    ///  ));
+    /// ```
+    pub fn new(xs: &[T], ys: &[T], surface: &[&[T]]) -> Result<DynTwoDLookUpTable<T>, ConstructionError> {
+        Self::with_interpolation(xs, ys, surface, [AxisInterpolation::Linear; 2])
+    }
+
+    /// Constructs a `DynTwoDLookUpTable` object, which uses the given `interpolation` schemes along the x
+    /// and y axes. The inputs are validated in the same way as [`DynTwoDLookUpTable::new`].
+    pub fn with_interpolation(
+        xs: &[T],
+        ys: &[T],
+        surface: &[&[T]],
+        interpolation: [AxisInterpolation; 2],
+    ) -> Result<DynTwoDLookUpTable<T>, ConstructionError> {
+        is_object_constructible::<T, _, _, _>(xs.iter(), ys.iter(), surface.iter().copied())?;
+        is_shape_valid(xs.len(), ys.len(), surface)?;
+
+        let mut values = Vec::new();
+        for row in surface {
+            row.iter().try_for_each(|&v| push(&mut values, v))?;
+        }
+
+        Ok(DynTwoDLookUpTable {
+            x: to_vec(xs)?,
+            y: to_vec(ys)?,
+            surface: values,
+            interpolation,
+            extrapolation: [Extrapolation::Clamp; 2],
+            cache: LocalCache::default(),
         })
     }
+}
+
+impl<T: Float, C: Cache<Key, T>> DynTwoDLookUpTable<T, C> {
+    /// Returns the table with a different [`Cache`], which starts out empty.
+    pub fn with_cache<D: Cache<Key, T> + Default>(self) -> DynTwoDLookUpTable<T, D> {
+        DynTwoDLookUpTable {
+            x: self.x,
+            y: self.y,
+            surface: self.surface,
+            interpolation: self.interpolation,
+            extrapolation: self.extrapolation,
+            cache: D::default(),
+        }
+    }
 
-    pub fn get(&self, x: &f64, y: &f64) -> f64 {
-        let (x, y) = if self.xy_swapped { (y, x) } else { (x, y) };
+    /// Returns the counts of the hits, misses and evictions of the cache of the table.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Returns the breakpoints of the x and y axes, eg. to inspect a table built by sampling a function.
+    pub fn axes(&self) -> [&[T]; 2] {
+        [&self.x, &self.y]
+    }
+
+    /// Sets the interpolation scheme between the grid points. The default scheme is
+    /// [`SurfaceInterpolation::Bilinear`].
+    pub fn set_interpolation(&mut self, interpolation: SurfaceInterpolation) {
+        self.interpolation = interpolation.per_axis();
+        self.cache.clear();
+    }
+
+    /// Sets the interpolation schemes along each of the axes, eg. to interpolate linearly along a
+    /// continuous x axis, and to step along a discrete y axis.
+    pub fn set_axis_interpolation(&mut self, x: AxisInterpolation, y: AxisInterpolation) {
+        self.interpolation = [x, y];
+        self.cache.clear();
+    }
+
+    /// Sets the policies for the lookups outside the range of the breakpoints, for each of the axes.
+    /// The default policy is [`Extrapolation::Clamp`] for both the axes.
+    pub fn set_extrapolation(&mut self, x: Extrapolation<T>, y: Extrapolation<T>) {
+        self.extrapolation = [x, y];
+        self.cache.clear();
+    }
+
+    /// Returns an interpolated value for the given `x` and `y` indices, same as [`TwoDLookUpTable::get`].
+    pub fn get(&self, x: &T, y: &T) -> T {
+        self.try_get(x, y).unwrap_or(T::nan())
+    }
+
+    /// Returns an interpolated value for the given `x` and `y` indices, or an error if an index lies
+    /// outside the range of the breakpoints, and the extrapolation policy of its axis is
+    /// [`Extrapolation::Error`].
+    pub fn try_get(&self, x: &T, y: &T) -> Result<T, LookUpError> {
+        self.view().try_get(x, y)
+    }
+
+    /// Returns an interpolated value for the given `x` and `y` indices, along with where the indices lie
+    /// relative to the breakpoints of each of the axes.
+    pub fn get_checked(&self, x: &T, y: &T) -> Result<Checked<T, 2>, LookUpError> {
+        self.view().get_checked(x, y)
+    }
+
+    /// Returns the `y` index at which the table takes the value `z` for the given `x` index, or NaN if
+    /// there is no unique index. See [`TwoDLookUpTable::inverse_y`].
+    pub fn inverse_y(&self, x: &T, z: &T) -> T {
+        self.try_inverse_y(x, z).unwrap_or(T::nan())
+    }
+
+    /// Returns the `y` index at which the table takes the value `z` for the given `x` index, or an error if
+    /// the slice at `x` is not strictly monotonic, or doesn't enclose `z`. See
+    /// [`TwoDLookUpTable::try_inverse_y`].
+    pub fn try_inverse_y(&self, x: &T, z: &T) -> Result<T, LookUpError> {
+        self.view().try_inverse_y(x, z)
+    }
+
+    /// Returns all the `y` indices within the range of the breakpoints at which the table takes the value
+    /// `z` for the given `x` index, in increasing order. See [`TwoDLookUpTable::inverse_y_all`].
+    pub fn inverse_y_all(&self, x: &T, z: &T) -> Result<impl Iterator<Item = T> + '_, LookUpError> {
+        self.view().inverse_y_all(x, z)
+    }
+
+    /// Returns the partial derivatives of the table at the given `x` and `y` indices, with respect to each
+    /// of the indices, or NaNs if the lookup is an error. See [`TwoDLookUpTable::gradient`].
+    pub fn gradient(&self, x: &T, y: &T) -> [T; 2] {
+        self.try_gradient(x, y).unwrap_or([T::nan(); 2])
+    }
+
+    /// Returns the partial derivatives `[∂z/∂x, ∂z/∂y]` of the table at the given `x` and `y` indices, or an
+    /// error if an index lies outside the range of the breakpoints, and the extrapolation policy of its
+    /// axis is [`Extrapolation::Error`]. See [`TwoDLookUpTable::try_gradient`].
+    pub fn try_gradient(&self, x: &T, y: &T) -> Result<[T; 2], LookUpError> {
+        self.view().try_gradient(x, y)
+    }
+
+    /// Returns a [`DynTwoDLookUpTable`] with the fewest rows and columns this finds, whose lookups are
+    /// within `tolerance` of the lookups of this table, along with the largest difference, or an error if
+    /// the interpolation is not linear along both of the axes. See [`TwoDLookUpTable::compress`].
+    pub fn compress(&self, tolerance: T) -> Result<Compressed<DynTwoDLookUpTable<T>, T>, CompressionError> {
+        self.view().compress(tolerance)
+    }
+
+    fn view(&self) -> View<'_, T, C> {
+        View {
+            xs: &self.x,
+            ys: &self.y,
+            interpolation: &self.interpolation,
+            extrapolation: &self.extrapolation,
+            cache: &self.cache,
+            surface: self,
+        }
+    }
+}
+
+impl<T: Float, C> SurfaceValueGetter<T> for DynTwoDLookUpTable<T, C> {
+    fn get(&self, x: usize, y: usize) -> T {
+        self.surface[x * self.y.len() + y]
+    }
+}
+
+/// The fields of a table which its lookups need, borrowed from an owned, a borrowed or a dynamic table, so
+/// that they share the bodies of their methods.
+struct View<'t, T, C> {
+    xs: &'t [T],
+    ys: &'t [T],
+    interpolation: &'t [AxisInterpolation; 2],
+    extrapolation: &'t [Extrapolation<T>; 2],
+    cache: &'t C,
+    surface: &'t dyn SurfaceValueGetter<T>,
+}
+
+impl<'t, T: Float, C: Cache<Key, T>> View<'t, T, C> {
+    fn try_get(&self, x: &T, y: &T) -> Result<T, LookUpError> {
         // First do the cache lookup
         let key = (x.integer_decode(), y.integer_decode());
 
-        if self.cache.borrow().contains_key(&key) {
-            return *self.cache.borrow().get(&key).unwrap();
+        if let Some(z) = self.cache.get(&key) {
+            return Ok(z);
         }
 
-        let z = if self.xy_swapped {
-            interpolate(x, y, self.ys, self.xs, self)
-        } else {
-            interpolate(x, y, self.xs, self.ys, self)
-        };
+        let z = interpolate(
+            [*x, *y],
+            [self.xs, self.ys],
+            self.interpolation,
+            self.extrapolation,
+            self.surface,
+            [None; 2],
+        )?;
 
         // store the value in cache before returning, to speedup look up process in the future.
-        self.cache.borrow_mut().insert(key, z);
+        self.cache.insert(key, z);
+
+        Ok(z)
+    }
+
+    fn get_checked(&self, x: &T, y: &T) -> Result<Checked<T, 2>, LookUpError> {
+        Ok(Checked {
+            value: self.try_get(x, y)?,
+            range: [AxisRange::of(*x, self.xs), AxisRange::of(*y, self.ys)],
+        })
+    }
+
+    fn try_get_at(&self, x: &AxisPosition<T>, y: &AxisPosition<T>) -> Result<T, LookUpError> {
+        interpolate(
+            [x.value(), y.value()],
+            [self.xs, self.ys],
+            self.interpolation,
+            self.extrapolation,
+            self.surface,
+            [Some(x), Some(y)],
+        )
+    }
+
+    fn try_interpolate_with(&self, x: &IndexFraction<T>, y: &IndexFraction<T>) -> Result<T, LookUpError> {
+        interpolate_with([x, y], [self.xs, self.ys], self.interpolation, self.extrapolation, self.surface)
+    }
+
+    fn try_inverse_y(&self, x: &T, z: &T) -> Result<T, LookUpError> {
+        self.column(x)?.invert(*z)
+    }
+
+    fn inverse_y_all(self, x: &T, z: &T) -> Result<impl Iterator<Item = T> + 't, LookUpError> {
+        Ok(self.column(x)?.solutions(*z))
+    }
+
+    fn try_gradient(&self, x: &T, y: &T) -> Result<[T; 2], LookUpError> {
+        differentiate(
+            [*x, *y],
+            [self.xs, self.ys],
+            self.interpolation,
+            self.extrapolation,
+            self.surface,
+        )
+    }
+
+    /// Compresses the surface of a bilinearly interpolated table into a dynamic table, with the same
+    /// extrapolation policies, as described in [`TwoDLookUpTable::compress`].
+    fn compress(&self, tolerance: T) -> Result<Compressed<DynTwoDLookUpTable<T>, T>, CompressionError> {
+        if *self.interpolation != [AxisInterpolation::Linear; 2] {
+            return Err(CompressionError::NonLinearInterpolation);
+        }
+
+        let extrapolation = *self.extrapolation;
+        let value = |i, j| self.surface.get(i, j);
+        let compressed = compress_surface([self.xs, self.ys], value, extrapolation, tolerance, |[x, y], values| {
+            let mut rows = Vec::new();
+            for row in values.chunks(y.len()) {
+                push(&mut rows, row)?;
+            }
+
+            let mut table = DynTwoDLookUpTable::new(x, y, &rows)?;
+            table.set_extrapolation(extrapolation[0], extrapolation[1]);
+            Ok(table)
+        })?;
+
+        Ok(compressed)
+    }
 
-        *self.cache.borrow().get(&key).unwrap()
+    fn column(&self, x: &T) -> Result<Column<'t, T>, LookUpError> {
+        Column::new(*x, [self.xs, self.ys], self.interpolation, self.extrapolation, self.surface)
     }
 }
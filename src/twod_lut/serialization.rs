@@ -0,0 +1,204 @@
+use crate::serialization::{invalid, Array, Values};
+use crate::twod_lut::Key;
+use crate::{
+    push, AxisInterpolation, Cache, ConstructionError, DynTwoDLookUpTable, Extrapolation, TwoDLookUpTable, Vec,
+};
+use core::fmt;
+use core::marker::PhantomData;
+use num::Float;
+use serde::de::{Deserialize, DeserializeSeed, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// The serialized form of a [`TwoDLookUpTable`], which is validated by the constructor before it becomes a
+/// table. The surface has a row for each of the x breakpoints. The interpolation and the extrapolation
+/// can be left out, to use the defaults of the table.
+#[derive(serde::Deserialize)]
+#[serde(rename = "TwoDLookUpTable", bound(deserialize = "T: Deserialize<'de>"))]
+struct Data<T, const M: usize, const N: usize> {
+    x: Array<T, M>,
+    y: Array<T, N>,
+    surface: Array<Array<T, N>, M>,
+    #[serde(default)]
+    interpolation: [AxisInterpolation; 2],
+    #[serde(default)]
+    extrapolation: [Extrapolation<T>; 2],
+}
+
+/// The serialized form of a [`DynTwoDLookUpTable`], which is the same as that of a [`TwoDLookUpTable`],
+/// except that the breakpoints can be of any number.
+#[derive(serde::Deserialize)]
+#[serde(rename = "DynTwoDLookUpTable", bound(deserialize = "T: Deserialize<'de>"))]
+struct DynData<T> {
+    x: Values<T>,
+    y: Values<T>,
+    surface: Surface<T>,
+    #[serde(default)]
+    interpolation: [AxisInterpolation; 2],
+    #[serde(default)]
+    extrapolation: [Extrapolation<T>; 2],
+}
+
+/// The rows of a surface, serialized as a sequence of sequences.
+struct Rows<'a, T, const N: usize>(&'a [[T; N]]);
+
+impl<T: Serialize, const N: usize> Serialize for Rows<'_, T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|row| &row[..]))
+    }
+}
+
+/// The values of a surface in row major order, serialized as a sequence of rows with `width` values each.
+struct DynRows<'a, T> {
+    values: &'a [T],
+    width: usize,
+}
+
+impl<T: Serialize> Serialize for DynRows<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.values.chunks(self.width))
+    }
+}
+
+/// The rows of a surface of any shape, deserialized into a single buffer along with the length of each of
+/// the rows, rather than into a buffer for each of the rows, which would be bounded separately in no-std
+/// builds.
+struct Surface<T> {
+    values: Vec<T>,
+    lengths: Vec<usize>,
+}
+
+impl<T> Surface<T> {
+    /// Returns the rows of the surface, in the order they were deserialized.
+    fn rows(&self) -> Result<Vec<&[T]>, ConstructionError> {
+        let (mut rows, mut start) = (Vec::new(), 0);
+        for &length in &self.lengths {
+            push(&mut rows, &self.values[start..start + length])?;
+            start += length;
+        }
+
+        Ok(rows)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Surface<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SurfaceVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for SurfaceVisitor<T> {
+            type Value = Surface<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a sequence of rows of values")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut surface = Surface {
+                    values: Vec::new(),
+                    lengths: Vec::new(),
+                };
+                while let Some(length) = seq.next_element_seed(Row(&mut surface.values))? {
+                    push(&mut surface.lengths, length).map_err(invalid)?;
+                }
+
+                Ok(surface)
+            }
+        }
+
+        deserializer.deserialize_seq(SurfaceVisitor(PhantomData))
+    }
+}
+
+/// A row of a surface, whose values are appended to the buffer of the surface. The length of the row is
+/// returned.
+struct Row<'a, T>(&'a mut Vec<T>);
+
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for Row<'_, T> {
+    type Value = usize;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for Row<'_, T> {
+    type Value = usize;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a sequence of values")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut length = 0;
+        while let Some(value) = seq.next_element()? {
+            push(self.0, value).map_err(invalid)?;
+            length += 1;
+        }
+
+        Ok(length)
+    }
+}
+
+impl<const M: usize, const N: usize, T: Float + Serialize, C> Serialize for TwoDLookUpTable<M, N, T, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut table = serializer.serialize_struct("TwoDLookUpTable", 5)?;
+        table.serialize_field("x", &self.x[..])?;
+        table.serialize_field("y", &self.y[..])?;
+        table.serialize_field("surface", &Rows(&self.surface))?;
+        table.serialize_field("interpolation", &self.interpolation)?;
+        table.serialize_field("extrapolation", &self.extrapolation)?;
+        table.end()
+    }
+}
+
+impl<'de, const M: usize, const N: usize, T, C> Deserialize<'de> for TwoDLookUpTable<M, N, T, C>
+where
+    T: Float + Deserialize<'de>,
+    C: Cache<Key, T> + Default,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = Data::<T, M, N>::deserialize(deserializer)?;
+
+        let mut lut = TwoDLookUpTable::new(data.x.0, data.y.0, data.surface.0.map(|row| row.0)).map_err(invalid)?;
+        let ([x_method, y_method], [x, y]) = (data.interpolation, data.extrapolation);
+        lut.set_axis_interpolation(x_method, y_method);
+        lut.set_extrapolation(x, y);
+
+        Ok(lut.with_cache())
+    }
+}
+
+impl<T: Float + Serialize, C> Serialize for DynTwoDLookUpTable<T, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut table = serializer.serialize_struct("DynTwoDLookUpTable", 5)?;
+        table.serialize_field("x", &self.x[..])?;
+        table.serialize_field("y", &self.y[..])?;
+        table.serialize_field(
+            "surface",
+            &DynRows {
+                values: &self.surface,
+                width: self.y.len(),
+            },
+        )?;
+        table.serialize_field("interpolation", &self.interpolation)?;
+        table.serialize_field("extrapolation", &self.extrapolation)?;
+        table.end()
+    }
+}
+
+impl<'de, T, C> Deserialize<'de> for DynTwoDLookUpTable<T, C>
+where
+    T: Float + Deserialize<'de>,
+    C: Cache<Key, T> + Default,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = DynData::<T>::deserialize(deserializer)?;
+
+        let rows = data.surface.rows().map_err(invalid)?;
+        let mut lut =
+            DynTwoDLookUpTable::with_interpolation(&data.x.0, &data.y.0, &rows, data.interpolation).map_err(invalid)?;
+        let [x, y] = data.extrapolation;
+        lut.set_extrapolation(x, y);
+
+        Ok(lut.with_cache())
+    }
+}
@@ -0,0 +1,239 @@
+use look_up_table::{
+    Axis, ConstructionError, Extrapolation, Interpolation, LookUpError, OneDLookUpTable, OneDLookUpTableRef,
+    TwoDLookUpTable, TwoDLookUpTableRef,
+};
+use rstest::rstest;
+
+const SPEED: [f64; 5] = [800.0, 1500.0, 2500.0, 4000.0, 6000.0];
+const TORQUE: [f64; 5] = [90.0, 140.0, 175.0, 160.0, 120.0];
+const FUEL: [f64; 5] = [0.8, 1.6, 2.9, 4.1, 6.5];
+const LOAD: [f64; 3] = [0.0, 0.5, 1.0];
+const MAP: [[f64; 3]; 5] = [
+    [1.0, 2.0, 4.0],
+    [2.0, 3.5, 6.0],
+    [3.0, 5.0, 8.5],
+    [4.0, 7.0, 11.0],
+    [5.0, 9.0, 14.0],
+];
+
+#[rstest]
+#[case(800.0)]
+#[case(1000.0)]
+#[case(2500.0)]
+#[case(3999.9)]
+#[case(100.0)]
+#[case(9000.0)]
+fn when_position_is_shared_then_match_individual_lookups(
+    #[case] x: f64,
+    #[values(Interpolation::Linear, Interpolation::Pchip, Interpolation::Next)] method: Interpolation,
+) {
+    let speed = Axis::new(&SPEED).unwrap();
+    let torque = OneDLookUpTableRef::from_axis(&speed, &TORQUE, method).unwrap();
+    let fuel = OneDLookUpTableRef::from_axis(&speed, &FUEL, method).unwrap();
+
+    let position = speed.search(x);
+
+    assert_eq!(torque.get_at(&position), torque.get(&x));
+    assert_eq!(fuel.get_at(&position), fuel.get(&x));
+}
+
+#[rstest]
+#[case(1000.0, 0.25)]
+#[case(2500.0, 0.5)]
+#[case(5000.0, 0.8)]
+#[case(-1.0, 2.0)]
+fn when_axes_are_shared_then_two_d_lookups_match(#[case] x: f64, #[case] y: f64) {
+    let (speed, load) = (Axis::new(&SPEED).unwrap(), Axis::new(&LOAD).unwrap());
+    let rows: Vec<&[f64]> = MAP.iter().map(|row| &row[..]).collect();
+    let map = TwoDLookUpTableRef::from_axes(&speed, &load, &rows).unwrap();
+    let torque = OneDLookUpTableRef::from_axis(&speed, &TORQUE, Interpolation::Linear).unwrap();
+
+    let (px, py) = (speed.search(x), load.search(y));
+
+    assert_eq!(map.get_at(&px, &py), map.get(&x, &y));
+    assert_eq!(torque.get_at(&px), torque.get(&x));
+}
+
+#[test]
+fn when_position_is_from_another_axis_then_search_again() {
+    let speed = Axis::new(&SPEED).unwrap();
+    let coarse = Axis::new(&[0.0, 10000.0]).unwrap();
+    let torque = OneDLookUpTableRef::from_axis(&speed, &TORQUE, Interpolation::Linear).unwrap();
+
+    let position = coarse.search(2000.0);
+
+    assert!((torque.get_at(&position) - 157.5).abs() < 0.000001);
+}
+
+#[test]
+fn when_extrapolation_is_set_then_apply_it_to_positions() {
+    let speed = Axis::new(&SPEED).unwrap();
+    let mut torque = OneDLookUpTableRef::from_axis(&speed, &TORQUE, Interpolation::Linear).unwrap();
+    torque.set_extrapolation(Extrapolation::Error);
+
+    assert_eq!(
+        torque.try_get_at(&speed.search(7000.0)),
+        Err(LookUpError::OutOfRange { axis: 0 })
+    );
+}
+
+#[test]
+fn when_breakpoints_are_invalid_dont_construct_axis() {
+    assert!(matches!(
+        Axis::new(&[1.0, 1.0, 2.0]).unwrap_err(),
+        ConstructionError::IncreasingDimOrderError
+    ));
+    assert!(matches!(Axis::new(&[1.0]).unwrap_err(), ConstructionError::MinLengthError));
+    assert!(matches!(
+        Axis::new(&[1.0, f64::INFINITY]).unwrap_err(),
+        ConstructionError::ContainingNansOrInfinities
+    ));
+}
+
+#[test]
+fn when_values_do_not_fit_axes_dont_construct_object() {
+    let (speed, load) = (Axis::new(&SPEED).unwrap(), Axis::new(&LOAD).unwrap());
+
+    let res = OneDLookUpTableRef::from_axis(&speed, &[1.0, f64::NAN], Interpolation::Linear);
+    assert!(matches!(
+        res.unwrap_err(),
+        ConstructionError::DimensionMismatch { expected: 5, actual: 2 }
+    ));
+
+    let res = OneDLookUpTableRef::from_axis(&speed, &[1.0, 2.0, f64::NAN, 4.0, 5.0], Interpolation::Linear);
+    assert!(matches!(res.unwrap_err(), ConstructionError::ContainingNansOrInfinities));
+
+    let rows: Vec<&[f64]> = MAP.iter().take(4).map(|row| &row[..]).collect();
+    let res = TwoDLookUpTableRef::from_axes(&speed, &load, &rows);
+    assert!(matches!(
+        res.unwrap_err(),
+        ConstructionError::RowCountMismatch { expected: 5, actual: 4 }
+    ));
+
+    let rows: Vec<&[f64]> = MAP.iter().map(|row| &row[..2]).collect();
+    let res = TwoDLookUpTableRef::from_axes(&speed, &load, &rows);
+    assert!(matches!(
+        res.unwrap_err(),
+        ConstructionError::RowLengthMismatch {
+            row: 0,
+            expected: 3,
+            actual: 2
+        }
+    ));
+}
+
+#[rstest]
+#[case(800.0, 0, 0.0)]
+#[case(1150.0, 0, 0.5)]
+#[case(2500.0, 2, 0.0)]
+#[case(6000.0, 3, 1.0)]
+#[case(400.0, 0, -4.0 / 7.0)]
+#[case(7000.0, 3, 1.5)]
+fn when_prelookup_is_done_then_return_index_and_fraction(#[case] x: f64, #[case] index: usize, #[case] fraction: f64) {
+    let at = Axis::new(&SPEED).unwrap().prelookup(x);
+
+    assert_eq!(at.index, index);
+    assert!((at.fraction - fraction).abs() < 0.000001);
+}
+
+#[rstest]
+#[case(800.0)]
+#[case(1234.5)]
+#[case(4000.0)]
+#[case(6000.0)]
+#[case(-300.0)]
+#[case(8500.0)]
+fn when_interpolating_with_prelookup_then_match_get(
+    #[case] x: f64,
+    #[values(Interpolation::Linear, Interpolation::Makima, Interpolation::Previous)] method: Interpolation,
+    #[values(Extrapolation::Clamp, Extrapolation::Linear, Extrapolation::Periodic)] extrapolation: Extrapolation,
+) {
+    let speed = Axis::new(&SPEED).unwrap();
+    let mut owned = OneDLookUpTable::with_interpolation(SPEED, TORQUE, method).unwrap();
+    let mut borrowed = OneDLookUpTableRef::with_interpolation(&SPEED, &FUEL, method).unwrap();
+    owned.set_extrapolation(extrapolation);
+    borrowed.set_extrapolation(extrapolation);
+
+    let at = speed.prelookup(x);
+
+    assert!((owned.interpolate_with(&at) - owned.get(&x)).abs() < 0.000001);
+    assert!((borrowed.interpolate_with(&at) - borrowed.get(&x)).abs() < 0.000001);
+}
+
+#[rstest]
+#[case(1000.0, 0.25)]
+#[case(2500.0, 0.5)]
+#[case(6000.0, 1.0)]
+#[case(5000.0, -0.4)]
+#[case(-1.0, 2.0)]
+fn when_interpolating_two_d_with_prelookups_then_match_get(
+    #[case] x: f64,
+    #[case] y: f64,
+    #[values(Extrapolation::Clamp, Extrapolation::Linear, Extrapolation::Constant(-1.0))] extrapolation: Extrapolation,
+) {
+    let (speed, load) = (Axis::new(&SPEED).unwrap(), Axis::new(&LOAD).unwrap());
+    let rows: Vec<&[f64]> = MAP.iter().map(|row| &row[..]).collect();
+    let mut borrowed = TwoDLookUpTableRef::from_axes(&speed, &load, &rows).unwrap();
+    let mut owned = TwoDLookUpTable::new(SPEED, LOAD, MAP).unwrap();
+    borrowed.set_extrapolation(extrapolation, Extrapolation::Clamp);
+    owned.set_extrapolation(Extrapolation::Clamp, extrapolation);
+
+    let (px, py) = (speed.prelookup(x), load.prelookup(y));
+
+    assert!((borrowed.interpolate_with(&px, &py) - borrowed.get(&x, &y)).abs() < 0.000001);
+    assert!((owned.interpolate_with(&px, &py) - owned.get(&x, &y)).abs() < 0.000001);
+}
+
+#[test]
+fn when_prelookup_is_out_of_range_then_report_error() {
+    let speed = Axis::new(&SPEED).unwrap();
+    let mut torque = OneDLookUpTable::new(SPEED, TORQUE).unwrap();
+    torque.set_extrapolation(Extrapolation::Error);
+
+    assert_eq!(
+        torque.try_interpolate_with(&speed.prelookup(100.0)),
+        Err(LookUpError::OutOfRange { axis: 0 })
+    );
+}
+
+#[rstest]
+#[case(1000.0, 0.25)]
+#[case(2500.0, 1.0)]
+#[case(9000.0, -0.5)]
+fn when_owned_tables_are_built_from_axes_then_match_ref_tables(#[case] x: f64, #[case] y: f64) {
+    let (speed, load) = (Axis::new(&SPEED).unwrap(), Axis::new(&LOAD).unwrap());
+    let rows: Vec<&[f64]> = MAP.iter().map(|row| &row[..]).collect();
+    let owned_map = TwoDLookUpTable::from_axes(&speed, &load, MAP).unwrap();
+    let ref_map = TwoDLookUpTableRef::from_axes(&speed, &load, &rows).unwrap();
+    let owned_torque = OneDLookUpTable::from_axis(&speed, TORQUE, Interpolation::Pchip).unwrap();
+    let ref_torque = OneDLookUpTableRef::from_axis(&speed, &TORQUE, Interpolation::Pchip).unwrap();
+
+    let (px, py) = (speed.search(x), load.search(y));
+
+    assert_eq!(owned_map.get_at(&px, &py), ref_map.get_at(&px, &py));
+    assert_eq!(owned_map.get_at(&px, &py), owned_map.get(&x, &y));
+    assert_eq!(owned_torque.get_at(&px), ref_torque.get_at(&px));
+    assert_eq!(owned_torque.get_at(&px), owned_torque.get(&x));
+}
+
+#[test]
+fn when_values_do_not_fit_axes_dont_construct_owned_object() {
+    let (speed, load) = (Axis::new(&SPEED).unwrap(), Axis::new(&LOAD).unwrap());
+
+    let res = OneDLookUpTable::from_axis(&speed, [1.0, 2.0, 3.0], Interpolation::Linear);
+    assert!(matches!(
+        res.unwrap_err(),
+        ConstructionError::DimensionMismatch { expected: 5, actual: 3 }
+    ));
+
+    let res = TwoDLookUpTable::from_axes(&load, &speed, MAP);
+    assert!(matches!(
+        res.unwrap_err(),
+        ConstructionError::RowCountMismatch { expected: 3, actual: 5 }
+    ));
+
+    let mut map = MAP;
+    map[2][1] = f64::NAN;
+    let res = TwoDLookUpTable::from_axes(&speed, &load, map);
+    assert!(matches!(res.unwrap_err(), ConstructionError::ContainingNansOrInfinities));
+}
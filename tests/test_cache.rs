@@ -0,0 +1,218 @@
+use look_up_table::{
+    CacheStats, DirectMappedCache, Extrapolation, Interpolation, LruCache, NDLookUpTable, NoCache, OneDLookUpTable,
+    OneDLookUpTableRef, TwoDLookUpTable,
+};
+use rstest::rstest;
+
+#[cfg(not(feature = "no-std"))]
+use look_up_table::{ShardedCache, SurfaceOrientation, TwoDLookUpTableRef};
+#[cfg(not(feature = "no-std"))]
+use std::{sync::Arc, thread};
+
+const SPEED: [f64; 5] = [800.0, 1500.0, 2500.0, 4000.0, 6000.0];
+const TORQUE: [f64; 5] = [90.0, 140.0, 175.0, 160.0, 120.0];
+const LOAD: [f64; 3] = [0.0, 0.5, 1.0];
+const MAP: [[f64; 3]; 5] = [
+    [1.0, 2.0, 4.0],
+    [2.0, 3.5, 6.0],
+    [3.0, 5.0, 8.5],
+    [4.0, 7.0, 11.0],
+    [5.0, 9.0, 14.0],
+];
+
+#[cfg(not(feature = "no-std"))]
+fn assert_sync<S: Sync>(_: &S) {}
+
+#[cfg(not(feature = "no-std"))]
+#[rstest]
+#[case(800.0)]
+#[case(1234.5)]
+#[case(4000.0)]
+#[case(-300.0)]
+#[case(8500.0)]
+#[case(f64::NAN)]
+fn when_cache_is_changed_then_one_d_lookups_are_identical(
+    #[case] x: f64,
+    #[values(Interpolation::Linear, Interpolation::Pchip, Interpolation::Nearest)] method: Interpolation,
+) {
+    let local = OneDLookUpTable::with_interpolation(SPEED, TORQUE, method).unwrap();
+    let uncached: OneDLookUpTable<5, f64, NoCache> = OneDLookUpTable::with_interpolation(SPEED, TORQUE, method)
+        .unwrap()
+        .with_cache();
+    let sharded: OneDLookUpTableRef<'_, '_, f64, ShardedCache<_, _>> =
+        OneDLookUpTableRef::with_interpolation(&SPEED, &TORQUE, method)
+            .unwrap()
+            .with_cache();
+
+    for _ in 0..2 {
+        assert_eq!(uncached.get(&x).to_bits(), local.get(&x).to_bits());
+        assert_eq!(sharded.get(&x).to_bits(), local.get(&x).to_bits());
+    }
+}
+
+#[cfg(not(feature = "no-std"))]
+#[rstest]
+#[case(1000.0, 0.25)]
+#[case(2500.0, 0.5)]
+#[case(6000.0, 1.0)]
+#[case(-1.0, 2.0)]
+fn when_cache_is_changed_then_two_d_lookups_are_identical(#[case] x: f64, #[case] y: f64) {
+    let rows: Vec<&[f64]> = MAP.iter().map(|row| &row[..]).collect();
+    let mut local = TwoDLookUpTable::new(SPEED, LOAD, MAP).unwrap();
+    let mut uncached: TwoDLookUpTable<5, 3, f64, NoCache> =
+        TwoDLookUpTable::new(SPEED, LOAD, MAP).unwrap().with_cache();
+    let mut sharded: TwoDLookUpTableRef<'_, '_, '_, f64, ShardedCache<_, _>> =
+        TwoDLookUpTableRef::new(&SPEED, &LOAD, &rows, SurfaceOrientation::RowPerX)
+            .unwrap()
+            .with_cache();
+    local.set_extrapolation(Extrapolation::Linear, Extrapolation::Clamp);
+    uncached.set_extrapolation(Extrapolation::Linear, Extrapolation::Clamp);
+    sharded.set_extrapolation(Extrapolation::Linear, Extrapolation::Clamp);
+
+    for _ in 0..2 {
+        assert_eq!(uncached.get(&x, &y), local.get(&x, &y));
+        assert_eq!(sharded.get(&x, &y), local.get(&x, &y));
+    }
+}
+
+#[cfg(not(feature = "no-std"))]
+#[test]
+fn when_extrapolation_is_changed_then_clear_sharded_cache() {
+    let mut lut: OneDLookUpTable<5, f64, ShardedCache<_, _>> =
+        OneDLookUpTable::new(SPEED, TORQUE).unwrap().with_cache();
+
+    assert_eq!(lut.get(&7000.0), 120.0);
+    lut.set_extrapolation(Extrapolation::Constant(0.0));
+    assert_eq!(lut.get(&7000.0), 0.0);
+}
+
+#[cfg(not(feature = "no-std"))]
+#[test]
+fn when_table_is_shared_across_threads_then_lookups_match_get() {
+    let local = NDLookUpTable::new([&SPEED, &LOAD], MAP.as_flattened()).unwrap();
+    let sharded: Arc<NDLookUpTable<2, f64, ShardedCache<_, _>>> = Arc::new(
+        NDLookUpTable::new([&SPEED, &LOAD], MAP.as_flattened())
+            .unwrap()
+            .with_cache(),
+    );
+    let uncached: Arc<NDLookUpTable<2, f64, NoCache>> = Arc::new(
+        NDLookUpTable::new([&SPEED, &LOAD], MAP.as_flattened())
+            .unwrap()
+            .with_cache(),
+    );
+    assert_sync(&sharded);
+    assert_sync(&uncached);
+
+    let workers: Vec<_> = (0..8)
+        .map(|i| {
+            let (sharded, uncached) = (Arc::clone(&sharded), Arc::clone(&uncached));
+            thread::spawn(move || {
+                (0..100)
+                    .map(|j| {
+                        let index = [500.0 + 60.0 * (j % 20) as f64, 0.1 * (i as f64) + 0.01 * j as f64];
+                        (index, sharded.get(&index), uncached.get(&index))
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    for worker in workers {
+        for (index, sharded, uncached) in worker.join().unwrap() {
+            assert_eq!(sharded, local.get(&index));
+            assert_eq!(uncached, local.get(&index));
+        }
+    }
+}
+
+#[rstest]
+#[case(800.0)]
+#[case(1234.5)]
+#[case(4000.0)]
+#[case(-300.0)]
+#[case(8500.0)]
+fn when_cache_is_bounded_then_lookups_are_identical(
+    #[case] x: f64,
+    #[values(Interpolation::Linear, Interpolation::Akima, Interpolation::Previous)] method: Interpolation,
+) {
+    let local = OneDLookUpTable::with_interpolation(SPEED, TORQUE, method).unwrap();
+    let lru: OneDLookUpTable<5, f64, LruCache<_, _, 2>> = OneDLookUpTable::with_interpolation(SPEED, TORQUE, method)
+        .unwrap()
+        .with_cache();
+    let direct: OneDLookUpTableRef<'_, '_, f64, DirectMappedCache<_, _, 3>> =
+        OneDLookUpTableRef::with_interpolation(&SPEED, &TORQUE, method)
+            .unwrap()
+            .with_cache();
+
+    // Interleave other lookups, so that the bounded caches evict the value in between.
+    for other in [x, 1000.0, 2000.0, 3000.0, x] {
+        assert_eq!(lru.get(&other).to_bits(), local.get(&other).to_bits());
+        assert_eq!(direct.get(&other).to_bits(), local.get(&other).to_bits());
+    }
+}
+
+#[test]
+fn when_lru_cache_is_full_then_evict_least_recently_used() {
+    let lut: OneDLookUpTable<5, f64, LruCache<_, _, 2>> = OneDLookUpTable::new(SPEED, TORQUE).unwrap().with_cache();
+
+    lut.get(&1000.0); // miss
+    lut.get(&2000.0); // miss
+    lut.get(&1000.0); // hit, so 2000 is now the least recently used
+    lut.get(&3000.0); // miss, evicts 2000
+    lut.get(&1000.0); // hit
+    lut.get(&2000.0); // miss, evicts 3000
+
+    assert_eq!(
+        lut.cache_stats(),
+        CacheStats {
+            hits: 2,
+            misses: 4,
+            evictions: 2
+        }
+    );
+}
+
+#[test]
+fn when_cache_is_cleared_then_keep_stats() {
+    let mut lut = TwoDLookUpTable::new(SPEED, LOAD, MAP).unwrap();
+
+    lut.get(&1000.0, &0.25);
+    lut.get(&1000.0, &0.25);
+    lut.set_extrapolation(Extrapolation::Linear, Extrapolation::Linear);
+    lut.get(&1000.0, &0.25);
+
+    assert_eq!(
+        lut.cache_stats(),
+        CacheStats {
+            hits: 1,
+            misses: 2,
+            evictions: 0
+        }
+    );
+}
+
+#[test]
+fn when_direct_mapped_cache_is_used_then_stay_within_capacity() {
+    let lut: NDLookUpTable<2, f64, DirectMappedCache<_, _, 8>> =
+        NDLookUpTable::new([&SPEED, &LOAD], MAP.as_flattened())
+            .unwrap()
+            .with_cache();
+
+    for i in 0..100 {
+        lut.get(&[800.0 + 50.0 * i as f64, 0.5]);
+    }
+    let stats = lut.cache_stats();
+
+    assert_eq!((stats.hits, stats.misses), (0, 100));
+    assert!(stats.evictions >= 100 - 8);
+}
+
+#[test]
+fn when_cache_is_disabled_then_report_no_lookups() {
+    let lut: OneDLookUpTable<5, f64, NoCache> = OneDLookUpTable::new(SPEED, TORQUE).unwrap().with_cache();
+
+    lut.get(&1000.0);
+    lut.get(&1000.0);
+
+    assert_eq!(lut.cache_stats(), CacheStats::default());
+}
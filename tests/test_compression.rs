@@ -0,0 +1,238 @@
+use look_up_table::{
+    CompressionError, ConstructionError, Extrapolation, Interpolation, NDLookUpTable, OneDLookUpTable,
+    OneDLookUpTableRef, SplineBoundary, SurfaceInterpolation, SurfaceOrientation, TwoDLookUpTable, TwoDLookUpTableRef,
+};
+use rstest::rstest;
+
+fn curve(n: usize) -> NDLookUpTable<1> {
+    let xs: Vec<f64> = (0..n).map(|i| i as f64 * 6.0 / (n - 1) as f64).collect();
+    let ys: Vec<f64> = xs.iter().map(|x| 100.0 * (x * 0.8).tanh() + x.sin()).collect();
+    NDLookUpTable::new([&xs], &ys).unwrap()
+}
+
+fn surface(m: usize, n: usize) -> NDLookUpTable<2> {
+    let xs: Vec<f64> = (0..m).map(|i| 800.0 + i as f64 * 5200.0 / (m - 1) as f64).collect();
+    let ys: Vec<f64> = (0..n).map(|j| j as f64 / (n - 1) as f64).collect();
+    let values: Vec<f64> = xs
+        .iter()
+        .flat_map(|x| {
+            ys.iter()
+                .map(move |y| 200.0 * y * y * (1.0 - ((x - 3500.0) / 3000.0).powi(2)))
+        })
+        .collect();
+    NDLookUpTable::new([&xs, &ys], &values).unwrap()
+}
+
+#[rstest]
+fn when_curve_is_compressed_then_lookups_are_within_tolerance(#[values(1e-2, 0.1, 0.5, 5.0)] tolerance: f64) {
+    let lut = curve(600);
+    let compressed = lut.compress(tolerance).unwrap();
+
+    let xs = lut.axes()[0];
+    let measured = xs
+        .iter()
+        .map(|x| (compressed.table.get(&[*x]) - lut.get(&[*x])).abs())
+        .fold(0.0, f64::max);
+    assert!(compressed.error <= tolerance);
+    assert!((compressed.error - measured).abs() < 1e-9, "{} {measured}", compressed.error);
+    assert!(compressed.table.axes()[0].len() < xs.len() / 5);
+    assert_eq!(compressed.table.axes()[0][0], xs[0]);
+    assert_eq!(compressed.table.axes()[0].last(), xs.last());
+    for k in 0..=3000 {
+        let x = k as f64 / 500.0;
+        assert!((compressed.table.get(&[x]) - lut.get(&[x])).abs() <= compressed.error + 1e-9);
+    }
+}
+
+#[test]
+fn when_curve_is_a_line_then_keep_only_its_ends() {
+    let lut = NDLookUpTable::new([&[0.0, 1.0, 2.0, 4.0]], &[1.0, 3.0, 5.0, 9.0]).unwrap();
+
+    let compressed = lut.compress(0.0).unwrap();
+
+    assert_eq!(compressed.table.axes(), [&[0.0, 4.0][..]]);
+    assert_eq!(compressed.error, 0.0);
+    assert_eq!(compressed.table.get(&[5.0]), 9.0);
+}
+
+#[test]
+fn when_extrapolation_is_linear_then_keep_the_end_segments() {
+    let mut lut = NDLookUpTable::<1>::new([&[0.0, 1.0, 2.0, 3.0, 4.0]], &[0.0, 0.2, 0.0, 0.2, 0.0]).unwrap();
+    lut.set_extrapolation([Extrapolation::Linear]);
+
+    let compressed = lut.compress(0.3).unwrap();
+
+    assert_eq!(compressed.table.axes(), [&[0.0, 1.0, 3.0, 4.0][..]]);
+    assert!((compressed.error - 0.2).abs() < 1e-12);
+    for x in [-5.0, -0.5, 4.5, 10.0] {
+        assert!((compressed.table.get(&[x]) - lut.get(&[x])).abs() < 1e-12, "{x}");
+    }
+}
+
+#[test]
+fn when_extrapolation_is_linear_along_an_axis_then_keep_its_end_segments() {
+    let zs = [0.0, 0.2, 0.0, 0.2, 0.0];
+    let values: Vec<f64> = zs.iter().flat_map(|z| [*z, 2.0 * z]).collect();
+    let mut lut = NDLookUpTable::new([&[0.0, 1.0, 2.0, 3.0, 4.0], &[0.0, 1.0]], &values).unwrap();
+    lut.set_extrapolation([Extrapolation::Linear, Extrapolation::Clamp]);
+
+    let compressed = lut.compress(0.5).unwrap();
+
+    assert_eq!(compressed.table.axes()[0], [0.0, 1.0, 3.0, 4.0]);
+    for index in [[-2.0, 0.5], [6.0, 0.25], [10.0, 1.0]] {
+        assert!((compressed.table.get(&index) - lut.get(&index)).abs() < 1e-12, "{index:?}");
+    }
+}
+
+#[test]
+fn when_tolerance_is_zero_then_keep_every_corner() {
+    let lut = NDLookUpTable::new([&[0.0, 1.0, 2.0, 3.0, 4.0]], &[0.0, 1.0, 2.0, 0.0, -2.0]).unwrap();
+
+    let compressed = lut.compress(0.0).unwrap();
+
+    assert_eq!(compressed.table.axes(), [&[0.0, 2.0, 4.0][..]]);
+}
+
+#[rstest]
+fn when_surface_is_compressed_then_lookups_are_within_tolerance(#[values(0.5, 2.0, 10.0)] tolerance: f64) {
+    let lut = surface(40, 24);
+    let compressed = lut.compress(tolerance).unwrap();
+
+    let [xs, ys] = lut.axes();
+    let measured = xs
+        .iter()
+        .flat_map(|x| ys.iter().map(move |y| [*x, *y]))
+        .map(|index| (compressed.table.get(&index) - lut.get(&index)).abs())
+        .fold(0.0, f64::max);
+    let [cx, cy] = compressed.table.axes();
+    assert!(compressed.error <= tolerance);
+    assert!((compressed.error - measured).abs() < 1e-9, "{} {measured}", compressed.error);
+    assert!(cx.len() < xs.len() && cy.len() < ys.len(), "{} {}", cx.len(), cy.len());
+    for i in 0..=100 {
+        for j in 0..=100 {
+            let index = [800.0 + i as f64 * 52.0, j as f64 / 100.0];
+            assert!((compressed.table.get(&index) - lut.get(&index)).abs() <= compressed.error + 1e-9);
+        }
+    }
+}
+
+#[test]
+fn when_surface_is_compressed_then_keep_extrapolation() {
+    let mut lut = surface(10, 10);
+    lut.set_extrapolation([Extrapolation::Constant(-1.0), Extrapolation::Clamp]);
+
+    let compressed = lut.compress(1.0).unwrap();
+
+    assert_eq!(compressed.table.get(&[0.0, 0.5]), -1.0);
+    assert_eq!(compressed.table.get(&[6000.0, 2.0]), lut.get(&[6000.0, 1.0]));
+}
+
+#[rstest]
+#[case(f64::NAN)]
+#[case(f64::INFINITY)]
+fn when_tolerance_is_not_finite_then_dont_compress(#[case] tolerance: f64) {
+    assert!(matches!(
+        curve(10).compress(tolerance).err().unwrap(),
+        ConstructionError::ContainingNansOrInfinities
+    ));
+    assert!(matches!(
+        surface(3, 3).compress(tolerance).err().unwrap(),
+        ConstructionError::ContainingNansOrInfinities
+    ));
+}
+
+#[rstest]
+#[case(-1e-3)]
+#[case(f64::NEG_INFINITY)]
+fn when_tolerance_is_negative_then_dont_compress(#[case] tolerance: f64) {
+    let table = TwoDLookUpTable::new([0.0, 1.0], [0.0, 1.0], [[0.0, 1.0], [1.0, 2.0]]).unwrap();
+
+    assert!(matches!(
+        curve(10).compress(tolerance).err().unwrap(),
+        ConstructionError::NegativeTolerance
+    ));
+    assert!(matches!(
+        surface(3, 3).compress(tolerance).err().unwrap(),
+        ConstructionError::NegativeTolerance
+    ));
+    assert!(matches!(
+        table.compress(tolerance).err().unwrap(),
+        CompressionError::InvalidTable(ConstructionError::NegativeTolerance)
+    ));
+}
+
+#[rstest]
+fn when_one_d_tables_are_compressed_then_match_the_dynamic_table(#[values(0.0, 0.1, 2.0)] tolerance: f64) {
+    let xs: Vec<f64> = (0..40).map(|i| i as f64 * 6.0 / 39.0).collect();
+    let ys: Vec<f64> = xs.iter().map(|x| 100.0 * (x * 0.8).tanh() + x.sin()).collect();
+    let mut lut = NDLookUpTable::new([&xs], &ys).unwrap();
+    let mut owned = OneDLookUpTable::<40>::new(xs[..].try_into().unwrap(), ys[..].try_into().unwrap()).unwrap();
+    let mut borrowed = OneDLookUpTableRef::new(&xs, &ys).unwrap();
+    owned.set_extrapolation(Extrapolation::Linear);
+    borrowed.set_extrapolation(Extrapolation::Linear);
+    lut.set_extrapolation([Extrapolation::Linear]);
+
+    let expected = lut.compress(tolerance).unwrap();
+    let dynamic = expected.table.compress(tolerance).unwrap();
+    assert_eq!(dynamic.table.axes(), expected.table.axes());
+    for compressed in [
+        owned.compress(tolerance).unwrap(),
+        borrowed.compress(tolerance).unwrap(),
+    ] {
+        assert_eq!(compressed.table.breakpoints(), expected.table.axes()[0]);
+        assert_eq!(compressed.error, expected.error);
+        assert!((compressed.table.get(&7.0) - expected.table.get(&[7.0])).abs() < 1e-9);
+        assert_eq!(
+            compressed.table.compress(0.0).unwrap().table.breakpoints(),
+            dynamic.table.axes()[0]
+        );
+    }
+}
+
+#[rstest]
+fn when_two_d_tables_are_compressed_then_match_the_dynamic_table(#[values(0.0, 0.5, 10.0)] tolerance: f64) {
+    let lut = surface(6, 5);
+    let [xs, ys] = lut.axes();
+    let rows: [[f64; 5]; 6] = core::array::from_fn(|i| core::array::from_fn(|j| lut.get(&[xs[i], ys[j]])));
+    let columns: Vec<Vec<f64>> = (0..5).map(|j| rows.iter().map(|row| row[j]).collect()).collect();
+    let columns: Vec<&[f64]> = columns.iter().map(|column| &column[..]).collect();
+    let mut owned = TwoDLookUpTable::new(xs.try_into().unwrap(), ys.try_into().unwrap(), rows).unwrap();
+    let mut borrowed = TwoDLookUpTableRef::new(xs, ys, &columns, SurfaceOrientation::RowPerY).unwrap();
+    owned.set_extrapolation(Extrapolation::Clamp, Extrapolation::Constant(-1.0));
+    borrowed.set_extrapolation(Extrapolation::Clamp, Extrapolation::Constant(-1.0));
+
+    let expected = lut.compress(tolerance).unwrap();
+    for compressed in [
+        owned.compress(tolerance).unwrap(),
+        borrowed.compress(tolerance).unwrap(),
+    ] {
+        assert_eq!(compressed.table.axes(), expected.table.axes());
+        assert_eq!(compressed.error, expected.error);
+        assert_eq!(compressed.table.get(&1000.0, &2.0), -1.0);
+        assert!((compressed.table.get(&1234.0, &0.3) - expected.table.get(&[1234.0, 0.3])).abs() < 1e-9);
+        assert_eq!(compressed.table.compress(0.0).unwrap().table.axes(), expected.table.axes());
+    }
+}
+
+#[test]
+fn when_interpolation_is_not_linear_then_dont_compress() {
+    let (xs, ys) = ([0.0, 1.0, 2.0, 3.0], [0.0, 1.0, 0.0, 1.0]);
+    let spline = Interpolation::CubicSpline(SplineBoundary::Natural);
+    let owned = OneDLookUpTable::with_interpolation(xs, ys, spline).unwrap();
+    let borrowed = OneDLookUpTableRef::with_interpolation(&xs, &ys, Interpolation::Previous).unwrap();
+    let mut surface = TwoDLookUpTable::new([0.0, 1.0], [0.0, 1.0], [[0.0, 1.0], [1.0, 2.0]]).unwrap();
+    surface.set_interpolation(SurfaceInterpolation::Bicubic);
+
+    assert!(matches!(
+        owned.compress(0.1).err().unwrap(),
+        CompressionError::NonLinearInterpolation
+    ));
+    assert!(matches!(
+        borrowed.compress(0.1).err().unwrap(),
+        CompressionError::NonLinearInterpolation
+    ));
+    assert!(matches!(
+        surface.compress(0.1).err().unwrap(),
+        CompressionError::NonLinearInterpolation
+    ));
+}
@@ -0,0 +1,129 @@
+use look_up_table::{
+    lut, lut2d, AxisRange, ConstOneDLookUpTable, ConstTwoDLookUpTable, ConstructionError, Extrapolation, LookUpError,
+    OneDLookUpTable, TwoDLookUpTable,
+};
+use rstest::rstest;
+
+const SPEED: [f64; 5] = [800.0, 1500.0, 2500.0, 4000.0, 6000.0];
+const TORQUE: [f64; 5] = [90.0, 140.0, 175.0, 160.0, 120.0];
+const LOAD: [f64; 3] = [0.0, 0.5, 1.0];
+const MAP: [[f64; 3]; 5] = [
+    [1.0, 2.0, 4.0],
+    [2.0, 3.5, 6.0],
+    [3.0, 5.0, 8.5],
+    [4.0, 7.0, 11.0],
+    [5.0, 9.0, 14.0],
+];
+
+static TORQUE_CURVE: ConstOneDLookUpTable<5> = ConstOneDLookUpTable::<5>::new(SPEED, TORQUE);
+static TORQUE_MAP: ConstTwoDLookUpTable<5, 3> =
+    ConstTwoDLookUpTable::<5, 3>::new(SPEED, LOAD, MAP).with_extrapolation(Extrapolation::Linear, Extrapolation::Clamp);
+
+#[rstest]
+#[case(800.0)]
+#[case(1234.5)]
+#[case(4000.0)]
+#[case(-300.0)]
+#[case(8500.0)]
+fn when_const_table_is_looked_up_then_match_owned_table(
+    #[case] x: f64,
+    #[values(Extrapolation::Clamp, Extrapolation::Linear, Extrapolation::Periodic)] extrapolation: Extrapolation,
+) {
+    let mut owned = OneDLookUpTable::new(SPEED, TORQUE).unwrap();
+    owned.set_extrapolation(extrapolation);
+    let lut = TORQUE_CURVE.with_extrapolation(extrapolation);
+
+    assert_eq!(lut.get(&x), owned.get(&x));
+}
+
+#[rstest]
+#[case(1000.0, 0.25)]
+#[case(2500.0, 0.5)]
+#[case(6000.0, 1.0)]
+#[case(-1.0, 2.0)]
+fn when_const_surface_is_looked_up_then_match_owned_table(#[case] x: f64, #[case] y: f64) {
+    let mut owned = TwoDLookUpTable::new(SPEED, LOAD, MAP).unwrap();
+    owned.set_extrapolation(Extrapolation::Linear, Extrapolation::Clamp);
+
+    assert_eq!(TORQUE_MAP.get(&x, &y), owned.get(&x, &y));
+}
+
+#[test]
+fn when_const_table_is_single_precision_then_interpolate() {
+    const LUT: ConstOneDLookUpTable<3, f32> = ConstOneDLookUpTable::<3, f32>::new([1.0, 2.0, 3.0], [2.0, 4.0, 8.0]);
+
+    assert!((LUT.get(&2.5) - 6.0).abs() < f32::EPSILON);
+}
+
+#[test]
+fn when_index_is_out_of_range_then_report_it() {
+    let lut = TORQUE_CURVE.with_extrapolation(Extrapolation::Error);
+
+    assert_eq!(lut.try_get(&100.0), Err(LookUpError::OutOfRange { axis: 0 }));
+    assert!(lut.get(&100.0).is_nan());
+    assert_eq!(
+        TORQUE_MAP.get_checked(&7000.0, &0.25).unwrap().range,
+        [AxisRange::Above, AxisRange::Between]
+    );
+}
+
+#[test]
+fn when_values_are_invalid_then_report_same_errors_as_owned_tables() {
+    assert!(matches!(
+        ConstOneDLookUpTable::<3>::try_new([1.0, 3.0, 2.0], [0.0; 3]).unwrap_err(),
+        ConstructionError::IncreasingDimOrderError
+    ));
+    assert!(matches!(
+        ConstOneDLookUpTable::<1>::try_new([1.0], [0.0]).unwrap_err(),
+        ConstructionError::MinLengthError
+    ));
+    assert!(matches!(
+        ConstTwoDLookUpTable::<2, 2>::try_new([1.0, 2.0], [1.0, 2.0], [[0.0, 1.0], [f64::INFINITY, 1.0]]).unwrap_err(),
+        ConstructionError::ContainingNansOrInfinities
+    ));
+    assert!(matches!(
+        ConstTwoDLookUpTable::<2, 2, f32>::try_new([1.0, 2.0], [2.0, 2.0], [[0.0; 2]; 2]).unwrap_err(),
+        ConstructionError::IncreasingDimOrderError
+    ));
+}
+
+#[test]
+fn when_table_is_written_with_macro_then_match_constructor() {
+    let curve = lut![
+        800.0 => 90.0,
+        1500.0 => 140.0,
+        2500.0 => 175.0,
+        4000.0 => 160.0,
+        6000.0 => 120.0,
+    ];
+    let map = lut2d![
+        y:         [0.0, 0.5, 1.0],
+        800.0  => [1.0, 2.0, 4.0],
+        1500.0 => [2.0, 3.5, 6.0],
+        2500.0 => [3.0, 5.0, 8.5],
+        4000.0 => [4.0, 7.0, 11.0],
+        6000.0 => [5.0, 9.0, 14.0],
+    ];
+
+    for x in [700.0, 1000.0, 2500.0, 5999.0] {
+        assert_eq!(curve.get(&x), TORQUE_CURVE.get(&x));
+        assert_eq!(
+            map.get(&x, &0.3),
+            TORQUE_MAP
+                .with_extrapolation(Extrapolation::Clamp, Extrapolation::Clamp)
+                .get(&x, &0.3)
+        );
+    }
+}
+
+#[test]
+fn when_macro_is_given_element_type_then_use_it() {
+    let lut = lut2d![f32;
+        y:      [1.0, 2.0],
+        1.0 => [1.0, 2.0],
+        2.0 => [3.0, 4.0],
+    ];
+    let _: ConstTwoDLookUpTable<2, 2, f32> = lut;
+
+    assert!((lut.get(&1.5, &1.25) - 2.25).abs() < f32::EPSILON);
+}
@@ -0,0 +1,214 @@
+use look_up_table::{
+    ConstructionError, CsvError, DynOneDLookUpTable, DynTwoDLookUpTable, NDLookUpTable, OneDLookUpTable,
+    TwoDLookUpTable,
+};
+use rstest::rstest;
+
+const SPEED: [f64; 5] = [800.0, 1500.0, 2500.0, 4000.0, 6000.0];
+const TORQUE: [f64; 5] = [90.0, 140.0, 175.0, 160.0, 120.0];
+const LOAD: [f64; 3] = [0.0, 0.5, 1.0];
+const MAP: [[f64; 3]; 5] = [
+    [1.0, 2.0, 4.0],
+    [2.0, 3.5, 6.0],
+    [3.0, 5.0, 8.5],
+    [4.0, 7.0, 11.0],
+    [5.0, 9.0, 14.0],
+];
+
+const CURVE: &str = "800, 90\r\n1500, 140\n\n2500, 175\n4000, 160\n 6000 , 120 \n";
+const SURFACE: &str = "\
+load\\speed, 800, 1500, 2500, 4000, 6000
+0.0,         1,    2,    3,    4,    5
+0.5,         2,  3.5,    5,    7,    9
+1.0,         4,    6,  8.5,   11,   14
+";
+
+#[test]
+fn when_curve_is_read_then_lookups_match_the_table() {
+    let lut = OneDLookUpTable::new(SPEED, TORQUE).unwrap();
+    let read = OneDLookUpTable::<5>::from_csv(CURVE).unwrap();
+    let dynamic = NDLookUpTable::<1>::from_csv(CURVE).unwrap();
+    let sized = DynOneDLookUpTable::<f64>::from_csv(CURVE).unwrap();
+
+    for x in [0.0, 800.0, 1000.0, 3333.0, 7000.0] {
+        assert_eq!(read.get(&x), lut.get(&x));
+        assert_eq!(sized.get(&x), lut.get(&x));
+        assert!((dynamic.get(&[x]) - lut.get(&x)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn when_surface_is_read_then_lookups_match_the_table() {
+    let lut = TwoDLookUpTable::new(SPEED, LOAD, MAP).unwrap();
+    let read = TwoDLookUpTable::<5, 3>::from_csv(SURFACE).unwrap();
+    let dynamic = NDLookUpTable::<2>::from_csv(SURFACE).unwrap();
+    let sized = DynTwoDLookUpTable::<f64>::from_csv(SURFACE).unwrap();
+
+    for (x, y) in [(1000.0, 0.25), (2500.0, 0.5), (7000.0, 0.9), (3000.0, 2.0)] {
+        assert_eq!(read.get(&x, &y), lut.get(&x, &y));
+        assert_eq!(sized.get(&x, &y), lut.get(&x, &y));
+        assert!((dynamic.get(&[x, y]) - lut.get(&x, &y)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn when_tables_are_written_then_they_are_read_back() {
+    let (mut curve, mut surface) = (String::new(), String::new());
+    OneDLookUpTable::new(SPEED, [0.1, 1.0 / 3.0, 1e-20, -2.5, 1e20])
+        .unwrap()
+        .to_csv(&mut curve)
+        .unwrap();
+    TwoDLookUpTable::new(SPEED, LOAD, MAP)
+        .unwrap()
+        .to_csv(&mut surface)
+        .unwrap();
+
+    let mut copy = String::new();
+    OneDLookUpTable::<5>::from_csv(&curve)
+        .unwrap()
+        .to_csv(&mut copy)
+        .unwrap();
+    assert_eq!(copy, curve);
+
+    copy.clear();
+    NDLookUpTable::<2>::from_csv(&surface)
+        .unwrap()
+        .to_csv(&mut copy)
+        .unwrap();
+    assert_eq!(copy, surface);
+
+    copy.clear();
+    DynOneDLookUpTable::<f64>::from_csv(&curve)
+        .unwrap()
+        .to_csv(&mut copy)
+        .unwrap();
+    assert_eq!(copy, curve);
+
+    copy.clear();
+    DynTwoDLookUpTable::<f64>::from_csv(&surface)
+        .unwrap()
+        .to_csv(&mut copy)
+        .unwrap();
+    assert_eq!(copy, surface);
+    assert!(surface.starts_with(",800,1500,2500,4000,6000\n0,1,2,3,4,5\n"));
+}
+
+#[rstest]
+#[case("1, 2\n2, x\n", 2, 2)]
+#[case("1, 2\n, 3\n", 2, 1)]
+fn when_curve_value_is_not_a_number_then_report_its_position(
+    #[case] csv: &str,
+    #[case] line: usize,
+    #[case] column: usize,
+) {
+    let err = NDLookUpTable::<1>::from_csv(csv).unwrap_err();
+
+    assert!(matches!(err, CsvError::InvalidNumber { line: l, column: c } if l == line && c == column));
+}
+
+#[test]
+fn when_line_has_wrong_number_of_columns_then_report_the_line() {
+    let err = OneDLookUpTable::<2>::from_csv("1, 2\n\n2, 3, 4\n").unwrap_err();
+
+    assert!(matches!(
+        err,
+        CsvError::ColumnCountMismatch {
+            line: 3,
+            expected: 2,
+            actual: 3
+        }
+    ));
+    assert_eq!(err.to_string(), "Line 3: Expected 2 columns, but found 3");
+}
+
+#[rstest]
+#[case("1, 2\n2, 3\n2, 4\n", 3, 1, ConstructionError::IncreasingDimOrderError)]
+#[case("1, 2\n2, inf\n3, 4\n", 2, 2, ConstructionError::ContainingNansOrInfinities)]
+#[case("1, 2\n2, 3\n", 3, 1, ConstructionError::DimensionMismatch { expected: 3, actual: 2 })]
+fn when_curve_is_invalid_then_report_the_position_and_error(
+    #[case] csv: &str,
+    #[case] line: usize,
+    #[case] column: usize,
+    #[case] error: ConstructionError,
+) {
+    let err = OneDLookUpTable::<3>::from_csv(csv).unwrap_err();
+
+    let CsvError::InvalidTable {
+        line: l,
+        column: c,
+        error: e,
+    } = err
+    else {
+        panic!("{err:?}");
+    };
+    assert_eq!((l, c), (line, column));
+    assert_eq!(e.to_string(), error.to_string());
+}
+
+#[rstest]
+#[case(
+    "x, 1, 1, 3\n0, 1, 2, 3\n1, 1, 2, 3\n",
+    1,
+    3,
+    ConstructionError::IncreasingDimOrderError
+)]
+#[case(
+    "x, 1, 2, 3\n1, 1, 2, 3\n0, 1, 2, 3\n",
+    3,
+    1,
+    ConstructionError::IncreasingDimOrderError
+)]
+#[case(
+    "x, 1, 2, 3\n0, 1, 2, 3\n1, 1, NaN, 3\n",
+    3,
+    3,
+    ConstructionError::ContainingNansOrInfinities
+)]
+#[case("x, 1, 2, 3\n0, 1, 2, 3\n", 3, 1, ConstructionError::MinLengthError)]
+fn when_surface_is_invalid_then_report_the_position_and_error(
+    #[case] csv: &str,
+    #[case] line: usize,
+    #[case] column: usize,
+    #[case] error: ConstructionError,
+) {
+    let errors = [
+        NDLookUpTable::<2>::from_csv(csv).unwrap_err(),
+        DynTwoDLookUpTable::<f64>::from_csv(csv).unwrap_err(),
+    ];
+
+    for err in errors {
+        let CsvError::InvalidTable {
+            line: l,
+            column: c,
+            error: e,
+        } = err
+        else {
+            panic!("{err:?}");
+        };
+        assert_eq!((l, c), (line, column));
+        assert_eq!(e.to_string(), error.to_string());
+    }
+}
+
+#[test]
+fn when_surface_has_wrong_shape_then_report_it() {
+    let err = TwoDLookUpTable::<5, 3>::from_csv(SURFACE.trim_end().rsplit_once('\n').unwrap().0).unwrap_err();
+    assert!(matches!(
+        err,
+        CsvError::InvalidTable {
+            line: 4,
+            column: 1,
+            error: ConstructionError::RowCountMismatch { expected: 3, actual: 2 }
+        }
+    ));
+
+    let err = TwoDLookUpTable::<4, 3>::from_csv(SURFACE).unwrap_err();
+    assert!(matches!(
+        err,
+        CsvError::ColumnCountMismatch {
+            line: 1,
+            expected: 5,
+            actual: 6
+        }
+    ));
+}
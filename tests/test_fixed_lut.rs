@@ -0,0 +1,125 @@
+use look_up_table::{
+    ConstructionError, FixedOneDLookUpTable, FixedTwoDLookUpTable, OneDLookUpTable, Scaling, TwoDLookUpTable,
+};
+use rstest::{fixture, rstest};
+
+const X_SCALING: Scaling = Scaling::new(0.01, 0.0);
+
+#[fixture]
+fn fixed_lut() -> FixedOneDLookUpTable<5> {
+    FixedOneDLookUpTable::from_physical(
+        [-1.0, 0.0, 2.5, 3.0, 10.0],
+        [0.5, -0.25, 0.75, 0.999, -1.0],
+        &X_SCALING,
+        &Scaling::Q15,
+    )
+    .unwrap()
+}
+
+#[rstest]
+#[case(-0.5)]
+#[case(0.01)]
+#[case(1.37)]
+#[case(2.99)]
+#[case(7.77)]
+#[case(10.0)]
+fn when_index_is_within_bounds_then_match_float_table_within_half_lsb(
+    fixed_lut: FixedOneDLookUpTable<5>,
+    #[case] x: f64,
+) {
+    // The float table is built from the quantized values, so the only difference is the final rounding.
+    let xs = [-1.0, 0.0, 2.5, 3.0, 10.0].map(|v| X_SCALING.to_physical(X_SCALING.to_raw::<i16>(v)));
+    let ys = [0.5, -0.25, 0.75, 0.999, -1.0].map(|v| Scaling::Q15.to_physical(Scaling::Q15.to_raw::<i16>(v)));
+    let float_lut = OneDLookUpTable::new(xs, ys).unwrap();
+
+    let raw_x = X_SCALING.to_raw::<i16>(x);
+    let expected = float_lut.get(&X_SCALING.to_physical(raw_x));
+    let actual = Scaling::Q15.to_physical(fixed_lut.get(raw_x));
+
+    assert!((actual - expected).abs() <= Scaling::Q15.scale / 2.0);
+}
+
+#[rstest]
+#[case(-2000, 16384)]
+#[case(i16::MIN, 16384)]
+#[case(1001, -32768)]
+#[case(i16::MAX, -32768)]
+fn when_index_is_out_of_bounds_then_clamp_to_boundary(
+    fixed_lut: FixedOneDLookUpTable<5>,
+    #[case] x: i16,
+    #[case] expected: i16,
+) {
+    assert_eq!(fixed_lut.get(x), expected);
+}
+
+#[rstest]
+#[case(1, 1)] // 0.5 rounds up
+#[case(3, 2)] // 1.5 rounds up
+#[case(-1, -1)] // -0.5 rounds down
+#[case(-3, -2)] // -1.5 rounds down
+fn when_result_is_a_tie_then_round_away_from_zero(#[case] x: i16, #[case] expected: i16) {
+    let lut = FixedOneDLookUpTable::<3, i16>::new([-4, 0, 4], [-2, 0, 2]).unwrap();
+
+    assert_eq!(lut.get(x), expected);
+}
+
+#[test]
+fn when_values_span_the_full_range_then_dont_overflow() {
+    let lut = FixedOneDLookUpTable::<2, i32>::new([i32::MIN, i32::MAX], [i32::MAX, i32::MIN]).unwrap();
+    assert_eq!(lut.get(i32::MIN), i32::MAX);
+    assert_eq!(lut.get(-1), 0);
+
+    let lut = FixedOneDLookUpTable::<2, u16>::new([0, u16::MAX], [u16::MAX, 0]).unwrap();
+    assert_eq!(lut.get(1), u16::MAX - 1);
+}
+
+#[rstest]
+#[case(0.5, 16384)]
+#[case(-1.0, i16::MIN)]
+#[case(1.0, i16::MAX)]
+#[case(-7.5, i16::MIN)]
+#[case(f64::NAN, 0)]
+#[case(0.5 / 32768.0, 1)]
+#[case(-0.5 / 32768.0, -1)]
+fn when_converting_to_raw_then_round_and_saturate(#[case] v: f64, #[case] expected: i16) {
+    assert_eq!(Scaling::Q15.to_raw::<i16>(v), expected);
+}
+
+#[rstest]
+#[case(0.5, 0.5)]
+#[case(12.0, 20.0)]
+#[case(17.3, 23.7)]
+#[case(33.3, 44.4)]
+#[case(-5.0, 21.0)]
+fn when_two_d_index_is_given_then_match_float_table_within_half_lsb(#[case] x: f64, #[case] y: f64) {
+    let xs = [0.0, 10.0, 25.0, 40.0];
+    let ys = [0.0, 20.0, 50.0];
+    let surface = [[0.1, -0.4, 0.9], [0.3, 0.7, -0.9], [-0.2, 0.0, 0.5], [0.8, -0.6, 0.25]];
+    let z_scaling = Scaling::Q31;
+    let fixed_lut: FixedTwoDLookUpTable<4, 3, i32> =
+        FixedTwoDLookUpTable::from_physical(xs, ys, surface, &X_SCALING, &X_SCALING, &z_scaling).unwrap();
+    let float_lut = TwoDLookUpTable::new(
+        xs,
+        ys,
+        surface.map(|row| row.map(|v| z_scaling.to_physical(z_scaling.to_raw::<i32>(v)))),
+    )
+    .unwrap();
+
+    let (raw_x, raw_y) = (X_SCALING.to_raw::<i32>(x), X_SCALING.to_raw::<i32>(y));
+    let expected = float_lut.get(&X_SCALING.to_physical(raw_x), &X_SCALING.to_physical(raw_y));
+    let actual = z_scaling.to_physical(fixed_lut.get(raw_x, raw_y));
+
+    assert!((actual - expected).abs() <= z_scaling.scale / 2.0 + 1e-15);
+}
+
+#[test]
+fn when_breakpoints_are_invalid_dont_construct_object() {
+    let res = FixedOneDLookUpTable::<3, i16>::new([0, 5, 5], [1, 2, 3]);
+    assert!(matches!(res.unwrap_err(), ConstructionError::IncreasingDimOrderError));
+
+    let res = FixedOneDLookUpTable::<1, i16>::new([0], [1]);
+    assert!(matches!(res.unwrap_err(), ConstructionError::MinLengthError));
+
+    let res = FixedTwoDLookUpTable::<2, 2, u32>::new([0, 1], [1, 0], [[0; 2]; 2]);
+    assert!(matches!(res.unwrap_err(), ConstructionError::IncreasingDimOrderError));
+}
@@ -0,0 +1,119 @@
+use look_up_table::{
+    AxisRange, ConstructionError, Extrapolation, LookUpError, NDLookUpTable, NDLookUpTableRef, TwoDLookUpTable,
+};
+use rstest::{fixture, rstest};
+
+type Volume = NDLookUpTable<3>;
+
+// f(x, y, z) = x + 2y + 3z sampled on a non uniform grid, which multilinear interpolation reproduces exactly.
+#[fixture]
+fn linear_volume() -> Volume {
+    let xs = [0.0, 1.0, 4.0];
+    let ys = [-1.0, 0.5];
+    let zs = [2.0, 3.0, 5.0, 6.0];
+    let mut values = Vec::new();
+    for x in xs {
+        for y in ys {
+            for z in zs {
+                values.push(x + 2.0 * y + 3.0 * z);
+            }
+        }
+    }
+
+    NDLookUpTable::new([&xs, &ys, &zs], &values).unwrap()
+}
+
+#[rstest]
+#[case([0.0, -1.0, 2.0], 4.0)]
+#[case([4.0, 0.5, 6.0], 23.0)]
+#[case([2.5, 0.0, 4.2], 15.1)]
+#[case([1.0, -0.25, 5.5], 17.0)]
+fn when_index_is_within_bounds_then_perform_multilinear_interpolation(
+    linear_volume: Volume,
+    #[case] index: [f64; 3],
+    #[case] expected: f64,
+) {
+    let actual = linear_volume.get(&index);
+
+    assert!((actual - expected).abs() < 0.000001);
+}
+
+#[rstest]
+#[case([-3.0, -5.0, 0.0], 4.0)]
+#[case([10.0, 0.0, 10.0], 22.0)]
+fn when_index_is_out_of_bounds_then_clamp_to_boundary(
+    linear_volume: Volume,
+    #[case] index: [f64; 3],
+    #[case] expected: f64,
+) {
+    let actual = linear_volume.get(&index);
+
+    assert!((actual - expected).abs() < 0.000001);
+}
+
+#[rstest]
+fn when_extrapolation_policies_are_set_then_apply_them_per_axis(mut linear_volume: Volume) {
+    linear_volume.set_extrapolation([Extrapolation::Linear, Extrapolation::Clamp, Extrapolation::Error]);
+
+    assert!((linear_volume.get(&[6.0, 3.0, 2.0]) - 13.0).abs() < 0.000001);
+    assert_eq!(
+        linear_volume.try_get(&[1.0, 0.0, 7.0]),
+        Err(LookUpError::OutOfRange { axis: 2 })
+    );
+}
+
+#[rstest]
+#[case(14.5, 20.2)]
+#[case(14.0, 20.7)]
+#[case(16.0, 19.0)]
+fn when_table_has_two_dimensions_then_match_two_d_table(#[case] x: f64, #[case] y: f64) {
+    let xs = [14.0, 15.0];
+    let ys = [20.0, 21.0];
+    let values = [91.0, 210.0, 162.0, 95.0];
+    let nd = NDLookUpTableRef::new([&xs, &ys], &values).unwrap();
+    let twod = TwoDLookUpTable::new(xs, ys, [[91.0, 210.0], [162.0, 95.0]]).unwrap();
+
+    assert!((nd.get(&[x, y]) - twod.get(&x, &y)).abs() < 0.000001);
+}
+
+#[test]
+fn when_values_do_not_match_grid_size_dont_construct_object() {
+    let res = NDLookUpTableRef::new([&[0.0, 1.0], &[0.0, 1.0], &[0.0, 1.0]], &[0.0; 7]);
+
+    assert!(matches!(
+        res.unwrap_err(),
+        ConstructionError::DimensionMismatch { expected: 8, actual: 7 }
+    ));
+}
+
+#[test]
+fn when_an_axis_is_invalid_dont_construct_object() {
+    let res = NDLookUpTable::new([&[0.0, 1.0], &[1.0, 0.0]], &[0.0; 4]);
+    assert!(matches!(res.unwrap_err(), ConstructionError::IncreasingDimOrderError));
+
+    let res = NDLookUpTable::new([&[0.0, 1.0], &[1.0]], &[0.0; 2]);
+    assert!(matches!(res.unwrap_err(), ConstructionError::MinLengthError));
+
+    let res = NDLookUpTable::new([&[0.0, 1.0], &[0.0, 1.0]], &[0.0, f64::NAN, 0.0, 0.0]);
+    assert!(matches!(res.unwrap_err(), ConstructionError::ContainingNansOrInfinities));
+}
+
+#[rstest]
+fn when_lookup_is_checked_then_report_where_index_lies_per_axis(linear_volume: Volume) {
+    let checked = linear_volume.get_checked(&[5.0, 0.5, 2.5]).unwrap();
+
+    assert!((checked.value - 12.5).abs() < 0.000001);
+    assert_eq!(checked.range, [AxisRange::Above, AxisRange::OnBreakpoint, AxisRange::Between]);
+    assert!(checked.is_out_of_range());
+}
+
+#[rstest]
+#[case(Extrapolation::Linear)]
+#[case(Extrapolation::Periodic)]
+fn when_index_is_nan_and_not_clamped_then_return_nan(#[case] extrapolation: Extrapolation) {
+    let mut lut = NDLookUpTable::new([&[0.0, 1.0, 2.0], &[0.0, 1.0]], &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+    lut.set_extrapolation([extrapolation; 2]);
+
+    assert!(lut.get(&[f64::NAN, 0.5]).is_nan());
+    assert!(lut.get(&[1.5, f64::NAN]).is_nan());
+}
@@ -2,7 +2,10 @@
 // extern crate test;
 // use test::Bencher;
 
-use look_up_table::{ConstructionError, OneDLookUpTable};
+use look_up_table::{
+    AxisRange, ConstructionError, Extrapolation, Interpolation, LookUpError, OneDLookUpTable, OneDLookUpTableRef,
+    SplineBoundary,
+};
 use rstest::{fixture, rstest};
 
 type IncrFunc = OneDLookUpTable<5>;
@@ -57,6 +60,23 @@ fn when_given_single_x_value_dont_construct_object() {
     assert!(matches!(res.unwrap_err(), ConstructionError::MinLengthError))
 }
 
+#[rstest]
+#[case(&[1.0, 2.0, 3.0], &[1.0, 2.0], 3, 2)]
+#[case(&[1.0, 2.0], &[1.0, 2.0, 3.0, 4.0], 2, 4)]
+fn when_ref_lengths_differ_dont_construct_object(
+    #[case] xs: &[f64],
+    #[case] ys: &[f64],
+    #[case] expected: usize,
+    #[case] actual: usize,
+) {
+    let res = OneDLookUpTableRef::new(xs, ys);
+
+    assert!(matches!(
+        res.unwrap_err(),
+        ConstructionError::DimensionMismatch { expected: e, actual: a } if e == expected && a == actual
+    ))
+}
+
 #[rstest]
 fn when_x_or_y_values_contain_nan_or_infinities_dont_construct_object(
     #[values([f64::NAN;5], [0.0, 1.5, f64::INFINITY, 4.5, 2.3], [1.1, 2.2, 3.3, 4.4, 5.5])] x: [f64; 5],
@@ -76,6 +96,236 @@ fn when_x_is_in_range_return_interpolated_value(random_function: RandFunc, #[cas
     assert!((actual - expected).abs() < 0.0001);
 }
 
+#[rstest]
+#[case(0.5, 0.125)]
+#[case(2.5, 15.625)]
+#[case(3.2, 32.768)]
+fn when_spline_is_clamped_with_exact_slopes_then_cubic_is_reproduced(#[case] input: f64, #[case] expected: f64) {
+    let x = [0.0, 1.0, 2.0, 3.0, 4.0];
+    let y = x.map(|v| v * v * v);
+    let spline = Interpolation::CubicSpline(SplineBoundary::Clamped(0.0, 48.0));
+    let lut = OneDLookUpTable::with_interpolation(x, y, spline).unwrap();
+
+    let actual = lut.get(&input);
+    assert!((actual - expected).abs() < 1e-9);
+}
+
+#[rstest]
+#[case(-1.0, 8.0)]
+#[case(2.0, 4.0)]
+#[case(25.0, 2.0)]
+fn when_spline_is_queried_at_breakpoints_or_outside_return_sample_values(#[case] input: f64, #[case] expected: f64) {
+    let x = [1.0, 2.0, 7.0, 9.0, 13.0, 20.0];
+    let y = [8.0, 4.0, 6.0, 10.0, 3.0, 2.0];
+    let lut =
+        OneDLookUpTableRef::with_interpolation(&x, &y, Interpolation::CubicSpline(SplineBoundary::Natural)).unwrap();
+
+    assert!((lut.get(&input) - expected).abs() < f64::EPSILON);
+}
+
+#[test]
+fn when_natural_spline_is_evaluated_then_second_derivative_vanishes_at_ends() {
+    let x = [1.0, 2.0, 7.0, 9.0, 13.0, 20.0];
+    let y = [8.0, 4.0, 6.0, 10.0, 3.0, 2.0];
+    let lut =
+        OneDLookUpTableRef::with_interpolation(&x, &y, Interpolation::CubicSpline(SplineBoundary::Natural)).unwrap();
+
+    let h: f64 = 1e-3;
+    for end in [1.0 + h, 20.0 - h] {
+        let second = (lut.get(&(end + h)) - 2.0 * lut.get(&end) + lut.get(&(end - h))) / (h * h);
+        assert!(second.abs() < 1e-2);
+    }
+}
+
+#[test]
+fn when_spline_has_two_points_then_it_is_linear() {
+    let lut = OneDLookUpTable::with_interpolation(
+        [1.0f64, 3.0],
+        [2.0, 6.0],
+        Interpolation::CubicSpline(SplineBoundary::Natural),
+    )
+    .unwrap();
+
+    assert!((lut.get(&2.5) - 5.0).abs() < 1e-12);
+}
+
+#[rstest]
+fn when_pchip_is_used_then_values_never_overshoot_neighbouring_samples(random_function: RandFunc) {
+    let x = [1.0f64, 2.0, 7.0, 9.0, 13.0, 20.0];
+    let y = [8.0f64, 4.0, 6.0, 10.0, 3.0, 2.0];
+    let lut = OneDLookUpTableRef::with_interpolation(&x, &y, Interpolation::Pchip).unwrap();
+
+    for (xw, yw) in x.windows(2).zip(y.windows(2)) {
+        let (lo, hi) = (yw[0].min(yw[1]), yw[0].max(yw[1]));
+        for i in 0..=100 {
+            let v = lut.get(&(xw[0] + (xw[1] - xw[0]) * i as f64 / 100.0));
+            assert!(v >= lo - 1e-12 && v <= hi + 1e-12);
+        }
+    }
+
+    // The breakpoints are still reproduced exactly, and agree with the linear table.
+    for xv in x {
+        assert!((lut.get(&xv) - random_function.get(&xv)).abs() < f64::EPSILON);
+    }
+}
+
+#[test]
+fn when_pchip_data_is_monotone_then_interpolation_is_monotone() {
+    let x = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = [0.0, 0.1, 0.15, 5.0, 5.05, 10.0];
+    let lut = OneDLookUpTable::with_interpolation(x, y, Interpolation::Pchip).unwrap();
+
+    let samples: Vec<f64> = (0..=500).map(|i| lut.get(&(i as f64 / 100.0))).collect();
+    assert!(samples.windows(2).all(|w| w[1] >= w[0]));
+}
+
+#[test]
+fn when_pchip_has_two_points_then_it_is_linear() {
+    let lut = OneDLookUpTable::with_interpolation([1.0f64, 3.0], [2.0, 6.0], Interpolation::Pchip).unwrap();
+
+    assert!((lut.get(&1.5) - 3.0).abs() < 1e-12);
+}
+
+#[rstest]
+fn when_akima_data_has_an_outlier_then_distant_segments_are_not_affected(
+    #[values(Interpolation::Akima, Interpolation::Makima)] method: Interpolation,
+) {
+    let x: [f64; 11] = std::array::from_fn(|i| i as f64);
+    let mut y = [0.0; 11];
+    y[5] = 10.0;
+    let akima = OneDLookUpTable::with_interpolation(x, y, method).unwrap();
+    let spline =
+        OneDLookUpTable::with_interpolation(x, y, Interpolation::CubicSpline(SplineBoundary::Natural)).unwrap();
+
+    assert!(akima.get(&2.5).abs() < f64::EPSILON);
+    assert!(akima.get(&7.5).abs() < f64::EPSILON);
+    assert!(spline.get(&2.5).abs() > 0.01);
+}
+
+#[rstest]
+fn when_akima_has_less_than_four_points_then_it_is_linear(
+    #[values(Interpolation::Akima, Interpolation::Makima)] method: Interpolation,
+    #[values(1.3, 2.0, 2.7)] input: f64,
+) {
+    let x = [1.0, 2.0, 4.0];
+    let y = [3.0, -1.0, 5.0];
+    let linear = OneDLookUpTableRef::new(&x, &y).unwrap();
+    let akima = OneDLookUpTableRef::with_interpolation(&x, &y, method).unwrap();
+
+    assert!((akima.get(&input) - linear.get(&input)).abs() < f64::EPSILON);
+}
+
+#[test]
+fn when_makima_data_is_flat_then_it_does_not_overshoot() {
+    let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+    let y = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+    let lut = OneDLookUpTable::with_interpolation(x, y, Interpolation::Makima).unwrap();
+
+    for i in 0..=500 {
+        let v = lut.get(&(1.0 + i as f64 / 100.0));
+        assert!((-1e-12..=1.0 + 1e-12).contains(&v));
+    }
+}
+
+#[test]
+fn when_akima_data_is_linear_then_it_is_reproduced() {
+    let x = [0.0f64, 1.0, 3.0, 4.0, 7.0];
+    let lut = OneDLookUpTable::with_interpolation(x, x.map(|v| 2.0 * v + 1.0), Interpolation::Akima).unwrap();
+
+    assert!((lut.get(&5.5) - 12.0).abs() < 1e-12);
+}
+
+#[rstest]
+#[case(Interpolation::Previous, 3.0, 4.0)]
+#[case(Interpolation::Previous, 2.0, 4.0)]
+#[case(Interpolation::Next, 3.0, 2.0)]
+#[case(Interpolation::Next, 2.0, 4.0)]
+#[case(Interpolation::Nearest, 3.4, 4.0)]
+#[case(Interpolation::Nearest, 5.5, 3.0)]
+#[case(Interpolation::Nearest, 3.6, 2.0)]
+#[case(Interpolation::Next, 0.0, 1.0)]
+#[case(Interpolation::Previous, 12.0, 3.0)]
+fn when_step_interpolation_is_used_then_return_breakpoint_values(
+    #[case] method: Interpolation,
+    #[case] input: f64,
+    #[case] expected: f64,
+) {
+    let x = [1.0, 2.0, 5.0, 6.0];
+    let y = [1.0, 4.0, 2.0, 3.0];
+    let lut = OneDLookUpTableRef::with_interpolation(&x, &y, method).unwrap();
+
+    assert!((lut.get(&input) - expected).abs() < f64::EPSILON);
+}
+
+#[rstest]
+#[case(Extrapolation::Clamp, -1.0, 1.0)]
+#[case(Extrapolation::Linear, -1.0, -1.0)]
+#[case(Extrapolation::Linear, 7.5, 7.5)]
+#[case(Extrapolation::Constant(42.0), 6.0, 42.0)]
+#[case(Extrapolation::Periodic, 6.5, 2.5)]
+#[case(Extrapolation::Periodic, -0.5, 3.5)]
+#[case(Extrapolation::Periodic, 5.0, 5.0)]
+fn when_x_value_is_outside_interval_then_apply_extrapolation_policy(
+    mut simple_increasing_function: IncrFunc,
+    #[case] policy: Extrapolation,
+    #[case] input: f64,
+    #[case] expected: f64,
+) {
+    simple_increasing_function.set_extrapolation(policy);
+    let actual = simple_increasing_function.get(&input);
+
+    assert!((actual - expected).abs() < 0.000001);
+}
+
+#[rstest]
+fn when_extrapolation_policy_changes_then_cached_values_are_discarded(mut simple_increasing_function: IncrFunc) {
+    assert!((simple_increasing_function.get(&10.0) - 5.0).abs() < f64::EPSILON);
+
+    simple_increasing_function.set_extrapolation(Extrapolation::NaN);
+    assert!(simple_increasing_function.get(&10.0).is_nan());
+}
+
+#[test]
+fn when_extrapolation_policy_is_error_then_out_of_range_lookup_fails() {
+    let x = [1.0f64, 2.0, 7.0];
+    let y = [8.0, 4.0, 6.0];
+    let mut lut = OneDLookUpTableRef::new(&x, &y).unwrap();
+    lut.set_extrapolation(Extrapolation::Error);
+
+    assert_eq!(lut.try_get(&7.5), Err(LookUpError::OutOfRange { axis: 0 }));
+    assert!(lut.get(&0.5).is_nan());
+    assert!((lut.try_get(&7.0).unwrap() - 6.0).abs() < f64::EPSILON);
+}
+
+#[rstest]
+#[case(Interpolation::Linear, Interpolation::Linear)]
+#[case(Interpolation::Pchip, Interpolation::Pchip)]
+#[case(
+    Interpolation::CubicSpline(SplineBoundary::Clamped(-4.0, 0.1)),
+    Interpolation::CubicSpline(SplineBoundary::Clamped(-4.0, 0.1))
+)]
+fn when_table_is_single_precision_then_match_double_precision_table(
+    #[case] method: Interpolation,
+    #[case] method32: Interpolation<f32>,
+) {
+    let x = [1.0f32, 2.0, 7.0, 9.0, 13.0, 20.0];
+    let y = [8.0f32, 4.0, 6.0, 10.0, 3.0, 2.0];
+    let single = OneDLookUpTableRef::with_interpolation(&x, &y, method32).unwrap();
+    let double = OneDLookUpTable::with_interpolation(x.map(f64::from), y.map(f64::from), method).unwrap();
+
+    for i in 0..=220 {
+        let input = i as f64 / 10.0;
+        assert!((single.get(&(input as f32)) as f64 - double.get(&input)).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn when_single_precision_values_are_not_increasing_dont_construct_object() {
+    let res = OneDLookUpTable::new([1.0f32, 1.0 + 1e-9, 2.0], [0.0; 3]);
+
+    assert!(matches!(res.unwrap_err(), ConstructionError::IncreasingDimOrderError))
+}
+
 // Currently benchmarking is not supported on stable channel.
 // #[bench]
 // fn bench_when_same_x_value_is_queried_lookup_should_be_constant(b: &mut Bencher) {
@@ -85,3 +335,262 @@ fn when_x_is_in_range_return_interpolated_value(random_function: RandFunc, #[cas
 //
 //     b.iter(move || rand_func.get(16.0));
 // }
+
+#[rstest]
+#[case(0.0, 1.0, AxisRange::Below)]
+#[case(9.0, 5.0, AxisRange::Above)]
+#[case(f64::NAN, 5.0, AxisRange::Above)]
+#[case(3.0, 3.0, AxisRange::OnBreakpoint)]
+#[case(3.5, 3.5, AxisRange::Between)]
+fn when_lookup_is_checked_then_report_where_index_lies(
+    simple_increasing_function: IncrFunc,
+    #[case] x: f64,
+    #[case] expected: f64,
+    #[case] range: AxisRange,
+) {
+    let checked = simple_increasing_function.get_checked(&x).unwrap();
+    let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+    let borrowed = OneDLookUpTableRef::new(&xs, &xs).unwrap().get_checked(&x).unwrap();
+
+    assert!((checked.value - expected).abs() < 0.000001);
+    assert_eq!(checked.range, [range]);
+    assert_eq!(checked.is_out_of_range(), range.is_out_of_range());
+    assert_eq!(borrowed, checked);
+}
+
+#[rstest]
+fn when_checked_lookup_is_out_of_range_with_error_policy_then_report_error(mut simple_increasing_function: IncrFunc) {
+    simple_increasing_function.set_extrapolation(Extrapolation::Error);
+
+    assert_eq!(
+        simple_increasing_function.get_checked(&6.0),
+        Err(LookUpError::OutOfRange { axis: 0 })
+    );
+}
+
+#[rstest]
+fn when_values_are_monotonic_then_inverse_round_trips(
+    #[values(
+        Interpolation::Linear,
+        Interpolation::CubicSpline(SplineBoundary::Natural),
+        Interpolation::Pchip,
+        Interpolation::Akima,
+        Interpolation::Makima
+    )]
+    method: Interpolation,
+    #[values([90.0, 140.0, 175.0, 190.0, 200.0], [200.0, 190.0, 175.0, 140.0, 90.0])] ys: [f64; 5],
+) {
+    let xs = [800.0, 1500.0, 2500.0, 4000.0, 6000.0];
+    let lut = OneDLookUpTable::with_interpolation(xs, ys, method).unwrap();
+    let borrowed = OneDLookUpTableRef::with_interpolation(&xs, &ys, method).unwrap();
+
+    for y in [90.0, 100.0, 140.0, 150.5, 199.9, 200.0] {
+        let x = lut.try_inverse(&y).unwrap();
+        assert!((lut.get(&x) - y).abs() < 1e-9, "{y} {x}");
+        assert_eq!(borrowed.inverse(&y), x);
+    }
+    for (x, y) in xs.iter().zip(ys) {
+        assert_eq!(lut.inverse(&y), *x);
+    }
+}
+
+#[rstest]
+fn when_values_are_linear_then_inverse_is_exact(simple_increasing_function: IncrFunc) {
+    let lut = OneDLookUpTable::new([0.0, 1.0, 4.0], [10.0, 20.0, 35.0]).unwrap();
+
+    assert_eq!(lut.inverse(&15.0), 0.5);
+    assert_eq!(lut.inverse(&30.0), 3.0);
+    assert_eq!(simple_increasing_function.inverse(&2.25), 2.25);
+}
+
+#[rstest]
+#[case(0.5)]
+#[case(5.5)]
+#[case(f64::NAN)]
+fn when_value_is_out_of_range_then_dont_inverse(simple_increasing_function: IncrFunc, #[case] y: f64) {
+    assert_eq!(simple_increasing_function.try_inverse(&y), Err(LookUpError::ValueOutOfRange));
+    assert!(simple_increasing_function.inverse(&y).is_nan());
+}
+
+#[rstest]
+fn when_values_are_not_monotonic_then_dont_inverse(random_function: RandFunc) {
+    let flat = OneDLookUpTable::new([1.0, 2.0, 3.0], [1.0, 2.0, 2.0]).unwrap();
+    let steps = OneDLookUpTable::with_interpolation([1.0, 2.0, 3.0], [1.0, 2.0, 3.0], Interpolation::Previous).unwrap();
+
+    assert_eq!(random_function.try_inverse(&5.0), Err(LookUpError::NotMonotonic));
+    assert_eq!(flat.try_inverse(&1.5), Err(LookUpError::NotMonotonic));
+    assert_eq!(steps.try_inverse(&1.5), Err(LookUpError::NotMonotonic));
+    assert_eq!(steps.inverse_all(&1.5).count(), 0);
+}
+
+#[rstest]
+#[case(5.0, vec![1.75, 4.5, 9.0 + 20.0 / 7.0])]
+#[case(8.0, vec![1.0, 8.0, 9.0 + 8.0 / 7.0])]
+#[case(2.0, vec![20.0])]
+#[case(11.0, vec![])]
+fn when_values_are_not_monotonic_then_find_all_inverses(
+    random_function: RandFunc,
+    #[case] y: f64,
+    #[case] expected: Vec<f64>,
+) {
+    let xs = [1.0, 2.0, 7.0, 9.0, 13.0, 20.0];
+    let ys = [8.0, 4.0, 6.0, 10.0, 3.0, 2.0];
+    let borrowed = OneDLookUpTableRef::new(&xs, &ys).unwrap();
+
+    let solutions: Vec<f64> = random_function.inverse_all(&y).collect();
+    assert_eq!(solutions.len(), expected.len(), "{solutions:?}");
+    for (x, e) in solutions.iter().zip(&expected) {
+        assert!((x - e).abs() < 1e-12, "{solutions:?}");
+        assert!((random_function.get(x) - y).abs() < 1e-12);
+    }
+    assert_eq!(borrowed.inverse_all(&y).collect::<Vec<_>>(), solutions);
+}
+
+#[test]
+fn when_cubic_turns_back_between_breakpoints_then_dont_inverse() {
+    // The values at the breakpoints rise, but the natural spline dips between 0.8 and 1.5.
+    let (xs, ys) = ([0.0f64, 1.0, 2.0, 3.0], [0.0, 0.5, 1.0, 10.0]);
+    let spline =
+        OneDLookUpTable::with_interpolation(xs, ys, Interpolation::CubicSpline(SplineBoundary::Natural)).unwrap();
+    let pchip = OneDLookUpTable::with_interpolation(xs, ys, Interpolation::Pchip).unwrap();
+
+    assert_eq!(spline.try_inverse(&0.9), Err(LookUpError::NotMonotonic));
+    assert_eq!(spline.try_inverse(&5.0), Err(LookUpError::NotMonotonic));
+    let x = pchip.try_inverse(&0.9).unwrap();
+    assert!((pchip.get(&x) - 0.9).abs() < 1e-9);
+}
+
+#[rstest]
+#[case([0.0, 0.5, 1.0, 10.0], 0.3, 3)]
+#[case([0.0, 0.5, 1.0, 10.0], 0.5, 3)]
+#[case([0.0, 10.0, 10.0, 0.0], 10.5, 2)]
+#[case([0.0, 10.0, 10.0, 0.0], 10.0, 2)]
+#[case([0.0, 10.0, 10.0, 0.0], 12.0, 0)]
+fn when_cubic_overshoots_within_a_segment_then_find_all_inverses(
+    #[case] ys: [f64; 4],
+    #[case] y: f64,
+    #[case] count: usize,
+) {
+    let xs = [0.0, 1.0, 2.0, 3.0];
+    let lut = OneDLookUpTable::with_interpolation(xs, ys, Interpolation::CubicSpline(SplineBoundary::Natural)).unwrap();
+    let borrowed =
+        OneDLookUpTableRef::with_interpolation(&xs, &ys, Interpolation::CubicSpline(SplineBoundary::Natural)).unwrap();
+
+    let solutions: Vec<f64> = lut.inverse_all(&y).collect();
+    assert_eq!(solutions.len(), count, "{solutions:?}");
+    assert!(solutions.windows(2).all(|pair| pair[0] < pair[1]), "{solutions:?}");
+    for x in &solutions {
+        assert!((lut.get(x) - y).abs() < 1e-9, "{solutions:?}");
+    }
+    assert_eq!(borrowed.inverse_all(&y).collect::<Vec<_>>(), solutions);
+}
+
+#[rstest]
+fn when_interpolation_is_steps_then_find_the_breakpoints_at_the_value(
+    #[values(Interpolation::Previous, Interpolation::Next, Interpolation::Nearest)] method: Interpolation,
+) {
+    let lut = OneDLookUpTable::with_interpolation([1.0, 2.0, 3.0, 4.0], [1.0, 2.0, 1.0, 2.0], method).unwrap();
+
+    assert_eq!(lut.inverse_all(&2.0).collect::<Vec<_>>(), [2.0, 4.0]);
+    assert_eq!(lut.inverse_all(&1.0).collect::<Vec<_>>(), [1.0, 3.0]);
+    assert_eq!(lut.inverse_all(&1.5).count(), 0);
+    assert_eq!(lut.try_inverse(&2.0), Err(LookUpError::NotMonotonic));
+}
+
+#[rstest]
+#[case(Interpolation::Linear, 4.5)]
+#[case(Interpolation::CubicSpline(SplineBoundary::Natural), 4.5)]
+#[case(Interpolation::CubicSpline(SplineBoundary::Clamped(1.0, -2.0)), 15.25)]
+#[case(Interpolation::Pchip, 8.0)]
+#[case(Interpolation::Akima, 11.3)]
+#[case(Interpolation::Makima, 1.6)]
+fn when_index_is_between_breakpoints_then_derivative_matches_finite_difference(
+    #[case] interpolation: Interpolation,
+    #[case] x: f64,
+) {
+    let xs = [1.0, 2.0, 7.0, 9.0, 13.0, 20.0];
+    let lut = OneDLookUpTable::with_interpolation(xs, [8.0, 4.0, 6.0, 10.0, 3.0, 2.0], interpolation).unwrap();
+    let h = 1e-6;
+
+    let expected = (lut.get(&(x + h)) - lut.get(&(x - h))) / (2.0 * h);
+    assert!((lut.derivative(&x) - expected).abs() < 1e-6);
+}
+
+#[rstest]
+#[case(Interpolation::CubicSpline(SplineBoundary::Natural))]
+#[case(Interpolation::Pchip)]
+#[case(Interpolation::Akima)]
+#[case(Interpolation::Makima)]
+fn when_interpolation_is_cubic_then_derivative_is_continuous_across_breakpoints(#[case] interpolation: Interpolation) {
+    let lut =
+        OneDLookUpTable::with_interpolation([1.0, 2.0, 7.0, 9.0, 13.0], [8.0, 4.0, 6.0, 10.0, 3.0], interpolation)
+            .unwrap();
+
+    for x in [2.0, 7.0, 9.0] {
+        let on = lut.derivative(&x);
+        assert!((lut.derivative(&(x - 1e-9)) - on).abs() < 1e-6);
+        assert!((lut.derivative(&(x + 1e-9)) - on).abs() < 1e-6);
+    }
+}
+
+#[rstest]
+#[case(1.0, -4.0)]
+#[case(2.0, 0.4)]
+#[case(19.0, -1.0 / 7.0)]
+#[case(20.0, -1.0 / 7.0)]
+fn when_interpolation_is_linear_then_derivative_on_breakpoint_is_slope_to_the_right(
+    random_function: RandFunc,
+    #[case] x: f64,
+    #[case] expected: f64,
+) {
+    assert!((random_function.derivative(&x) - expected).abs() < 1e-12);
+}
+
+#[rstest]
+#[case(Interpolation::Previous, 3.0)]
+#[case(Interpolation::Next, 2.0)]
+#[case(Interpolation::Nearest, 3.5)]
+fn when_interpolation_is_step_then_derivative_is_zero(#[case] interpolation: Interpolation, #[case] x: f64) {
+    let lut = OneDLookUpTable::with_interpolation([1.0, 2.0, 3.0, 4.0, 5.0], [1.0, 4.0, 2.0, 5.0, 3.0], interpolation)
+        .unwrap();
+
+    assert_eq!(lut.derivative(&x), 0.0);
+}
+
+#[rstest]
+#[case(Extrapolation::Clamp, -1.0, 0.0)]
+#[case(Extrapolation::Linear, -1.0, -4.0)]
+#[case(Extrapolation::Linear, 25.0, -1.0 / 7.0)]
+#[case(Extrapolation::Constant(42.0), 25.0, 0.0)]
+#[case(Extrapolation::Periodic, 20.5, -4.0)]
+fn when_index_is_outside_interval_then_differentiate_extrapolated_values(
+    mut random_function: RandFunc,
+    #[case] extrapolation: Extrapolation,
+    #[case] x: f64,
+    #[case] expected: f64,
+) {
+    random_function.set_extrapolation(extrapolation);
+
+    assert!((random_function.derivative(&x) - expected).abs() < 1e-12);
+}
+
+#[rstest]
+fn when_extrapolation_is_nan_or_error_then_derivative_is_undefined(mut random_function: RandFunc) {
+    random_function.set_extrapolation(Extrapolation::NaN);
+    assert!(random_function.derivative(&0.0).is_nan());
+
+    random_function.set_extrapolation(Extrapolation::Error);
+    assert_eq!(random_function.try_derivative(&0.0), Err(LookUpError::OutOfRange { axis: 0 }));
+    assert!(random_function.derivative(&0.0).is_nan());
+}
+
+#[test]
+fn when_ref_table_is_differentiated_then_match_owned_table() {
+    let (xs, ys) = ([1.0, 2.0, 7.0, 9.0, 13.0, 20.0], [8.0, 4.0, 6.0, 10.0, 3.0, 2.0]);
+    let owned = OneDLookUpTable::with_interpolation(xs, ys, Interpolation::Pchip).unwrap();
+    let borrowed = OneDLookUpTableRef::with_interpolation(&xs, &ys, Interpolation::Pchip).unwrap();
+
+    for x in [1.0, 3.3, 9.0, 17.5] {
+        assert_eq!(borrowed.derivative(&x), owned.derivative(&x));
+    }
+}
@@ -0,0 +1,139 @@
+use look_up_table::{ConstructionError, DynOneDLookUpTable, DynTwoDLookUpTable, OneDLookUpTable, TwoDLookUpTable};
+use rstest::rstest;
+
+#[test]
+fn when_function_is_sampled_uniformly_then_table_matches_the_samples() {
+    let lut = OneDLookUpTable::new([0.0, 1.0, 2.0, 3.0, 4.0], [0.0, 1.0, 4.0, 9.0, 16.0]).unwrap();
+    let sampled = OneDLookUpTable::<5>::from_fn(|x| x * x, 0.0..=4.0).unwrap();
+
+    for x in [-1.0, 0.0, 0.5, 2.2, 3.9, 5.0] {
+        assert_eq!(sampled.get(&x), lut.get(&x));
+    }
+}
+
+#[test]
+fn when_surface_is_sampled_uniformly_then_table_matches_the_samples() {
+    let lut = TwoDLookUpTable::new([0.0, 1.0, 2.0], [0.0, 2.0], [[0.0, 2.0], [1.0, 3.0], [4.0, 6.0]]).unwrap();
+    let sampled = TwoDLookUpTable::<3, 2>::from_fn(|x, y| x * x + y, 0.0..=2.0, 0.0..=2.0).unwrap();
+
+    for (x, y) in [(0.0, 0.0), (0.5, 1.5), (1.7, 0.3), (3.0, -1.0)] {
+        assert_eq!(sampled.get(&x, &y), lut.get(&x, &y));
+    }
+}
+
+#[test]
+fn when_domain_is_reversed_then_dont_sample() {
+    let lut = OneDLookUpTable::<3>::from_fn(|x| x, 1.0..=0.0);
+    let surface = TwoDLookUpTable::<2, 2>::from_fn(|x, y| x + y, 0.0..=1.0, 0.0..=0.0);
+
+    assert!(matches!(lut.err().unwrap(), ConstructionError::IncreasingDimOrderError));
+    assert!(matches!(surface.err().unwrap(), ConstructionError::IncreasingDimOrderError));
+}
+
+#[rstest]
+#[case(f64::sin, 0.0, 6.0, 1e-3)]
+#[case(f64::exp, 0.0, 4.0, 1e-2)]
+#[case(|x: f64| x * x * x - 2.0 * x, -2.0, 2.0, 1e-4)]
+fn when_function_is_sampled_adaptively_then_error_is_within_tolerance(
+    #[case] f: fn(f64) -> f64,
+    #[case] start: f64,
+    #[case] end: f64,
+    #[case] tolerance: f64,
+) {
+    let lut = DynOneDLookUpTable::from_fn_adaptive(f, start..=end, tolerance).unwrap();
+
+    for pair in lut.breakpoints().windows(2) {
+        let x = (pair[0] + pair[1]) / 2.0;
+        assert!((lut.get(&x) - f(x)).abs() <= tolerance, "{x}");
+    }
+    for k in 0..=1000 {
+        let x = start + (end - start) * k as f64 / 1000.0;
+        assert!((lut.get(&x) - f(x)).abs() <= 2.0 * tolerance, "{x}");
+    }
+}
+
+#[test]
+fn when_function_is_sampled_adaptively_then_breakpoints_follow_the_curvature() {
+    let lut = DynOneDLookUpTable::from_fn_adaptive(f64::exp, 0.0..=4.0, 1e-2).unwrap();
+    let line = DynOneDLookUpTable::from_fn_adaptive(|x| 3.0 * x - 1.0, 0.0..=4.0, 1e-9).unwrap();
+
+    let xs = lut.breakpoints();
+    let count = |from: f64, to: f64| xs.iter().filter(|&&x| from <= x && x < to).count();
+    assert!(count(3.0, 4.0) > 2 * count(0.0, 1.0), "{xs:?}");
+    assert_eq!(line.breakpoints(), [0.0, 1.0, 2.0, 3.0, 4.0]);
+}
+
+#[test]
+fn when_surface_is_sampled_adaptively_then_error_is_within_tolerance() {
+    let f = |x: f64, y: f64| x * x + (3.0 * y).cos();
+    let lut = DynTwoDLookUpTable::from_fn_adaptive(f, -1.0..=1.0, 0.0..=2.0, 1e-2).unwrap();
+
+    for i in 0..=100 {
+        for j in 0..=100 {
+            let (x, y) = (-1.0 + i as f64 / 50.0, j as f64 / 50.0);
+            assert!((lut.get(&x, &y) - f(x, y)).abs() <= 2e-2, "{x}, {y}");
+        }
+    }
+}
+
+#[test]
+fn when_surface_is_linear_along_an_axis_then_dont_refine_it() {
+    let lut = DynTwoDLookUpTable::from_fn_adaptive(|x: f64, y| x.exp() * y, 0.0..=2.0, 0.0..=1.0, 1e-3).unwrap();
+
+    let [xs, ys] = lut.axes();
+    assert!(xs.len() > 5);
+    assert_eq!(ys, [0.0, 0.25, 0.5, 0.75, 1.0]);
+}
+
+#[test]
+fn when_function_is_sampled_adaptively_then_invert_and_differentiate_the_table() {
+    let lut = DynOneDLookUpTable::from_fn_adaptive(f64::exp, 0.0..=2.0, 1e-4).unwrap();
+    let surface = DynTwoDLookUpTable::from_fn_adaptive(|x, y: f64| x * y.exp(), 1.0..=2.0, 0.0..=2.0, 1e-3).unwrap();
+
+    assert!((lut.inverse(&3.0) - 3.0f64.ln()).abs() < 1e-3);
+    assert!((lut.derivative(&1.0) - 1.0f64.exp()).abs() < 5e-2);
+    assert!((surface.inverse_y(&1.5, &6.0) - 4.0f64.ln()).abs() < 1e-3);
+    let [dx, dy] = surface.gradient(&1.5, &1.0);
+    assert!((dx - 1.0f64.exp()).abs() < 5e-2, "{dx}");
+    assert!((dy - 1.5 * 1.0f64.exp()).abs() < 1e-1, "{dy}");
+}
+
+#[test]
+fn when_function_is_not_finite_then_dont_sample_it() {
+    let lut = DynOneDLookUpTable::from_fn_adaptive(|x: f64| 1.0 / x, -1.0..=1.0, 1e-3);
+    let surface = DynTwoDLookUpTable::from_fn_adaptive(|x: f64, y: f64| (x - y).sqrt(), 0.0..=1.0, 0.0..=1.0, 1e-3);
+    let tolerance = DynOneDLookUpTable::from_fn_adaptive(|x| x, 0.0..=1.0, f64::NAN);
+
+    assert!(matches!(lut.err().unwrap(), ConstructionError::ContainingNansOrInfinities));
+    assert!(matches!(surface.err().unwrap(), ConstructionError::ContainingNansOrInfinities));
+    assert!(matches!(
+        tolerance.err().unwrap(),
+        ConstructionError::ContainingNansOrInfinities
+    ));
+}
+
+#[rstest]
+#[case(-1e-3)]
+#[case(f64::NEG_INFINITY)]
+fn when_tolerance_is_negative_then_dont_sample(#[case] tolerance: f64) {
+    let lut = DynOneDLookUpTable::from_fn_adaptive(f64::sin, 0.0..=6.0, tolerance);
+    let surface = DynTwoDLookUpTable::from_fn_adaptive(|x, y| x * y, 0.0..=1.0, 0.0..=1.0, tolerance);
+
+    assert!(matches!(lut.err().unwrap(), ConstructionError::NegativeTolerance));
+    assert!(matches!(surface.err().unwrap(), ConstructionError::NegativeTolerance));
+}
+
+#[test]
+fn when_dynamic_surface_is_invalid_then_report_same_errors_as_owned_tables() {
+    let single: [&[f64]; 1] = [&[1.0]];
+    let nan: [&[f64]; 1] = [&[1.0, f64::NAN]];
+
+    assert!(matches!(
+        DynTwoDLookUpTable::new(&[1.0], &[1.0, 2.0], &single).unwrap_err(),
+        ConstructionError::MinLengthError
+    ));
+    assert!(matches!(
+        DynTwoDLookUpTable::new(&[1.0, 2.0], &[1.0, 2.0], &nan).unwrap_err(),
+        ConstructionError::ContainingNansOrInfinities
+    ));
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "serde")]
+
+use look_up_table::{
+    AxisInterpolation, DynOneDLookUpTable, DynTwoDLookUpTable, Extrapolation, Interpolation, NDLookUpTable, NoCache,
+    OneDLookUpTable, SplineBoundary, TwoDLookUpTable,
+};
+use rstest::rstest;
+
+const SPEED: [f64; 5] = [800.0, 1500.0, 2500.0, 4000.0, 6000.0];
+const TORQUE: [f64; 5] = [90.0, 140.0, 175.0, 160.0, 120.0];
+const LOAD: [f64; 3] = [0.0, 0.5, 1.0];
+const MAP: [[f64; 3]; 5] = [
+    [1.0, 2.0, 4.0],
+    [2.0, 3.5, 6.0],
+    [3.0, 5.0, 8.5],
+    [4.0, 7.0, 11.0],
+    [5.0, 9.0, 14.0],
+];
+
+#[rstest]
+fn when_one_d_table_is_round_tripped_then_lookups_are_identical(
+    #[values(
+        Interpolation::Linear,
+        Interpolation::CubicSpline(SplineBoundary::Clamped(0.1, -0.02)),
+        Interpolation::Makima
+    )]
+    method: Interpolation,
+    #[values(Extrapolation::Clamp, Extrapolation::Constant(-1.0), Extrapolation::Periodic)]
+    extrapolation: Extrapolation,
+) {
+    let mut lut = OneDLookUpTable::with_interpolation(SPEED, TORQUE, method).unwrap();
+    lut.set_extrapolation(extrapolation);
+    lut.get(&1000.0);
+
+    let json = serde_json::to_string(&lut).unwrap();
+    let copy: OneDLookUpTable<5> = serde_json::from_str(&json).unwrap();
+
+    assert!(!json.contains("cache"));
+    for x in [0.0, 800.0, 1000.0, 3333.0, 7000.0] {
+        assert_eq!(copy.get(&x), lut.get(&x));
+    }
+}
+
+#[test]
+fn when_two_d_table_is_round_tripped_then_lookups_are_identical() {
+    let mut lut = TwoDLookUpTable::new(SPEED, LOAD, MAP).unwrap();
+    lut.set_axis_interpolation(AxisInterpolation::Cubic, AxisInterpolation::Previous);
+    lut.set_extrapolation(Extrapolation::Linear, Extrapolation::NaN);
+
+    let json = serde_json::to_string(&lut).unwrap();
+    let copy: TwoDLookUpTable<5, 3, f64, NoCache> = serde_json::from_str(&json).unwrap();
+
+    for (x, y) in [(1000.0, 0.25), (2500.0, 0.5), (7000.0, 0.9), (3000.0, 2.0)] {
+        assert_eq!(copy.get(&x, &y).to_bits(), lut.get(&x, &y).to_bits());
+    }
+}
+
+#[test]
+fn when_n_d_table_is_round_tripped_then_lookups_are_identical() {
+    let mut lut = NDLookUpTable::new([&SPEED, &LOAD], MAP.as_flattened()).unwrap();
+    lut.set_extrapolation([Extrapolation::Linear, Extrapolation::Clamp]);
+
+    let json = serde_json::to_string(&lut).unwrap();
+    let copy: NDLookUpTable<2> = serde_json::from_str(&json).unwrap();
+
+    for index in [[1000.0, 0.25], [2500.0, 0.5], [7000.0, 1.5]] {
+        assert_eq!(copy.get(&index), lut.get(&index));
+    }
+}
+
+#[test]
+fn when_dynamic_tables_are_round_tripped_then_lookups_are_identical() {
+    let mut curve = DynOneDLookUpTable::with_interpolation(&SPEED, &TORQUE, Interpolation::Pchip).unwrap();
+    curve.set_extrapolation(Extrapolation::Linear);
+    let rows: Vec<&[f64]> = MAP.iter().map(|row| &row[..]).collect();
+    let mut surface = DynTwoDLookUpTable::new(&SPEED, &LOAD, &rows).unwrap();
+    surface.set_axis_interpolation(AxisInterpolation::Cubic, AxisInterpolation::Nearest);
+    surface.set_extrapolation(Extrapolation::Clamp, Extrapolation::Constant(-1.0));
+
+    let curve_copy: DynOneDLookUpTable = serde_json::from_str(&serde_json::to_string(&curve).unwrap()).unwrap();
+    let json = serde_json::to_string(&surface).unwrap();
+    let surface_copy: DynTwoDLookUpTable<f64, NoCache> = serde_json::from_str(&json).unwrap();
+
+    for (x, y) in [(0.0, 0.25), (1000.0, 0.5), (3333.0, 0.9), (7000.0, 2.0)] {
+        assert_eq!(curve_copy.get(&x), curve.get(&x));
+        assert_eq!(surface_copy.get(&x, &y), surface.get(&x, &y));
+    }
+    // The same form as the owned tables.
+    let owned: TwoDLookUpTable<5, 3> = serde_json::from_str(&json).unwrap();
+    assert_eq!(owned.get(&3333.0, &0.9), surface.get(&3333.0, &0.9));
+}
+
+#[rstest]
+#[case(
+    r#"{ "x": [1.0, 2.0], "y": [1.0, 2.0], "surface": [[1.0, 2.0], [3.0]] }"#,
+    "Expected 2 values in row 1"
+)]
+#[case(
+    r#"{ "x": [1.0, 2.0], "y": [1.0, 2.0], "surface": [[1.0, 2.0]] }"#,
+    "Expected 2 rows"
+)]
+#[case(
+    r#"{ "x": [1.0, 2.0], "y": [1.0, 1.0], "surface": [[1.0, 2.0], [3.0, 4.0]] }"#,
+    "strictly increasing order"
+)]
+fn when_serialized_dynamic_surface_is_invalid_then_dont_deserialize(#[case] json: &str, #[case] message: &str) {
+    let err = serde_json::from_str::<DynTwoDLookUpTable>(json).unwrap_err();
+
+    assert!(err.to_string().contains(message), "{err}");
+}
+
+#[test]
+fn when_configuration_is_left_out_then_use_defaults() {
+    let lut: TwoDLookUpTable<2, 2> =
+        serde_json::from_str(r#"{ "x": [1.0, 2.0], "y": [1.0, 2.0], "surface": [[1.0, 2.0], [3.0, 4.0]] }"#).unwrap();
+
+    assert_eq!(lut.get(&1.5, &1.5), 2.5);
+    assert_eq!(lut.get(&5.0, &5.0), 4.0);
+}
+
+#[rstest]
+#[case(r#"{ "x": [1.0, 1.0, 2.0], "y": [1.0, 2.0, 3.0] }"#, "strictly increasing order")]
+#[case(r#"{ "x": [1.0, 2.0, 3.0], "y": [1.0, 2.0] }"#, "a sequence of 3 values")]
+#[case(r#"{ "x": [1.0, 2.0, 3.0], "y": [1.0, 2.0, 3.0, 4.0] }"#, "a sequence of 3 values")]
+fn when_serialized_table_is_invalid_then_dont_deserialize(#[case] json: &str, #[case] message: &str) {
+    let err = serde_json::from_str::<OneDLookUpTable<3>>(json).unwrap_err();
+
+    assert!(err.to_string().contains(message), "{err}");
+}
+
+#[test]
+fn when_serialized_surface_is_invalid_then_dont_deserialize() {
+    let json = r#"{ "axes": [[0.0, 1.0], [0.0, 1.0]], "values": [1.0, 2.0, 3.0] }"#;
+    let err = serde_json::from_str::<NDLookUpTable<2>>(json).unwrap_err();
+
+    assert!(err.to_string().contains("Expected 4 values"), "{err}");
+}
@@ -1,4 +1,7 @@
-use look_up_table::{ConstructionError, TwoDLookUpTable};
+use look_up_table::{
+    AxisInterpolation, AxisRange, ConstructionError, DynTwoDLookUpTable, Extrapolation, LookUpError,
+    SurfaceInterpolation, SurfaceOrientation, TwoDLookUpTable, TwoDLookUpTableRef,
+};
 use rstest::{fixture, rstest};
 type IncrSurface = TwoDLookUpTable<5, 5>;
 
@@ -21,9 +24,9 @@ fn simple_increasing_surface() -> IncrSurface {
 // All the corner out of bounds should produce corner values.
 #[rstest]
 #[case(-2.0, -3.0, 1.0)]
-#[case(7.0, -1.0, 5.0)]
+#[case(7.0, -1.0, 21.0)]
 #[case(10.0, 10.0, 25.0)]
-#[case(-3.0, 8.0, 21.0)]
+#[case(-3.0, 8.0, 5.0)]
 fn when_x_y_values_are_out_of_bounds_return_corner_values(
     simple_increasing_surface: IncrSurface,
     #[case] x: f64,
@@ -58,7 +61,8 @@ fn when_x_or_y_surface_values_contain_nans_or_infinities_dont_construct_object()
 
 #[test]
 fn when_x_y_values_are_within_bounds_then_perform_bilinear_interpolation() {
-    let lut = TwoDLookUpTable::new([14.0, 15.0], [20.0, 21.0], [[91.0, 210.0], [162.0, 95.0]]).unwrap();
+    let lut: TwoDLookUpTable<2, 2> =
+        TwoDLookUpTable::new([14.0, 15.0], [20.0, 21.0], [[91.0, 210.0], [162.0, 95.0]]).unwrap();
 
     let expected = 131.7;
     let actual = lut.get(&14.5, &20.2);
@@ -66,4 +70,508 @@ fn when_x_y_values_are_within_bounds_then_perform_bilinear_interpolation() {
     assert!((actual - expected).abs() < 0.00001);
 }
 
-// TODO: Write tests for interpolation in single direction.
+#[rstest]
+#[case(2.0, 3.5, 8.5)]
+#[case(2.5, 3.0, 10.5)]
+#[case(1.0, 5.0, 5.0)]
+fn when_one_index_is_on_a_breakpoint_then_interpolate_in_other_direction(
+    simple_increasing_surface: IncrSurface,
+    #[case] x: f64,
+    #[case] y: f64,
+    #[case] expected: f64,
+) {
+    let actual = simple_increasing_surface.get(&x, &y);
+
+    assert!((actual - expected).abs() < 0.000001);
+}
+
+#[test]
+fn when_surface_is_not_square_then_rows_correspond_to_x_values() {
+    let lut = TwoDLookUpTable::new([1.0f64, 2.0, 3.0], [10.0, 20.0], [[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]).unwrap();
+
+    assert!((lut.get(&3.0, &10.0) - 5.0).abs() < f64::EPSILON);
+    assert!((lut.get(&2.5, &15.0) - 4.5).abs() < 0.000001);
+}
+
+#[rstest]
+#[case(Extrapolation::Clamp, Extrapolation::Clamp, 0.0, 3.0, 3.0)]
+#[case(Extrapolation::Linear, Extrapolation::Clamp, 0.0, 3.0, -2.0)]
+#[case(Extrapolation::Linear, Extrapolation::Linear, 6.0, 6.0, 31.0)]
+#[case(Extrapolation::Periodic, Extrapolation::Clamp, 6.0, 2.0, 7.0)]
+#[case(Extrapolation::Constant(-1.0), Extrapolation::Clamp, 6.0, 2.0, -1.0)]
+#[case(Extrapolation::Clamp, Extrapolation::Constant(-1.0), 6.0, 2.0, 22.0)]
+fn when_indices_are_out_of_bounds_then_apply_extrapolation_policy_per_axis(
+    mut simple_increasing_surface: IncrSurface,
+    #[case] x_policy: Extrapolation,
+    #[case] y_policy: Extrapolation,
+    #[case] x: f64,
+    #[case] y: f64,
+    #[case] expected: f64,
+) {
+    simple_increasing_surface.set_extrapolation(x_policy, y_policy);
+    let actual = simple_increasing_surface.get(&x, &y);
+
+    assert!((actual - expected).abs() < 0.000001);
+}
+
+#[rstest]
+fn when_extrapolation_policy_is_error_then_report_out_of_range_axis(mut simple_increasing_surface: IncrSurface) {
+    simple_increasing_surface.set_extrapolation(Extrapolation::Clamp, Extrapolation::Error);
+
+    assert_eq!(
+        simple_increasing_surface.try_get(&9.0, &0.0),
+        Err(LookUpError::OutOfRange { axis: 1 })
+    );
+    assert!(simple_increasing_surface.get(&2.0, &9.0).is_nan());
+    assert!((simple_increasing_surface.try_get(&9.0, &2.0).unwrap() - 22.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn when_ref_surface_has_rows_along_y_then_interpolate_along_matching_axes() {
+    let xs = [1.0, 2.0, 3.0];
+    let ys = [10.0, 20.0];
+    let rows: [&[f64]; 2] = [&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]];
+    let mut lut = TwoDLookUpTableRef::new(&xs, &ys, &rows, SurfaceOrientation::RowPerY).unwrap();
+
+    assert!((lut.get(&3.0, &10.0) - 3.0).abs() < f64::EPSILON);
+    assert!((lut.get(&2.5, &15.0) - 4.0).abs() < 0.000001);
+
+    lut.set_extrapolation(Extrapolation::NaN, Extrapolation::Clamp);
+    assert!(lut.get(&0.0, &15.0).is_nan());
+}
+
+#[rstest]
+#[case(14.5, 20.2)]
+#[case(13.0, 20.7)]
+#[case(14.9, 22.0)]
+fn when_surface_is_single_precision_then_match_double_precision_surface(#[case] x: f64, #[case] y: f64) {
+    let single = TwoDLookUpTable::new([14.0f32, 15.0], [20.0, 21.0], [[91.0, 210.0], [162.0, 95.0]]).unwrap();
+    let double = TwoDLookUpTable::new([14.0, 15.0], [20.0, 21.0], [[91.0, 210.0], [162.0, 95.0]]).unwrap();
+
+    assert!((single.get(&(x as f32), &(y as f32)) as f64 - double.get(&x, &y)).abs() < 1e-4);
+}
+
+// f(x, y) = (x - 2)^2 + xy - y on a uniform grid, which is reproduced by bicubic interpolation in the cells
+// where both the breakpoints of the quadratic axis are interior, since the central differences are exact.
+fn quadratic_surface() -> TwoDLookUpTable<6, 3> {
+    let xs = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    let ys = [-1.0, 0.0, 2.0];
+    let mut lut = TwoDLookUpTable::new(xs, ys, xs.map(|x| ys.map(|y| (x - 2.0) * (x - 2.0) + x * y - y))).unwrap();
+    lut.set_interpolation(SurfaceInterpolation::Bicubic);
+
+    lut
+}
+
+#[rstest]
+#[case(1.5, -0.5)]
+#[case(2.25, 1.0)]
+#[case(3.9, 1.7)]
+#[case(2.0, 0.0)]
+fn when_interpolation_is_bicubic_then_reproduce_quadratic_surface(#[case] x: f64, #[case] y: f64) {
+    let actual = quadratic_surface().get(&x, &y);
+
+    assert!((actual - ((x - 2.0) * (x - 2.0) + x * y - y)).abs() < 0.000001);
+}
+
+#[test]
+fn when_interpolation_is_bicubic_then_gradient_is_continuous_across_grid_lines() {
+    let mut lut = TwoDLookUpTable::new(
+        [0.0, 1.0, 3.0, 4.0],
+        [0.0, 1.0, 2.0],
+        [[0.0, 2.0, 1.0], [3.0, -1.0, 4.0], [1.0, 5.0, 2.0], [2.0, 0.0, 3.0]],
+    )
+    .unwrap();
+    let h = 0.000001;
+    let slope = |lut: &TwoDLookUpTable<4, 3>, x: f64| (lut.get(&(x + h), &0.5) - lut.get(&(x - h), &0.5)) / (2.0 * h);
+
+    // Bilinear interpolation has a kink along the grid line x = 1.
+    assert!((slope(&lut, 1.0 - 0.001) - slope(&lut, 1.0 + 0.001)).abs() > 1.0);
+
+    lut.set_interpolation(SurfaceInterpolation::Bicubic);
+    assert!((slope(&lut, 1.0 - 0.001) - slope(&lut, 1.0 + 0.001)).abs() < 0.05);
+}
+
+#[rstest]
+fn when_interpolation_is_bicubic_then_match_breakpoints_and_clamp_to_edges(mut simple_increasing_surface: IncrSurface) {
+    let mut quadratic = quadratic_surface();
+    simple_increasing_surface.set_interpolation(SurfaceInterpolation::Bicubic);
+
+    assert_eq!(quadratic.get(&3.0, &2.0), 5.0);
+    assert_eq!(quadratic.get(&8.0, &2.0), quadratic.get(&5.0, &2.0));
+    assert_eq!(simple_increasing_surface.get(&-2.0, &-3.0), 1.0);
+    assert!((simple_increasing_surface.get(&2.5, &4.5) - 12.0).abs() < 0.000001);
+
+    // The linear extrapolation continues the boundary segment.
+    quadratic.set_extrapolation(Extrapolation::Linear, Extrapolation::Clamp);
+    let (at_edge, slope) = (quadratic.get(&5.0, &0.0), quadratic.get(&5.0, &0.0) - quadratic.get(&4.0, &0.0));
+    assert!((quadratic.get(&6.5, &0.0) - (at_edge + 1.5 * slope)).abs() < 0.000001);
+}
+
+#[rstest]
+#[case(0.0, 0.0)]
+#[case(0.3, 1.2)]
+#[case(2.6, 1.9)]
+#[case(4.0, 0.5)]
+fn when_interpolation_is_bicubic_then_ref_matches_owned_table(#[case] x: f64, #[case] y: f64) {
+    let xs = [0.0, 1.0, 3.0, 4.0];
+    let ys = [0.0, 1.0, 2.0];
+    let surface = [[0.0, 2.0, 1.0], [3.0, -1.0, 4.0], [1.0, 5.0, 2.0], [2.0, 0.0, 3.0]];
+    let rows: Vec<&[f64]> = surface.iter().map(|row| &row[..]).collect();
+    let mut owned = TwoDLookUpTable::new(xs, ys, surface).unwrap();
+    let mut borrowed = TwoDLookUpTableRef::new(&xs, &ys, &rows, SurfaceOrientation::RowPerX).unwrap();
+    owned.set_interpolation(SurfaceInterpolation::Bicubic);
+    borrowed.set_interpolation(SurfaceInterpolation::Bicubic);
+
+    assert!((owned.get(&x, &y) - borrowed.get(&x, &y)).abs() < 0.000001);
+}
+
+// Rows along speed, columns along gear, where the values only make sense at the integral gears.
+fn gear_map() -> TwoDLookUpTable<3, 3> {
+    TwoDLookUpTable::new(
+        [1000.0, 2000.0, 3000.0],
+        [1.0, 2.0, 3.0],
+        [[10.0, 20.0, 30.0], [30.0, 40.0, 50.0], [20.0, 30.0, 40.0]],
+    )
+    .unwrap()
+}
+
+#[rstest]
+#[case(AxisInterpolation::Linear, 1500.0, 2.4, 34.0)]
+#[case(AxisInterpolation::Previous, 1500.0, 2.4, 30.0)]
+#[case(AxisInterpolation::Next, 1500.0, 2.4, 40.0)]
+#[case(AxisInterpolation::Nearest, 1500.0, 2.4, 30.0)]
+#[case(AxisInterpolation::Nearest, 1500.0, 2.5, 40.0)]
+#[case(AxisInterpolation::Next, 2500.0, 2.0, 35.0)]
+#[case(AxisInterpolation::Previous, 2500.0, 5.0, 45.0)]
+fn when_axes_have_different_interpolations_then_apply_them_per_axis(
+    #[case] y_method: AxisInterpolation,
+    #[case] x: f64,
+    #[case] y: f64,
+    #[case] expected: f64,
+) {
+    let mut lut = gear_map();
+    lut.set_axis_interpolation(AxisInterpolation::Linear, y_method);
+
+    assert!((lut.get(&x, &y) - expected).abs() < 0.000001);
+}
+
+#[rstest]
+#[case(1000.0, 1.0)]
+#[case(1700.0, 2.6)]
+#[case(2900.0, 1.2)]
+fn when_axis_interpolations_match_surface_interpolation_then_give_same_values(#[case] x: f64, #[case] y: f64) {
+    let (mut per_axis, mut surface) = (gear_map(), gear_map());
+    per_axis.set_axis_interpolation(AxisInterpolation::Cubic, AxisInterpolation::Cubic);
+    surface.set_interpolation(SurfaceInterpolation::Bicubic);
+
+    assert_eq!(per_axis.get(&x, &y), surface.get(&x, &y));
+}
+
+#[test]
+fn when_ref_table_has_axis_interpolations_then_match_owned_table() {
+    let (xs, ys) = ([1000.0, 2000.0, 3000.0], [1.0, 2.0, 3.0]);
+    // The rows of the surface are the curves for each of the gears.
+    let rows: [&[f64]; 3] = [&[10.0, 30.0, 20.0], &[20.0, 40.0, 30.0], &[30.0, 50.0, 40.0]];
+    let mut borrowed = TwoDLookUpTableRef::new(&xs, &ys, &rows, SurfaceOrientation::RowPerY).unwrap();
+    let mut owned = gear_map();
+    borrowed.set_axis_interpolation(AxisInterpolation::Cubic, AxisInterpolation::Nearest);
+    owned.set_axis_interpolation(AxisInterpolation::Cubic, AxisInterpolation::Nearest);
+
+    for (x, y) in [(1200.0, 1.4), (2600.0, 2.7), (3000.0, 3.0)] {
+        assert!((borrowed.get(&x, &y) - owned.get(&x, &y)).abs() < 0.000001);
+    }
+}
+
+#[test]
+fn when_axis_interpolations_are_given_on_construction_then_match_setter() {
+    let (xs, ys) = ([1000.0, 2000.0, 3000.0], [1.0, 2.0, 3.0]);
+    let surface = [[10.0, 20.0, 30.0], [30.0, 40.0, 50.0], [20.0, 30.0, 40.0]];
+    let rows: [&[f64]; 3] = [&surface[0], &surface[1], &surface[2]];
+    let interpolation = [AxisInterpolation::Cubic, AxisInterpolation::Previous];
+    let owned = TwoDLookUpTable::with_interpolation(xs, ys, surface, interpolation).unwrap();
+    let borrowed =
+        TwoDLookUpTableRef::with_interpolation(&xs, &ys, &rows, SurfaceOrientation::RowPerX, interpolation).unwrap();
+    let dynamic = DynTwoDLookUpTable::with_interpolation(&xs, &ys, &rows, interpolation).unwrap();
+    let mut set = gear_map();
+    set.set_axis_interpolation(AxisInterpolation::Cubic, AxisInterpolation::Previous);
+
+    for (x, y) in [(1200.0, 1.4), (2600.0, 2.7), (3000.0, 3.0)] {
+        assert_eq!(owned.get(&x, &y), set.get(&x, &y));
+        assert_eq!(borrowed.get(&x, &y), set.get(&x, &y));
+        assert_eq!(dynamic.get(&x, &y), set.get(&x, &y));
+    }
+}
+
+#[rstest]
+#[case(0.0, 2.5, 2.5, [AxisRange::Below, AxisRange::Between])]
+#[case(3.0, 9.0, 15.0, [AxisRange::OnBreakpoint, AxisRange::Above])]
+#[case(2.5, 4.0, 11.5, [AxisRange::Between, AxisRange::OnBreakpoint])]
+fn when_lookup_is_checked_then_report_where_indices_lie_per_axis(
+    simple_increasing_surface: IncrSurface,
+    #[case] x: f64,
+    #[case] y: f64,
+    #[case] expected: f64,
+    #[case] range: [AxisRange; 2],
+) {
+    let checked = simple_increasing_surface.get_checked(&x, &y).unwrap();
+
+    assert!((checked.value - expected).abs() < 0.000001);
+    assert_eq!(checked.range, range);
+    assert!(checked.is_out_of_range() == range.iter().any(|r| r.is_out_of_range()));
+}
+
+#[test]
+fn when_ref_lookup_is_checked_then_report_ranges_of_matching_axes() {
+    let (xs, ys) = ([0.0, 1.0, 2.0], [0.0, 10.0]);
+    let rows: [&[f64]; 3] = [&[0.0, 1.0], &[2.0, 3.0], &[4.0, 5.0]];
+    let mut lut = TwoDLookUpTableRef::new(&xs, &ys, &rows, SurfaceOrientation::RowPerX).unwrap();
+
+    let checked = lut.get_checked(&1.5, &-4.0).unwrap();
+    assert!((checked.value - 3.0).abs() < 0.000001);
+    assert_eq!(checked.range, [AxisRange::Between, AxisRange::Below]);
+
+    lut.set_extrapolation(Extrapolation::Clamp, Extrapolation::Error);
+    assert_eq!(lut.get_checked(&1.5, &-4.0), Err(LookUpError::OutOfRange { axis: 1 }));
+}
+
+#[test]
+fn when_ref_surface_shape_does_not_match_axes_dont_construct_object() {
+    let (xs, ys) = ([1.0, 2.0, 3.0], [10.0, 20.0]);
+    let rows: [&[f64]; 2] = [&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0, 7.0]];
+
+    let res = TwoDLookUpTableRef::new(&xs, &ys, &rows, SurfaceOrientation::RowPerX);
+    assert!(matches!(
+        res.unwrap_err(),
+        ConstructionError::RowCountMismatch { expected: 3, actual: 2 }
+    ));
+
+    let res = TwoDLookUpTableRef::new(&xs, &ys, &rows, SurfaceOrientation::RowPerY);
+    assert!(matches!(
+        res.unwrap_err(),
+        ConstructionError::RowLengthMismatch {
+            row: 1,
+            expected: 3,
+            actual: 4
+        }
+    ));
+}
+
+#[test]
+fn when_ref_surface_is_square_then_follow_given_orientation() {
+    let xs = [0.0, 1.0];
+    let rows: [&[f64]; 2] = [&[0.0, 1.0], &[10.0, 11.0]];
+    let along_x = TwoDLookUpTableRef::new(&xs, &xs, &rows, SurfaceOrientation::RowPerX).unwrap();
+    let along_y = TwoDLookUpTableRef::new(&xs, &xs, &rows, SurfaceOrientation::RowPerY).unwrap();
+
+    assert!((along_x.get(&1.0, &0.0) - 10.0).abs() < f64::EPSILON);
+    assert!((along_y.get(&1.0, &0.0) - 1.0).abs() < f64::EPSILON);
+}
+
+#[rstest]
+#[case(SurfaceInterpolation::Bilinear, 1.0, 3.5)]
+#[case(SurfaceInterpolation::Bilinear, 2.7, 12.2)]
+#[case(SurfaceInterpolation::Bicubic, 2.7, 12.2)]
+#[case(SurfaceInterpolation::Bicubic, 4.5, 19.0)]
+fn when_slice_is_monotonic_then_inverse_y_gives_back_value(
+    mut simple_increasing_surface: IncrSurface,
+    #[case] interpolation: SurfaceInterpolation,
+    #[case] x: f64,
+    #[case] z: f64,
+) {
+    simple_increasing_surface.set_interpolation(interpolation);
+    let y = simple_increasing_surface.try_inverse_y(&x, &z).unwrap();
+
+    assert!((simple_increasing_surface.get(&x, &y) - z).abs() < 1e-9);
+}
+
+#[rstest]
+fn when_value_is_outside_slice_then_inverse_y_reports_it(mut simple_increasing_surface: IncrSurface) {
+    assert_eq!(
+        simple_increasing_surface.try_inverse_y(&2.5, &7.0),
+        Err(LookUpError::ValueOutOfRange)
+    );
+    assert_eq!(
+        simple_increasing_surface.try_inverse_y(&2.5, &13.0),
+        Err(LookUpError::ValueOutOfRange)
+    );
+    // Clamped along x, so the slice is the one at the last breakpoint.
+    assert_eq!(simple_increasing_surface.try_inverse_y(&9.0, &23.0), Ok(3.0));
+
+    simple_increasing_surface.set_extrapolation(Extrapolation::Error, Extrapolation::Clamp);
+    assert!(matches!(
+        simple_increasing_surface.try_inverse_y(&9.0, &23.0),
+        Err(LookUpError::OutOfRange { axis: 0, .. })
+    ));
+    assert!(simple_increasing_surface.inverse_y_all(&9.0, &23.0).is_err());
+}
+
+#[rstest]
+#[case(AxisInterpolation::Previous)]
+#[case(AxisInterpolation::Next)]
+#[case(AxisInterpolation::Nearest)]
+fn when_y_interpolation_is_step_then_inverse_y_only_finds_breakpoints(#[case] y_method: AxisInterpolation) {
+    let mut lut = gear_map();
+    lut.set_axis_interpolation(AxisInterpolation::Linear, y_method);
+
+    // The slice at 1500 is [20.0, 30.0, 40.0].
+    assert_eq!(lut.try_inverse_y(&1500.0, &30.0), Err(LookUpError::NotMonotonic));
+    assert_eq!(lut.inverse_y_all(&1500.0, &30.0).unwrap().collect::<Vec<_>>(), [2.0]);
+    assert_eq!(lut.inverse_y_all(&1500.0, &35.0).unwrap().count(), 0);
+}
+
+// A slice along y which rises and falls, whose peak moves with x.
+fn ridge() -> TwoDLookUpTable<2, 4> {
+    TwoDLookUpTable::new([0.0, 1.0], [0.0, 1.0, 2.0, 3.0], [[0.0, 4.0, 2.0, 0.0], [0.0, 2.0, 4.0, 0.0]]).unwrap()
+}
+
+#[test]
+fn when_slice_is_not_monotonic_then_inverse_y_all_gives_every_solution() {
+    let lut = ridge();
+
+    // The slice at x = 0.5 is [0.0, 3.0, 3.0, 0.0].
+    assert_eq!(lut.try_inverse_y(&0.5, &1.5), Err(LookUpError::NotMonotonic));
+    assert!(lut.inverse_y(&0.5, &1.5).is_nan());
+    assert_eq!(lut.inverse_y_all(&0.5, &1.5).unwrap().collect::<Vec<_>>(), [0.5, 2.5]);
+    assert_eq!(lut.inverse_y_all(&0.5, &3.0).unwrap().collect::<Vec<_>>(), [1.0, 2.0]);
+    assert_eq!(lut.inverse_y_all(&0.0, &3.0).unwrap().collect::<Vec<_>>(), [0.75, 1.5]);
+    assert_eq!(lut.inverse_y_all(&0.5, &5.0).unwrap().count(), 0);
+}
+
+#[rstest]
+#[case(0.0, 3)]
+#[case(-0.2, 2)]
+#[case(0.3, 1)]
+fn when_bicubic_slice_turns_back_between_breakpoints_then_find_every_solution(#[case] z: f64, #[case] count: usize) {
+    // The slice at x = 0.5 is [0.0, 0.2, 0.4, 20.0], whose values rise, but whose cubic dips below zero
+    // between the second and the third breakpoints.
+    let mut lut =
+        TwoDLookUpTable::new([0.0, 1.0], [0.0, 1.0, 2.0, 3.0], [[0.0, 0.1, 0.2, 10.0], [0.0, 0.3, 0.6, 30.0]]).unwrap();
+    lut.set_interpolation(SurfaceInterpolation::Bicubic);
+
+    assert_eq!(lut.try_inverse_y(&0.5, &z), Err(LookUpError::NotMonotonic));
+    let solutions: Vec<f64> = lut.inverse_y_all(&0.5, &z).unwrap().collect();
+    assert_eq!(solutions.len(), count, "{solutions:?}");
+    assert!(solutions.windows(2).all(|pair| pair[0] < pair[1]), "{solutions:?}");
+    for y in &solutions {
+        assert!((lut.get(&0.5, y) - z).abs() < 1e-9, "{solutions:?}");
+    }
+
+    lut.set_interpolation(SurfaceInterpolation::Bilinear);
+    assert!(lut.try_inverse_y(&0.5, &0.3).is_ok());
+}
+
+#[test]
+fn when_ref_table_is_inverted_along_y_then_match_owned_table() {
+    let (xs, ys) = ([0.0, 1.0], [0.0, 1.0, 2.0, 3.0]);
+    let surface = [[0.0, 4.0, 2.0, 0.0], [0.0, 2.0, 4.0, 0.0]];
+    let rows: Vec<&[f64]> = surface.iter().map(|row| &row[..]).collect();
+    let borrowed = TwoDLookUpTableRef::new(&xs, &ys, &rows, SurfaceOrientation::RowPerX).unwrap();
+    let owned = ridge();
+
+    assert_eq!(
+        borrowed.inverse_y_all(&0.25, &2.0).unwrap().collect::<Vec<_>>(),
+        owned.inverse_y_all(&0.25, &2.0).unwrap().collect::<Vec<_>>()
+    );
+    assert_eq!(borrowed.try_inverse_y(&0.25, &2.0), owned.try_inverse_y(&0.25, &2.0));
+}
+
+#[rstest]
+#[case(SurfaceInterpolation::Bilinear, 1.5, -0.5)]
+#[case(SurfaceInterpolation::Bilinear, 3.9, 1.7)]
+#[case(SurfaceInterpolation::Bicubic, 2.25, 1.0)]
+#[case(SurfaceInterpolation::Bicubic, 0.3, 0.6)]
+fn when_index_is_within_cell_then_gradient_matches_finite_differences(
+    #[case] interpolation: SurfaceInterpolation,
+    #[case] x: f64,
+    #[case] y: f64,
+) {
+    let mut lut = quadratic_surface();
+    lut.set_interpolation(interpolation);
+    let h = 1e-6;
+
+    let [dx, dy] = lut.gradient(&x, &y);
+    assert!((dx - (lut.get(&(x + h), &y) - lut.get(&(x - h), &y)) / (2.0 * h)).abs() < 1e-6);
+    assert!((dy - (lut.get(&x, &(y + h)) - lut.get(&x, &(y - h))) / (2.0 * h)).abs() < 1e-6);
+}
+
+#[test]
+fn when_interpolation_is_bicubic_then_gradient_is_exact_for_quadratic_surface() {
+    // Both the breakpoints of the quadratic axis are interior, so the central differences are exact.
+    let [dx, dy] = quadratic_surface().gradient(&2.5, &0.5);
+
+    assert!((dx - (2.0 * (2.5 - 2.0) + 0.5)).abs() < 1e-9);
+    assert!((dy - (2.5 - 1.0)).abs() < 1e-9);
+}
+
+#[rstest]
+#[case(2.0, 2.5, [5.0, 1.0])]
+#[case(5.0, 5.0, [5.0, 1.0])]
+#[case(2.5, 3.0, [5.0, 1.0])]
+fn when_surface_is_bilinear_then_gradient_on_breakpoints_is_slope_of_next_segment(
+    simple_increasing_surface: IncrSurface,
+    #[case] x: f64,
+    #[case] y: f64,
+    #[case] expected: [f64; 2],
+) {
+    assert_eq!(simple_increasing_surface.gradient(&x, &y), expected);
+}
+
+#[rstest]
+#[case(Extrapolation::Clamp, Extrapolation::Clamp, 7.0, 2.5, [0.0, 1.0])]
+#[case(Extrapolation::Clamp, Extrapolation::Clamp, 7.0, -1.0, [0.0, 0.0])]
+#[case(Extrapolation::Linear, Extrapolation::Clamp, 7.0, 2.5, [5.0, 1.0])]
+#[case(Extrapolation::Periodic, Extrapolation::Linear, 6.0, 0.0, [5.0, 1.0])]
+#[case(Extrapolation::Constant(3.0), Extrapolation::Clamp, 7.0, 2.5, [0.0, 0.0])]
+fn when_index_is_outside_grid_then_differentiate_extrapolated_surface(
+    mut simple_increasing_surface: IncrSurface,
+    #[case] x_policy: Extrapolation,
+    #[case] y_policy: Extrapolation,
+    #[case] x: f64,
+    #[case] y: f64,
+    #[case] expected: [f64; 2],
+) {
+    simple_increasing_surface.set_extrapolation(x_policy, y_policy);
+
+    assert_eq!(simple_increasing_surface.gradient(&x, &y), expected);
+}
+
+#[rstest]
+fn when_extrapolation_is_nan_or_error_then_gradient_is_undefined(mut simple_increasing_surface: IncrSurface) {
+    simple_increasing_surface.set_extrapolation(Extrapolation::Clamp, Extrapolation::NaN);
+    assert!(simple_increasing_surface
+        .gradient(&2.0, &9.0)
+        .iter()
+        .all(|d| d.is_nan()));
+
+    simple_increasing_surface.set_extrapolation(Extrapolation::Clamp, Extrapolation::Error);
+    assert!(matches!(
+        simple_increasing_surface.try_gradient(&2.0, &9.0),
+        Err(LookUpError::OutOfRange { axis: 1, .. })
+    ));
+}
+
+#[test]
+fn when_y_interpolation_is_step_then_gradient_along_y_is_zero() {
+    let mut lut = gear_map();
+    lut.set_axis_interpolation(AxisInterpolation::Cubic, AxisInterpolation::Previous);
+
+    let [dx, dy] = lut.gradient(&1500.0, &2.4);
+    let h = 1e-3;
+    assert!((dx - (lut.get(&(1500.0 + h), &2.4) - lut.get(&(1500.0 - h), &2.4)) / (2.0 * h)).abs() < 1e-6);
+    assert_eq!(dy, 0.0);
+}
+
+#[test]
+fn when_ref_table_is_differentiated_then_match_owned_table() {
+    let (xs, ys) = ([0.0, 1.0], [0.0, 1.0, 2.0, 3.0]);
+    let surface = [[0.0, 4.0, 2.0, 0.0], [0.0, 2.0, 4.0, 0.0]];
+    let rows: Vec<&[f64]> = surface.iter().map(|row| &row[..]).collect();
+    let mut borrowed = TwoDLookUpTableRef::new(&xs, &ys, &rows, SurfaceOrientation::RowPerX).unwrap();
+    let mut owned = ridge();
+    borrowed.set_interpolation(SurfaceInterpolation::Bicubic);
+    owned.set_interpolation(SurfaceInterpolation::Bicubic);
+
+    for (x, y) in [(0.25, 1.5), (1.0, 2.0), (0.5, 0.0)] {
+        assert_eq!(borrowed.gradient(&x, &y), owned.gradient(&x, &y));
+    }
+}